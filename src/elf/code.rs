@@ -0,0 +1,1807 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Disassembly driven ELF analysis: gadgets, string and format string searches, entry point tracing, patching and RE tool scripts */
+
+use super::*;
+
+impl Arguments
+{
+    pub(super) fn find_rop_gadgets(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let max_depth: usize = self.gadget_depth;
+
+        let mode: arch::x86::ArchMode = match elf_obj.header.e_machine
+        {
+            EM_X86_64 => arch::x86::ArchMode::Mode64,
+            EM_386 => arch::x86::ArchMode::Mode32,
+
+            _ => {
+                eprintln!("Error - --find-gadgets only supports x86/x86_64 binaries (e_machine = {})!", return_elf_emachine(elf_obj.header.e_machine));
+                std::process::exit(-1);
+            }
+        };
+
+        let cs: Capstone = match Capstone::new().x86().mode(mode).build()
+        {
+            Ok(cs) => cs,
+
+            Err(err) => {
+                eprintln!("Error - failed to initialize the Capstone disassembler: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        /* Sequence -> index into `gadgets`, repeated hits bump the count and keep the lowest address */
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut gadgets: Vec<GadgetRecord> = Vec::new();
+        let mut total_gadgets: usize = 0;
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_flags as u32 & SHF_EXECINSTR != 0 && shdr.sh_type != SHT_NOBITS)
+        {
+            let section_name: &str = elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined");
+            let code: &[u8] = match return_section_range(shdr, data)
+            {
+                Some(code) => code,
+                None => continue
+            };
+
+            /* Section offset -> instruction decoded there, every offset is disassembled at most once */
+            let mut decoded: HashMap<usize, Option<GadgetInstruction>> = HashMap::new();
+
+            for end in 0..code.len()
+            {
+                if return_gadget_terminator(&cs, &code[end..], shdr.sh_addr.wrapping_add(end as u64)).is_none()
+                {
+                    continue;
+                }
+
+                /* Every start offset that decodes cleanly into the terminator is a distinct gadget */
+                for start in end.saturating_sub(max_depth * X86_MAX_INSN_LEN)..=end
+                {
+                    let mut instructions: Vec<String> = Vec::new();
+                    let mut position: usize = start;
+
+                    /* No other control flow on the way, and the last instruction must end exactly on the terminator */
+                    while position < end && instructions.len() < max_depth
+                    {
+                        match decoded.entry(position).or_insert_with(|| return_gadget_instruction(&cs, code, position, shdr.sh_addr))
+                        {
+                            Some(insn) if !insn.is_branch => {
+                                instructions.push(insn.text.clone());
+                                position += insn.length;
+                            },
+
+                            _ => break
+                        }
+                    }
+
+                    if position != end
+                    {
+                        continue;
+                    }
+
+                    match decoded.entry(end).or_insert_with(|| return_gadget_instruction(&cs, code, end, shdr.sh_addr))
+                    {
+                        Some(terminator) => instructions.push(terminator.text.clone()),
+                        None => continue
+                    }
+
+                    let sequence: String = instructions.join(" ; ");
+                    let address: u64 = shdr.sh_addr.wrapping_add(start as u64);
+
+                    total_gadgets += 1;
+
+                    match seen.get(&sequence)
+                    {
+                        Some(&idx) => {
+                            gadgets[idx].occurrences += 1;
+
+                            if address < gadgets[idx].address
+                            {
+                                gadgets[idx].address = address;
+                                gadgets[idx].section = section_name.to_string();
+                            }
+                        },
+
+                        None => {
+                            seen.insert(sequence.clone(), gadgets.len());
+                            gadgets.push(GadgetRecord {
+                                address,
+                                instructions: sequence,
+                                section: section_name.to_string(),
+                                occurrences: 1
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        gadgets.sort_by_key(|gadget| gadget.address);
+
+        let report: GadgetReport = GadgetReport { total_gadgets, unique_sequences: gadgets.len(), gadgets };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("gadgets", &report);
+            return;
+        }
+
+        println!("\nROP Gadgets =>");
+
+        let mut gadget_table: Table = self.new_table();
+
+        gadget_table.set_header(vec![
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Instructions").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Hits").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for gadget in &report.gadgets
+        {
+            gadget_table.add_row(vec![
+                Cell::new(format!("{:#x}", gadget.address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&gadget.instructions).fg(Color::Yellow),
+                Cell::new(&gadget.section).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(gadget.occurrences)
+            ]);
+        }
+
+        println!("\n{gadget_table}");
+        println!("\n{} gadgets found, {} unique sequences (max depth {}).", report.total_gadgets, report.unique_sequences, max_depth);
+    }
+
+    /* strings(1) style printable runs, but scanned per section so every hit carries its section */
+    pub(super) fn find_string(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let pattern: &str = match self.get_flag_values("--find-string").first()
+        {
+            Some(pattern) => pattern,
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --find-string <regex> [--case-sensitive]");
+                std::process::exit(-1);
+            }
+        };
+
+        let case_sensitive: bool = self.flags.iter().any(|flag| flag == "--case-sensitive");
+
+        let regex: Regex = match RegexBuilder::new(pattern).case_insensitive(!case_sensitive).build()
+        {
+            Ok(regex) => regex,
+
+            Err(err) => {
+                eprintln!("Error - invalid regular expression \"{pattern}\": {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        let mut matches: Vec<FoundStringRecord> = Vec::new();
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_size > 0)
+        {
+            let section_bytes: &[u8] = match return_section_range(shdr, data)
+            {
+                Some(bytes) => bytes,
+                None => continue
+            };
+
+            let section_name: &str = elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined");
+
+            for (run_start, run) in return_printable_runs(section_bytes).into_iter().filter(|(_, run)| regex.is_match(run))
+            {
+                matches.push(FoundStringRecord {
+                    string: run,
+                    offset: shdr.sh_offset + run_start as u64,
+                    section: section_name.to_string(),
+                    executable: shdr.sh_flags & SHF_EXECINSTR as u64 != 0
+                });
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("found_strings", &matches);
+            return;
+        }
+
+        println!("\nString Matches =>");
+
+        let mut string_table: Table = self.new_table();
+
+        string_table.set_header(vec![
+                Cell::new("String").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &matches
+        {
+            /* Strings embedded in code are rarer and usually more interesting than data strings */
+            let section_cell: Cell = match record.executable
+            {
+                true => Cell::new(format!("{} (code)", record.section)).fg(Color::Red).add_attribute(Attribute::Bold),
+                false => Cell::new(&record.section).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            };
+
+            string_table.add_row(vec![
+                Cell::new(&record.string).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", record.offset)),
+                section_cell
+            ]);
+        }
+
+        println!("\n{string_table}");
+        println!("\n{} matches ({}).", matches.len(), match case_sensitive { true => "case sensitive", false => "case insensitive" });
+    }
+
+    /* The symbol list is inlined so the script runs standalone from File > Script file... in IDA */
+    pub(super) fn emit_ida_script(&self, elf_obj: &Elf)
+    {
+        let symbols: Vec<(u64, String)> = return_script_symbols(elf_obj, self.flags.iter().any(|flag| flag == "--demangle"));
+
+        println!("# IDAPython script generated by BinaryMagic for {}", self.file);
+        println!("# {} symbols, addresses are the unrelocated ELF virtual addresses\n", symbols.len());
+        println!("import idc");
+        println!("import idaapi\n");
+        println!("SYMBOLS = [");
+
+        for (address, name) in &symbols
+        {
+            /* A JSON string literal is also a valid Python string literal */
+            println!("    ({:#x}, {}),", address, serde_json::to_string(name).unwrap_or_default());
+        }
+
+        println!("]\n\n");
+        println!("def main():");
+        println!("    named = 0\n");
+        println!("    for address, name in SYMBOLS:");
+        println!("        # SN_NOCHECK lets IDA replace characters it does not allow in names (demangled ::, <>, ...)");
+        println!("        if idc.set_name(address, name, idc.SN_NOWARN | idc.SN_NOCHECK):");
+        println!("            named += 1\n");
+        println!("    idaapi.msg(\"BinaryMagic: named %d of %d symbols\\n\" % (named, len(SYMBOLS)))\n\n");
+        println!("if __name__ == \"__main__\":");
+        println!("    main()");
+    }
+
+    /* Ghidra rebases PIE images (0x100000 by default), the script shifts every address by the difference to the ELF base */
+    pub(super) fn emit_ghidra_script(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let symbols: Vec<(u64, String)> = return_script_symbols(elf_obj, self.flags.iter().any(|flag| flag == "--demangle"));
+        let line_rows: Vec<(u64, String, u64)> = return_dwarf_line_rows(elf_obj, data);
+
+        let elf_base: u64 = elf_obj.program_headers.iter()
+            .filter(|phdr| phdr.p_type == PT_LOAD)
+            .map(|phdr| phdr.p_vaddr & !(LOAD_PAGE_SIZE - 1))
+            .min()
+            .unwrap_or(0);
+
+        /* Ghidra refuses a second symbol of the same name in one namespace, repeats become name_1, name_2, ... skipping labels already taken */
+        let mut labels: HashSet<String> = HashSet::new();
+        let mut repeats: HashMap<String, usize> = HashMap::new();
+
+        println!("# Ghidra script generated by BinaryMagic for {}", self.file);
+        println!("# @category BinaryMagic");
+        println!("# {} symbols, run it from the Script Manager with the binary open\n", symbols.len());
+        println!("from ghidra.program.model.symbol import SourceType\n");
+        println!("ELF_BASE = {:#x}\n", elf_base);
+        println!("SYMBOLS = [");
+
+        for (address, name) in &symbols
+        {
+            let mut label: String = name.clone();
+
+            while !labels.insert(label.clone())
+            {
+                let count: &mut usize = repeats.entry(name.clone()).or_insert(0);
+                *count += 1;
+
+                label = format!("{name}_{count}");
+            }
+
+            /* Source location of the first line row at the symbol address, when DWARF is present */
+            let idx: usize = line_rows.partition_point(|row| row.0 < *address);
+            let comment: String = match line_rows.get(idx).filter(|row| row.0 == *address)
+            {
+                Some((_, file, line)) => serde_json::to_string(&format!("{file}:{line}")).unwrap_or_default(),
+                None => String::from("None")
+            };
+
+            println!("    ({:#x}, {}, {}),", address, serde_json::to_string(&label).unwrap_or_default(), comment);
+        }
+
+        println!("]\n\n");
+        println!("def main():");
+        println!("    offset = currentProgram.getImageBase().getOffset() - ELF_BASE");
+        println!("    labelled = 0\n");
+        println!("    for address, name, comment in SYMBOLS:");
+        println!("        target = toAddr(address + offset)\n");
+        println!("        if createLabel(target, name, True, SourceType.IMPORTED) is not None:");
+        println!("            labelled += 1\n");
+        println!("        if comment is not None:");
+        println!("            setPreComment(target, comment)\n");
+        println!("    print(\"BinaryMagic: labelled %d of %d symbols\" % (labelled, len(SYMBOLS)))\n\n");
+        println!("main()");
+    }
+
+    pub(super) fn patch_byte(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let parse_hex = |value: &String| -> Option<u64> { u64::from_str_radix(value.trim_start_matches("0x").trim_start_matches("0X"), 16).ok() };
+
+        let (offset, value): (u64, u8) = match self.get_flag_values("--patch-byte")
+        {
+            [offset, value] => match (parse_hex(offset), parse_hex(value).and_then(|value| u8::try_from(value).ok()))
+            {
+                (Some(offset), Some(value)) => (offset, value),
+
+                _ => {
+                    eprintln!("Error - usage: binarymagic <TARGET> --patch-byte <hex-offset> <hex-value> (value 0x00-0xff)");
+                    std::process::exit(-1);
+                }
+            },
+
+            _ => {
+                eprintln!("Error - usage: binarymagic <TARGET> --patch-byte <hex-offset> <hex-value>");
+                std::process::exit(-1);
+            }
+        };
+
+        if offset >= data.len() as u64
+        {
+            eprintln!("Error - offset {:#x} is past the end of the file ({:#x} bytes)!", offset, data.len());
+            std::process::exit(-1);
+        }
+
+        let mut patched: Vec<u8> = data.to_vec();
+        patched[offset as usize] = value;
+
+        let output: String = format!("{}.{:#x}.patched", self.file, offset);
+
+        let parse_error: Option<String> = Elf::parse(&patched).err().map(|err| err.to_string());
+
+        let section = elf_obj.section_headers.iter()
+            .find(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_offset <= offset && offset - shdr.sh_offset < shdr.sh_size);
+
+        let address: Option<u64> = section.filter(|shdr| shdr.sh_flags & SHF_ALLOC as u64 != 0).map(|shdr| shdr.sh_addr.wrapping_add(offset - shdr.sh_offset));
+
+        /* Instructions are decoded only inside executable sections on the architectures Capstone is set up for */
+        let (original_instruction, patched_instruction): (Option<String>, Option<String>) = match (section, address)
+        {
+            (Some(shdr), Some(address)) if shdr.sh_flags as u32 & SHF_EXECINSTR != 0 => match return_elf_capstone(elf_obj, false)
+            {
+                Ok(cs) => (
+                    return_section_range(shdr, data).and_then(|code| return_instruction_at(elf_obj, &cs, code, shdr.sh_addr, address)),
+                    return_section_range(shdr, &patched).and_then(|code| return_instruction_at(elf_obj, &cs, code, shdr.sh_addr, address))
+                ),
+
+                Err(_) => (None, None)
+            },
+
+            _ => (None, None)
+        };
+
+        let record: PatchByteRecord = PatchByteRecord {
+            offset,
+            original: data[offset as usize],
+            patched: value,
+            section: section.map(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("?").to_string()),
+            address,
+            function: address.and_then(|address| return_function_at(elf_obj, address)),
+            original_instruction,
+            patched_instruction,
+            output,
+            valid: parse_error.is_none(),
+            parse_error
+        };
+
+        /* Everything above only reads, the copy is written once the patch is known to be reportable */
+        if let Err(err) = fs::write(&record.output, &patched)
+        {
+            eprintln!("Error - unable to write \"{}\": {err}", record.output);
+            std::process::exit(-1);
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("patch_byte", &record);
+            return;
+        }
+
+        let mnemonic = |instruction: &Option<String>| -> String {
+            instruction.as_ref().and_then(|text| text.split_whitespace().next()).map_or(String::new(), |mnemonic| format!(" ({mnemonic})"))
+        };
+
+        println!("\nPatched {:#x}: {:#04x}{} -> {:#04x}{}",
+            record.offset,
+            record.original,
+            mnemonic(&record.original_instruction),
+            record.patched,
+            mnemonic(&record.patched_instruction)
+        );
+
+        if record.original == record.patched
+        {
+            println!("(the byte already had this value, the copy is identical)");
+        }
+
+        match (&record.section, record.address)
+        {
+            (Some(section), Some(address)) => println!("\nLocation: {} (address {:#x}{})",
+                section,
+                address,
+                record.function.as_ref().map_or(String::new(), |function| format!(", {function}"))
+            ),
+
+            (Some(section), None) => println!("\nLocation: {section} (not loaded into memory)"),
+            _ => println!("\nLocation: not covered by any section (headers or padding)")
+        }
+
+        if let (Some(before), Some(after)) = (&record.original_instruction, &record.patched_instruction)
+        {
+            println!("Before: {before}");
+            println!("After:  {after}");
+        }
+
+        /* 16 bytes around the patch with the modified byte bracketed */
+        let start: usize = (offset as usize).saturating_sub(8);
+        let end: usize = (offset as usize + 8).min(patched.len());
+
+        let context: Vec<String> = (start..end).map(|position| match position == offset as usize
+        {
+            true => format!("[{:02x}]", patched[position]),
+            false => format!("{:02x}", patched[position])
+        }).collect();
+
+        println!("Context: {:#x}: {}", start, context.join(" "));
+
+        match &record.parse_error
+        {
+            None => println!("\nWrote {} (still parses as ELF)", record.output),
+            Some(err) => println!("\nWrote {}\nWARNING: the patched file no longer parses as ELF: {err}", record.output)
+        }
+    }
+
+    /*
+        glibc's _start passes main to __libc_start_main as its first argument (rdi, x0, or the last push on i386),
+        the library call itself cannot be followed so main is taken from that argument
+    */
+    pub(super) fn trace_entry_to_main(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let cs: Capstone = match return_elf_capstone(elf_obj, false)
+        {
+            Ok(cs) => cs,
+
+            Err(err) => {
+                eprintln!("Error - failed to initialize the Capstone disassembler: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        if elf_obj.entry == 0
+        {
+            eprintln!("Error - binary has no entry point (e_entry = 0)!");
+            std::process::exit(-1);
+        }
+
+        let argument_register: &str = match elf_obj.header.e_machine { EM_AARCH64 => "x0", _ => "rdi" };
+        let plt_stubs: HashMap<u64, String> = return_plt_stub_names(elf_obj, data, &cs);
+
+        let main_symbol: Option<u64> = elf_obj.syms.iter().map(|symbol| (symbol, &elf_obj.strtab))
+            .chain(elf_obj.dynsyms.iter().map(|symbol| (symbol, &elf_obj.dynstrtab)))
+            .find(|(symbol, strtab)| symbol.st_type() == STT_FUNC && symbol.st_shndx != SHN_UNDEF as usize && strtab.get_at(symbol.st_name) == Some("main"))
+            .map(|(symbol, _)| symbol.st_value);
+
+        let name_of = |address: u64| -> String {
+            plt_stubs.get(&address).map(|name| format!("{name}@plt"))
+                .or_else(|| return_symbol_reference(elf_obj, address))
+                .unwrap_or_else(|| format!("sub_{address:x}"))
+        };
+
+        let mut record: EntryToMainRecord = EntryToMainRecord { entry: elf_obj.entry, steps: Vec::new(), main: None, stopped: None };
+        let mut current: u64 = elf_obj.entry;
+        let mut caller: String = return_function_at(elf_obj, current).unwrap_or_else(|| String::from("_start"));
+
+        while record.main.is_none() && record.stopped.is_none()
+        {
+            if record.steps.len() >= ENTRY_MAIN_MAX_DEPTH
+            {
+                record.stopped = Some(format!("more than {ENTRY_MAIN_MAX_DEPTH} calls without reaching main"));
+                break;
+            }
+
+            let code: Option<&[u8]> = elf_obj.section_headers.iter()
+                .find(|shdr| shdr.sh_flags as u32 & SHF_EXECINSTR != 0 && shdr.sh_type != SHT_NOBITS && shdr.sh_addr <= current && current - shdr.sh_addr < shdr.sh_size)
+                .and_then(|shdr| return_section_range(shdr, data).and_then(|bytes| bytes.get((current - shdr.sh_addr) as usize..)));
+
+            let insns = match code.and_then(|code| cs.disasm_count(code, current, ENTRY_MAIN_MAX_INSNS).ok())
+            {
+                Some(insns) => insns,
+
+                None => {
+                    record.stopped = Some(format!("{current:#x} is not inside an executable section"));
+                    break;
+                }
+            };
+
+            /* Addresses materialized into registers and pushed on the stack so far in this function */
+            let mut registers: HashMap<String, u64> = HashMap::new();
+            let mut pushed: Vec<u64> = Vec::new();
+            let mut pages: HashMap<RegId, u64> = HashMap::new();
+            let mut call: Option<(u64, u64)> = None;
+
+            for insn in insns.iter()
+            {
+                let operands: Vec<arch::ArchOperand> = match cs.insn_detail(insn)
+                {
+                    Ok(detail) => detail.arch_detail().operands(),
+                    Err(_) => continue
+                };
+
+                let mnemonic: &str = insn.mnemonic().unwrap_or("");
+                let targets: Vec<u64> = return_operand_targets(insn, &operands, &mut pages);
+
+                if matches!(mnemonic, "call" | "bl" | "blr")
+                {
+                    match targets.first()
+                    {
+                        Some(target) => call = Some((insn.address(), *target)),
+                        None => record.stopped = Some(format!("indirect call at {:#x} ({} {})", insn.address(), mnemonic, insn.op_str().unwrap_or("")))
+                    }
+
+                    break;
+                }
+
+                if matches!(mnemonic, "ret" | "hlt")
+                {
+                    record.stopped = Some(format!("{caller} returns without a call"));
+                    break;
+                }
+
+                if mnemonic == "push"
+                {
+                    pushed.push(targets.first().copied().unwrap_or(0));
+                    continue;
+                }
+
+                let written: Option<String> = operands.first().and_then(|operand| match operand
+                {
+                    arch::ArchOperand::X86Operand(operand) => match operand.op_type
+                    {
+                        arch::x86::X86OperandType::Reg(reg) => cs.reg_name(reg),
+                        _ => None
+                    },
+
+                    arch::ArchOperand::Arm64Operand(operand) => match operand.op_type
+                    {
+                        arch::arm64::Arm64OperandType::Reg(reg) => cs.reg_name(reg),
+                        _ => None
+                    },
+
+                    _ => None
+                }).map(|name| return_register_family(&name));
+
+                if let Some(register) = written
+                {
+                    match targets.first()
+                    {
+                        Some(target) => registers.insert(register, *target),
+                        None => registers.remove(&register)
+                    };
+                }
+            }
+
+            let (call_address, target): (u64, u64) = match call
+            {
+                Some(call) => call,
+
+                None => {
+                    record.stopped.get_or_insert_with(|| format!("no call within the first {ENTRY_MAIN_MAX_INSNS} instructions of {caller}"));
+                    break;
+                }
+            };
+
+            let callee: String = name_of(target);
+            let callee_name: &str = callee.split('@').next().unwrap_or("");
+
+            record.steps.push(EntryCallRecord { call_address, caller: caller.clone(), callee: callee.clone(), target });
+
+            if Some(target) == main_symbol
+            {
+                record.main = Some(target);
+                break;
+            }
+
+            /* Static glibc names the definition __libc_start_main_impl */
+            if callee_name.starts_with("__libc_start_main")
+            {
+                let argument: Option<u64> = match elf_obj.header.e_machine
+                {
+                    EM_386 => pushed.last().copied(),
+                    _ => registers.get(argument_register).copied()
+                };
+
+                match argument.filter(|address| *address != 0)
+                {
+                    Some(address) => record.main = Some(address),
+                    None => record.stopped = Some(String::from("the main argument of __libc_start_main is not a constant address"))
+                }
+
+                break;
+            }
+
+            /* Only code in this file can be followed, other imports end the trace */
+            if plt_stubs.contains_key(&target) || callee.ends_with("@got")
+            {
+                record.stopped = Some(format!("{callee} is a library function"));
+                break;
+            }
+
+            current = target;
+            caller = callee;
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("entry_to_main", &record);
+            return;
+        }
+
+        println!("\nEntry Point to main =>");
+
+        let mut step_table: Table = self.new_table();
+
+        step_table.set_header(vec![
+                Cell::new("Call Site").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Caller").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Callee").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Target").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for step in &record.steps
+        {
+            step_table.add_row(vec![
+                Cell::new(format!("{:#x}", step.call_address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&step.caller).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&step.callee).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", step.target))
+            ]);
+        }
+
+        println!("\n{step_table}");
+
+        let mut chain: String = format!("{:#x}: {}", record.entry, record.steps.first().map_or("_start", |step| step.caller.as_str()));
+
+        for step in &record.steps
+        {
+            chain += &format!(" -> calls {} @ {:#x}", step.callee, step.target);
+        }
+
+        match (record.main, &record.stopped)
+        {
+            (Some(main), _) if record.steps.last().is_some_and(|step| step.target == main) => (),
+            (Some(main), _) => chain += &format!(" -> eventually calls {} @ {:#x}", return_function_at(elf_obj, main).unwrap_or_else(|| String::from("main (stripped)")), main),
+            (None, Some(reason)) => chain += &format!(" -> stopped: {reason}"),
+            (None, None) => ()
+        }
+
+        println!("\n{chain}");
+    }
+
+    pub(super) fn show_entry_point_context(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let depth: usize = self.entry_depth;
+
+        let entry: u64 = elf_obj.entry;
+
+        if entry == 0
+        {
+            eprintln!("Error - binary has no entry point (e_entry = 0)!");
+            std::process::exit(-1);
+        }
+
+        /* ARM entry points with bit 0 set start in Thumb state */
+        let thumb: bool = elf_obj.header.e_machine == EM_ARM && entry & 1 != 0;
+        let entry: u64 = entry & !(thumb as u64);
+
+        let code: &[u8] = match elf_obj.program_headers.iter()
+            .find(|phdr| phdr.p_type == PT_LOAD && phdr.p_vaddr <= entry && entry - phdr.p_vaddr < phdr.p_filesz)
+            .and_then(|phdr| {
+                let start: u64 = phdr.p_offset.checked_add(entry - phdr.p_vaddr)?;
+                let end: u64 = phdr.p_offset.checked_add(phdr.p_filesz)?;
+
+                data.get(usize::try_from(start).ok()?..usize::try_from(end).ok()?)
+            })
+        {
+            Some(code) => code,
+
+            None => {
+                eprintln!("Error - e_entry {entry:#x} is not backed by file data in any PT_LOAD segment!");
+                std::process::exit(-1);
+            }
+        };
+
+        let cs: Capstone = match return_elf_capstone(elf_obj, thumb)
+        {
+            Ok(cs) => cs,
+
+            Err(err) => {
+                eprintln!("Error - {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        let insns = match cs.disasm_count(&code[..code.len().min(depth * X86_MAX_INSN_LEN)], entry, depth)
+        {
+            Ok(insns) => insns,
+
+            Err(err) => {
+                eprintln!("Error - unable to disassemble the entry point: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        let records: Vec<EntryInstructionRecord> = insns.iter().map(|insn| {
+            let next: u64 = insn.address() + insn.len() as u64;
+
+            /* Branch/call immediates and x86 rip relative operands are the targets worth naming */
+            let targets: Vec<u64> = cs.insn_detail(insn).map(|detail| detail.arch_detail().operands()).unwrap_or_default().iter()
+                .filter_map(|operand| match operand
+                {
+                    arch::ArchOperand::X86Operand(operand) => match &operand.op_type
+                    {
+                        arch::x86::X86OperandType::Imm(imm) => Some(*imm as u64),
+                        arch::x86::X86OperandType::Mem(mem) if mem.base() == RegId(arch::x86::X86Reg::X86_REG_RIP as RegIdInt) => Some(next.wrapping_add(mem.disp() as u64)),
+                        arch::x86::X86OperandType::Mem(mem) if mem.base().0 == 0 && mem.index().0 == 0 => Some(mem.disp() as u64),
+
+                        _ => None
+                    },
+
+                    arch::ArchOperand::Arm64Operand(operand) => match operand.op_type
+                    {
+                        arch::arm64::Arm64OperandType::Imm(imm) => Some(imm as u64),
+                        _ => None
+                    },
+
+                    arch::ArchOperand::ArmOperand(operand) => match operand.op_type
+                    {
+                        arch::arm::ArmOperandType::Imm(imm) => Some(imm as u32 as u64),
+                        _ => None
+                    },
+
+                    _ => None
+                })
+                .collect();
+
+            EntryInstructionRecord {
+                address: insn.address(),
+                bytes: insn.bytes().iter().map(|b| format!("{b:02x}")).collect::<Vec<String>>().join(" "),
+                instruction: format!("{} {}", insn.mnemonic().unwrap_or(""), insn.op_str().unwrap_or("")).trim_end().to_string(),
+                reference: targets.iter().find_map(|target| return_symbol_reference(elf_obj, *target))
+            }
+        })
+        .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("entry_point_context", &records);
+            return;
+        }
+
+        println!("\nEntry Point {:#x} ({}) =>", elf_obj.entry, return_function_at(elf_obj, entry).unwrap_or(String::from("no symbol")));
+
+        let mut entry_table: Table = self.new_table();
+
+        entry_table.set_header(vec![
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Bytes").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Instruction").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Reference").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            entry_table.add_row(vec![
+                Cell::new(format!("{:#x}", record.address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.bytes),
+                Cell::new(&record.instruction).fg(Color::Yellow),
+                Cell::new(record.reference.as_deref().unwrap_or("")).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{entry_table}");
+
+        if records.len() < depth
+        {
+            println!("\nStopped after {} instructions (end of segment or undecodable bytes).", records.len());
+        }
+    }
+
+    pub(super) fn xref_string(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let literal: &str = match self.get_flag_values("--xref-string").first()
+        {
+            Some(literal) if !literal.is_empty() => literal,
+
+            _ => {
+                eprintln!("Error - usage: binarymagic <TARGET> --xref-string <string>");
+                std::process::exit(-1);
+            }
+        };
+
+        if !matches!(elf_obj.header.e_machine, EM_X86_64 | EM_386 | EM_AARCH64)
+        {
+            eprintln!("Error - --xref-string only supports x86, x86_64 and AArch64 binaries (e_machine = {})!", return_elf_emachine(elf_obj.header.e_machine));
+            std::process::exit(-1);
+        }
+
+        let cs: Capstone = match return_elf_capstone(elf_obj, false)
+        {
+            Ok(cs) => cs,
+
+            Err(err) => {
+                eprintln!("Error - failed to initialize the Capstone disassembler: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        /* Code references the start of the printable run, a literal sitting inside it (tail merged strings) gets its own address too */
+        let mut targets: HashMap<u64, String> = HashMap::new();
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_flags & SHF_ALLOC as u64 != 0)
+        {
+            let section_bytes: &[u8] = match return_section_range(shdr, data)
+            {
+                Some(bytes) => bytes,
+                None => continue
+            };
+
+            for (run_start, run) in return_printable_runs(section_bytes)
+            {
+                if let Some(position) = run.find(literal)
+                {
+                    targets.insert(shdr.sh_addr.wrapping_add(run_start as u64), run.clone());
+                    targets.insert(shdr.sh_addr.wrapping_add((run_start + position) as u64), run[position..].to_string());
+                }
+            }
+        }
+
+        if targets.is_empty()
+        {
+            eprintln!("Error - \"{literal}\" was not found in any loaded section!");
+            std::process::exit(-1);
+        }
+
+        let mut records: Vec<StringXrefRecord> = Vec::new();
+
+        /* AArch64 materializes most addresses as adrp (page) + add (low 12 bits), pages are tracked per destination register */
+        let mut pages: HashMap<RegId, u64> = HashMap::new();
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_flags as u32 & SHF_EXECINSTR != 0 && shdr.sh_type != SHT_NOBITS)
+        {
+            let code: &[u8] = match return_section_range(shdr, data)
+            {
+                Some(code) => code,
+                None => continue
+            };
+
+            /* Linear sweep, capstone stops at undecodable bytes so the sweep resumes right behind them */
+            let step: usize = match elf_obj.header.e_machine { EM_AARCH64 => 4, _ => 1 };
+            let mut position: usize = 0;
+
+            while position < code.len()
+            {
+                let insns = match cs.disasm_all(&code[position..], shdr.sh_addr.wrapping_add(position as u64))
+                {
+                    Ok(insns) if !insns.is_empty() => insns,
+                    _ => { position += step; continue; }
+                };
+
+                for insn in insns.iter()
+                {
+                    let operands: Vec<arch::ArchOperand> = match cs.insn_detail(insn)
+                    {
+                        Ok(detail) => detail.arch_detail().operands(),
+                        Err(_) => continue
+                    };
+
+                    let target: Option<u64> = return_operand_targets(insn, &operands, &mut pages).into_iter().find(|target| targets.contains_key(target));
+
+                    if let Some((target, string)) = target.and_then(|target| targets.get(&target).map(|string| (target, string)))
+                    {
+                        records.push(StringXrefRecord {
+                            string: string.clone(),
+                            string_address: target,
+                            instruction_address: insn.address(),
+                            instruction: format!("{} {}", insn.mnemonic().unwrap_or(""), insn.op_str().unwrap_or("")),
+                            function: return_function_at(elf_obj, insn.address())
+                        });
+                    }
+                }
+
+                let last = insns.iter().last().map_or(0, |insn| insn.address() + insn.len() as u64 - shdr.sh_addr);
+                position = last as usize + step;
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("string_xrefs", &records);
+            return;
+        }
+
+        println!("\nString References =>");
+
+        if records.is_empty()
+        {
+            println!("\n* No instruction loads the address of \"{literal}\" ({} occurrence(s) found).", targets.len());
+            return;
+        }
+
+        let mut xref_table: Table = self.new_table();
+
+        xref_table.set_header(vec![
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Function").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Instruction").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("String").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            xref_table.add_row(vec![
+                Cell::new(format!("{:#x}", record.instruction_address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(record.function.as_deref().unwrap_or("?")).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&record.instruction),
+                Cell::new(format!("{:#x} \"{}\"", record.string_address, record.string)).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{xref_table}");
+        println!("\n{} references.", records.len());
+    }
+
+    /*
+        A printf family call whose format argument comes from .rodata is safe, one loaded from a register or memory
+        (printf(buf)) may carry user data. The argument registers are the SysV x86_64 and AAPCS64 ones.
+    */
+    pub(super) fn find_format_strings(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let argument_registers: [&str; 6] = match elf_obj.header.e_machine
+        {
+            EM_X86_64 => ["rdi", "rsi", "rdx", "rcx", "r8", "r9"],
+            EM_AARCH64 => ["x0", "x1", "x2", "x3", "x4", "x5"],
+
+            machine => {
+                eprintln!("Error - --find-format-strings only supports x86_64 and AArch64 binaries (e_machine = {})!", return_elf_emachine(machine));
+                std::process::exit(-1);
+            }
+        };
+
+        let cs: Capstone = match return_elf_capstone(elf_obj, false)
+        {
+            Ok(cs) => cs,
+
+            Err(err) => {
+                eprintln!("Error - failed to initialize the Capstone disassembler: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        let mut strings: Vec<FormatStringRecord> = return_format_strings(elf_obj, data).into_iter()
+            .map(|(address, string)| FormatStringRecord { address, has_percent_n: string.contains("%n"), string, references: Vec::new(), printf_calls: 0 })
+            .collect();
+
+        let string_index: HashMap<u64, usize> = strings.iter().enumerate().map(|(index, record)| (record.address, index)).collect();
+        let plt_stubs: HashMap<u64, String> = return_plt_stub_names(elf_obj, data, &cs);
+
+        let is_constant = |address: u64| -> bool {
+            elf_obj.section_headers.iter().any(|shdr| {
+                shdr.sh_flags & SHF_ALLOC as u64 != 0
+                    && shdr.sh_flags & (SHF_WRITE | SHF_EXECINSTR) as u64 == 0
+                    && shdr.sh_addr <= address && address - shdr.sh_addr < shdr.sh_size
+            })
+        };
+
+        /* call/bl target -> printf family name and format argument index */
+        let callee = |target: u64| -> Option<(String, usize)> {
+            let name: String = plt_stubs.get(&target).cloned()
+                .or_else(|| return_symbol_reference(elf_obj, target).filter(|name| !name.contains('+')))?;
+            let name: &str = name.split('@').next().unwrap_or("");
+
+            PRINTF_FAMILY.iter().find(|(function, _)| *function == name).map(|(function, index)| (function.to_string(), *index))
+        };
+
+        let mut calls: Vec<FormatCallRecord> = Vec::new();
+        let mut pages: HashMap<RegId, u64> = HashMap::new();
+
+        /* The PLT stubs jump to the imports themselves, they are not callers */
+        let is_plt = |sh_name: usize| -> bool { elf_obj.shdr_strtab.get_at(sh_name).is_some_and(|name| name.starts_with(".plt")) };
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_flags as u32 & SHF_EXECINSTR != 0 && shdr.sh_type != SHT_NOBITS && !is_plt(shdr.sh_name))
+        {
+            let code: &[u8] = match return_section_range(shdr, data)
+            {
+                Some(code) => code,
+                None => continue
+            };
+
+            let mut window: VecDeque<FormatWindowEntry> = VecDeque::new();
+            let step: usize = match elf_obj.header.e_machine { EM_AARCH64 => 4, _ => 1 };
+            let mut position: usize = 0;
+
+            while position < code.len()
+            {
+                let insns = match cs.disasm_all(&code[position..], shdr.sh_addr.wrapping_add(position as u64))
+                {
+                    Ok(insns) if !insns.is_empty() => insns,
+                    _ => { position += step; window.clear(); continue; }
+                };
+
+                for insn in insns.iter()
+                {
+                    let operands: Vec<arch::ArchOperand> = match cs.insn_detail(insn)
+                    {
+                        Ok(detail) => detail.arch_detail().operands(),
+                        Err(_) => continue
+                    };
+
+                    let text: String = format!("{} {}", insn.mnemonic().unwrap_or(""), insn.op_str().unwrap_or("")).trim_end().to_string();
+                    let targets: Vec<u64> = return_operand_targets(insn, &operands, &mut pages);
+
+                    for target in &targets
+                    {
+                        if let Some(&index) = string_index.get(target)
+                        {
+                            strings[index].references.push(insn.address());
+                        }
+                    }
+
+                    let call_target: Option<u64> = match insn.mnemonic().unwrap_or("")
+                    {
+                        "call" | "bl" | "jmp" | "b" => targets.first().copied(),
+                        _ => None
+                    };
+
+                    if let Some((name, argument)) = call_target.and_then(&callee)
+                    {
+                        let format_register: &str = argument_registers[argument];
+
+                        /* Unoptimized code goes through a scratch register (lea rax, [rip + x]; mov rdi, rax), register copies are followed */
+                        let mut wanted: String = format_register.to_string();
+                        let mut source: Option<&FormatWindowEntry> = None;
+
+                        for entry in window.iter().rev()
+                        {
+                            if entry.written.as_deref() != Some(wanted.as_str())
+                            {
+                                continue;
+                            }
+
+                            source = Some(entry);
+
+                            match &entry.copied_from
+                            {
+                                Some(copied_from) => wanted = copied_from.clone(),
+                                None => break
+                            }
+                        }
+
+                        let format_address: Option<u64> = source.and_then(|entry| entry.targets.iter().copied().find(|target| is_constant(*target)));
+                        let format_string: Option<String> = format_address.and_then(|address| string_index.get(&address)).map(|&index| {
+                            strings[index].printf_calls += 1;
+                            strings[index].string.clone()
+                        });
+
+                        let verdict: &str = match (source, format_address)
+                        {
+                            (Some(_), Some(_)) => "constant",
+                            (Some(_), None) => "suspicious",
+                            (None, _) => "potential false positive"
+                        };
+
+                        calls.push(FormatCallRecord {
+                            address: insn.address(),
+                            function: return_function_at(elf_obj, insn.address()),
+                            callee: name,
+                            format_register: format_register.to_string(),
+                            format_source: source.map(|entry| entry.text.clone()),
+                            format_string,
+                            verdict: verdict.to_string()
+                        });
+                    }
+
+                    let registers: Vec<Option<String>> = operands.iter().map(|operand| match operand
+                    {
+                        arch::ArchOperand::X86Operand(operand) => match operand.op_type
+                        {
+                            arch::x86::X86OperandType::Reg(reg) => cs.reg_name(reg),
+                            _ => None
+                        },
+
+                        arch::ArchOperand::Arm64Operand(operand) => match operand.op_type
+                        {
+                            arch::arm64::Arm64OperandType::Reg(reg) => cs.reg_name(reg),
+                            _ => None
+                        },
+
+                        _ => None
+                    }.map(|name| return_register_family(&name))).collect();
+
+                    let written: Option<String> = registers.first().cloned().flatten();
+
+                    let copied_from: Option<String> = match (insn.mnemonic().unwrap_or(""), registers.as_slice())
+                    {
+                        ("mov", [Some(_), Some(source)]) => Some(source.clone()),
+                        _ => None
+                    };
+
+                    window.push_back(FormatWindowEntry { text, written, copied_from, targets });
+
+                    if window.len() > FORMAT_CALL_WINDOW
+                    {
+                        window.pop_front();
+                    }
+                }
+
+                let last = insns.iter().last().map_or(0, |insn| insn.address() + insn.len() as u64 - shdr.sh_addr);
+                position = last as usize + step;
+            }
+        }
+
+        let report: FormatStringReport = FormatStringReport { strings, calls };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("format_strings", &report);
+            return;
+        }
+
+        println!("\nFormat Strings (.rodata) =>");
+
+        let mut string_table: Table = self.new_table();
+
+        string_table.set_header(vec![
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("String").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("References").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("printf Calls").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Notes").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &report.strings
+        {
+            string_table.add_row(vec![
+                Cell::new(format!("{:#x}", record.address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:?}", record.string)).fg(Color::Yellow),
+                Cell::new(record.references.len()),
+                Cell::new(record.printf_calls),
+                Cell::new(match record.has_percent_n { true => "%n (writes memory)", false => "" }).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{string_table}");
+
+        println!("\nprintf Family Calls =>");
+
+        let mut call_table: Table = self.new_table();
+
+        call_table.set_header(vec![
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Function").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Callee").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Format Argument").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Verdict").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for call in &report.calls
+        {
+            let argument: String = match (&call.format_string, &call.format_source)
+            {
+                (Some(string), _) => format!("{} = {:?}", call.format_register, string),
+                (None, Some(source)) => format!("{} <- {}", call.format_register, source),
+                (None, None) => format!("{} (not set within {} instructions)", call.format_register, FORMAT_CALL_WINDOW)
+            };
+
+            call_table.add_row(vec![
+                Cell::new(format!("{:#x}", call.address)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(call.function.as_deref().unwrap_or("?")).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&call.callee),
+                Cell::new(argument).fg(Color::Yellow),
+                match call.verdict.as_str()
+                {
+                    "constant" => Cell::new(&call.verdict).fg(Color::Green),
+                    "suspicious" => Cell::new(&call.verdict).fg(Color::Red),
+                    _ => Cell::new(&call.verdict).fg(Color::DarkGrey)
+                }
+            ]);
+        }
+
+        println!("\n{call_table}");
+
+        let count = |verdict: &str| -> usize { report.calls.iter().filter(|call| call.verdict == verdict).count() };
+
+        println!("\n{} printf family calls: {} suspicious (non-constant format), {} potential false positives, {} constant.",
+            report.calls.len(),
+            count("suspicious"),
+            count("potential false positive"),
+            count("constant")
+        );
+    }
+}
+
+
+const X86_MAX_INSN_LEN: usize = 15;
+
+/* printf family functions and the argument index of their format string (fortified __*_chk variants insert a flag argument) */
+const PRINTF_FAMILY: [(&str, usize); 20] = [
+    ("printf", 0), ("vprintf", 0), ("fprintf", 1), ("vfprintf", 1), ("dprintf", 1), ("vdprintf", 1),
+    ("sprintf", 1), ("vsprintf", 1), ("asprintf", 1), ("vasprintf", 1), ("snprintf", 2), ("vsnprintf", 2), ("syslog", 1),
+    ("__printf_chk", 1), ("__vprintf_chk", 1), ("__fprintf_chk", 2), ("__vfprintf_chk", 2), ("__dprintf_chk", 2),
+    ("__sprintf_chk", 3), ("__snprintf_chk", 4)
+];
+
+/* Instructions before a printf family call searched for the load of its format argument */
+const FORMAT_CALL_WINDOW: usize = 5;
+
+/* --entry-to-main limits: calls followed, instructions swept per function looking for the first call */
+const ENTRY_MAIN_MAX_DEPTH: usize = 10;
+const ENTRY_MAIN_MAX_INSNS: usize = 256;
+
+/* Shortest printable run reported by --find-string, same default as strings(1) */
+const MIN_STRING_LEN: usize = 4;
+
+#[derive(Serialize)]
+struct FoundStringRecord
+{
+    string: String,
+    offset: u64,
+    section: String,
+    executable: bool
+}
+
+#[derive(Serialize)]
+struct PatchByteRecord
+{
+    offset: u64,
+    original: u8,
+    patched: u8,
+    section: Option<String>,
+    address: Option<u64>,
+    function: Option<String>,
+    /* Instruction covering the offset, before and after the patch (executable sections only) */
+    original_instruction: Option<String>,
+    patched_instruction: Option<String>,
+    output: String,
+    /* The patched copy still parses as ELF */
+    valid: bool,
+    parse_error: Option<String>
+}
+
+#[derive(Serialize)]
+struct EntryCallRecord
+{
+    call_address: u64,
+    caller: String,
+    callee: String,
+    /* Call target, the GOT slot for calls through the GOT */
+    target: u64
+}
+
+#[derive(Serialize)]
+struct EntryToMainRecord
+{
+    entry: u64,
+    steps: Vec<EntryCallRecord>,
+    main: Option<u64>,
+    /* Why the trace ended before main, None when it was reached */
+    stopped: Option<String>
+}
+
+#[derive(Serialize)]
+struct EntryInstructionRecord
+{
+    address: u64,
+    bytes: String,
+    instruction: String,
+    /* Symbol (or GOT slot) a call/jump/memory operand points at */
+    reference: Option<String>
+}
+
+#[derive(Serialize)]
+struct FormatStringRecord
+{
+    address: u64,
+    string: String,
+    /* %n writes through a pointer argument */
+    has_percent_n: bool,
+    references: Vec<u64>,
+    printf_calls: usize
+}
+
+#[derive(Serialize)]
+struct FormatCallRecord
+{
+    address: u64,
+    function: Option<String>,
+    callee: String,
+    format_register: String,
+    /* Instruction that last set the format register within FORMAT_CALL_WINDOW */
+    format_source: Option<String>,
+    format_string: Option<String>,
+    /* constant, suspicious or potential false positive */
+    verdict: String
+}
+
+/* One instruction of the --find-format-strings look-behind window */
+struct FormatWindowEntry
+{
+    text: String,
+    written: Option<String>,
+    /* Source register of a register to register mov */
+    copied_from: Option<String>,
+    targets: Vec<u64>
+}
+
+#[derive(Serialize)]
+struct FormatStringReport
+{
+    strings: Vec<FormatStringRecord>,
+    calls: Vec<FormatCallRecord>
+}
+
+#[derive(Serialize)]
+struct StringXrefRecord
+{
+    string: String,
+    string_address: u64,
+    instruction_address: u64,
+    instruction: String,
+    /* Containing STT_FUNC symbol as name+offset, None when stripped */
+    function: Option<String>
+}
+
+#[derive(Serialize)]
+struct GadgetRecord
+{
+    address: u64,
+    instructions: String,
+    section: String,
+    occurrences: usize
+}
+
+/* Cached decode of one section offset while searching for gadgets */
+struct GadgetInstruction
+{
+    length: usize,
+    text: String,
+    is_branch: bool
+}
+
+#[derive(Serialize)]
+struct GadgetReport
+{
+    total_gadgets: usize,
+    unique_sequences: usize,
+    gadgets: Vec<GadgetRecord>
+}
+
+
+/* (offset, text) of every strings(1) style printable run of at least MIN_STRING_LEN bytes */
+fn return_printable_runs(bytes: &[u8]) -> Vec<(usize, String)>
+{
+    let mut runs: Vec<(usize, String)> = Vec::new();
+    let mut run_start: usize = 0;
+
+    /* Walk one byte past the end so a run touching the section boundary is still closed */
+    for index in 0..=bytes.len()
+    {
+        let printable: bool = bytes.get(index).is_some_and(|&b| b == b'\t' || (0x20..=0x7e).contains(&b));
+
+        if printable
+        {
+            continue;
+        }
+
+        if index - run_start >= MIN_STRING_LEN
+        {
+            runs.push((run_start, String::from_utf8_lossy(&bytes[run_start..index]).to_string()));
+        }
+
+        run_start = index + 1;
+    }
+
+    runs
+}
+
+
+/* (address, name) of the defined functions/objects for the IDA/Ghidra scripts, .symtab names win over the .dynsym copy of an address */
+fn return_script_symbols(elf: &Elf, demangle: bool) -> Vec<(u64, String)>
+{
+    let mut symbols: Vec<(u64, String)> = Vec::new();
+    let mut addresses: HashSet<u64> = HashSet::new();
+
+    for (symbol, strtab) in elf.syms.iter().map(|symbol| (symbol, &elf.strtab))
+        .chain(elf.dynsyms.iter().map(|symbol| (symbol, &elf.dynstrtab)))
+    {
+        let name: &str = match strtab.get_at(symbol.st_name)
+        {
+            Some(name) if !name.is_empty() => name,
+            _ => continue
+        };
+
+        if symbol.st_value == 0 || symbol.st_shndx == 0 || !matches!(symbol.st_type(), STT_FUNC | STT_OBJECT | STT_GNU_IFUNC)
+            || !addresses.insert(symbol.st_value)
+        {
+            continue;
+        }
+
+        symbols.push((symbol.st_value, match demangle
+        {
+            true => return_demangled_name(name),
+            false => name.to_string()
+        }));
+    }
+
+    symbols.sort();
+    symbols
+}
+
+
+/* Itanium C++ (_Z) through cpp_demangle, legacy (_ZN...17h<hash>E) and v0 (_R) Rust through rustc-demangle, anything else unchanged */
+fn return_demangled_name(name: &str) -> String
+{
+    /* Versioned references (_Znwm@GLIBCXX_3.4) keep their version */
+    if let Some((base, version)) = name.split_once('@')
+    {
+        return format!("{}@{version}", return_demangled_name(base));
+    }
+
+    /* Legacy Rust names are valid Itanium names too, only rustc-demangle unescapes their $LT$/$GT$ components */
+    if let Ok(demangled) = rustc_demangle::try_demangle(name)
+    {
+        return demangled.to_string();
+    }
+
+    cpp_demangle::Symbol::new(name).ok()
+        .and_then(|symbol| symbol.demangle().ok())
+        .unwrap_or_else(|| name.to_string())
+}
+
+
+/* Detail mode is always on, callers inspect operands */
+fn return_elf_capstone(elf: &Elf, thumb: bool) -> Result<Capstone, String>
+{
+    let built = match elf.header.e_machine
+    {
+        EM_X86_64 => Capstone::new().x86().mode(arch::x86::ArchMode::Mode64).detail(true).build(),
+        EM_386 => Capstone::new().x86().mode(arch::x86::ArchMode::Mode32).detail(true).build(),
+        EM_AARCH64 => Capstone::new().arm64().mode(arch::arm64::ArchMode::Arm).detail(true).build(),
+        EM_ARM if thumb => Capstone::new().arm().mode(arch::arm::ArchMode::Thumb).detail(true).build(),
+        EM_ARM => Capstone::new().arm().mode(arch::arm::ArchMode::Arm).detail(true).build(),
+
+        machine => return Err(format!("disassembly is not supported for {} binaries", return_elf_emachine(machine)))
+    };
+
+    built.map_err(|err| format!("failed to initialize the Capstone disassembler: {err}"))
+}
+
+
+/* Names an operand target: a covering function, a data symbol, or the import whose GOT slot it is */
+fn return_symbol_reference(elf: &Elf, address: u64) -> Option<String>
+{
+    if let Some(function) = return_function_at(elf, address)
+    {
+        return Some(function);
+    }
+
+    let symbol_name = |symbol_index: usize| -> Option<String> {
+        let symbol: Sym = elf.dynsyms.get(symbol_index)?;
+        elf.dynstrtab.get_at(symbol.st_name).filter(|name| !name.is_empty()).map(|name| name.to_string())
+    };
+
+    elf.dynrelas.iter().chain(elf.dynrels.iter()).chain(elf.pltrelocs.iter())
+        .find(|relocation| relocation.r_offset == address && relocation.r_sym != 0)
+        .and_then(|relocation| symbol_name(relocation.r_sym))
+        .map(|name| format!("{name}@got"))
+        .or_else(|| {
+            elf.syms.iter().map(|symbol| (symbol, &elf.strtab))
+                .chain(elf.dynsyms.iter().map(|symbol| (symbol, &elf.dynstrtab)))
+                .find(|(symbol, _)| symbol.st_value == address && symbol.st_shndx != 0)
+                .and_then(|(symbol, strtab)| strtab.get_at(symbol.st_name).filter(|name| !name.is_empty()).map(|name| name.to_string()))
+        })
+}
+
+
+/* Linear sweep from the covering function (or the section start) to the instruction containing address, as "mnemonic operands" */
+fn return_instruction_at(elf: &Elf, cs: &Capstone, code: &[u8], section_address: u64, address: u64) -> Option<String>
+{
+    let start: u64 = elf.syms.iter()
+        .find(|symbol| symbol.st_type() == STT_FUNC && symbol.st_value >= section_address && symbol.st_value <= address && address - symbol.st_value < symbol.st_size)
+        .map_or(section_address, |symbol| symbol.st_value);
+
+    let mut position: u64 = start;
+
+    while position <= address
+    {
+        let bytes: &[u8] = code.get((position - section_address) as usize..)?;
+        let instructions = cs.disasm_count(bytes, position, 1).ok()?;
+        let instruction = instructions.iter().next();
+
+        match instruction
+        {
+            Some(instruction) if address < position + instruction.len() as u64 => {
+                return Some(format!("{} {}", instruction.mnemonic().unwrap_or("?"), instruction.op_str().unwrap_or("")).trim_end().to_string());
+            },
+
+            Some(instruction) => position += instruction.len() as u64,
+
+            /* Undecodable bytes, step over one and resynchronize */
+            None => position += 1
+        }
+    }
+
+    None
+}
+
+
+/*
+    Addresses an instruction materializes: x86 rip relative or absolute memory operands and immediates,
+    AArch64 adr and adrp + add (the adrp pages are tracked per destination register across calls)
+*/
+fn return_operand_targets(insn: &Insn, operands: &[arch::ArchOperand], pages: &mut HashMap<RegId, u64>) -> Vec<u64>
+{
+    let next: u64 = insn.address() + insn.len() as u64;
+
+    match (insn.mnemonic().unwrap_or(""), operands)
+    {
+        ("adrp", [arch::ArchOperand::Arm64Operand(dst), arch::ArchOperand::Arm64Operand(page)]) => {
+            if let (arch::arm64::Arm64OperandType::Reg(reg), arch::arm64::Arm64OperandType::Imm(page)) = (&dst.op_type, &page.op_type)
+            {
+                pages.insert(*reg, *page as u64);
+            }
+
+            Vec::new()
+        },
+
+        ("add", [_, arch::ArchOperand::Arm64Operand(src), arch::ArchOperand::Arm64Operand(offset)]) => match (&src.op_type, &offset.op_type)
+        {
+            (arch::arm64::Arm64OperandType::Reg(reg), arch::arm64::Arm64OperandType::Imm(offset)) => pages.get(reg).map(|page| page.wrapping_add(*offset as u64)).into_iter().collect(),
+            _ => Vec::new()
+        },
+
+        ("adr", [_, arch::ArchOperand::Arm64Operand(address)]) => match address.op_type
+        {
+            arch::arm64::Arm64OperandType::Imm(address) => vec![address as u64],
+            _ => Vec::new()
+        },
+
+        /* x86: rip relative lea/mov, or an absolute address as displacement/immediate (non-PIE i386) */
+        (_, operands) => operands.iter().filter_map(|operand| match operand
+        {
+            arch::ArchOperand::X86Operand(operand) => match &operand.op_type
+            {
+                arch::x86::X86OperandType::Mem(mem) if mem.base() == RegId(arch::x86::X86Reg::X86_REG_RIP as RegIdInt) => Some(next.wrapping_add(mem.disp() as u64)),
+                arch::x86::X86OperandType::Mem(mem) if mem.base().0 == 0 && mem.index().0 == 0 => Some(mem.disp() as u64),
+                arch::x86::X86OperandType::Imm(imm) => Some(*imm as u64),
+
+                _ => None
+            },
+
+            _ => None
+        }).collect()
+    }
+}
+
+
+/* NUL terminated strings of the .rodata sections holding at least one printf conversion, by address */
+fn return_format_strings(elf: &Elf, data: &[u8]) -> Vec<(u64, String)>
+{
+    let conversion: Regex = Regex::new(r"%[-+ #0']*(\*|\d+)?(\.(\*|\d+))?(hh|h|ll|l|j|z|t|L)?[diouxXeEfFgGaAcspn]").expect("Failed to compile format pattern!");
+    let mut strings: Vec<(u64, String)> = Vec::new();
+
+    for shdr in elf.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && elf.shdr_strtab.get_at(shdr.sh_name).is_some_and(|name| name.starts_with(".rodata")))
+    {
+        let bytes: &[u8] = match return_section_range(shdr, data)
+        {
+            Some(bytes) => bytes,
+            None => continue
+        };
+
+        let mut start: usize = 0;
+
+        for chunk in bytes.split(|&byte| byte == 0)
+        {
+            let printable: bool = !chunk.is_empty() && chunk.iter().all(|&byte| byte == b'\t' || byte == b'\n' || byte == b'\r' || (0x20..=0x7e).contains(&byte));
+
+            if printable
+            {
+                let string: String = String::from_utf8_lossy(chunk).to_string();
+
+                if conversion.is_match(&string)
+                {
+                    strings.push((shdr.sh_addr.wrapping_add(start as u64), string));
+                }
+            }
+
+            start += chunk.len() + 1;
+        }
+    }
+
+    strings
+}
+
+
+/* x86 eax/ax/al -> rax, r8d -> r8, AArch64 w0 -> x0, so writes through a sub-register still count */
+fn return_register_family(name: &str) -> String
+{
+    match name
+    {
+        "eax" | "ax" | "al" => String::from("rax"),
+        "ebx" | "bx" | "bl" => String::from("rbx"),
+        "ecx" | "cx" | "cl" => String::from("rcx"),
+        "edx" | "dx" | "dl" => String::from("rdx"),
+        "esi" | "si" | "sil" => String::from("rsi"),
+        "edi" | "di" | "dil" => String::from("rdi"),
+
+        name if name.starts_with('r') && (name.ends_with('d') || name.ends_with('w') || name.ends_with('b')) && name[1..name.len() - 1].parse::<u8>().is_ok() => name[..name.len() - 1].to_string(),
+        name if name.starts_with('w') && name[1..].parse::<u8>().is_ok() => format!("x{}", &name[1..]),
+
+        name => name.to_string()
+    }
+}
+
+
+/* PLT stub address -> imported name, found through the GOT slot each .plt/.plt.sec/.plt.got stub jumps through */
+fn return_plt_stub_names(elf: &Elf, data: &[u8], cs: &Capstone) -> HashMap<u64, String>
+{
+    let has_plt_sec: bool = elf.section_headers.iter().any(|shdr| elf.shdr_strtab.get_at(shdr.sh_name) == Some(".plt.sec"));
+    let mut stubs: HashMap<u64, String> = HashMap::new();
+    let mut pages: HashMap<RegId, u64> = HashMap::new();
+
+    for shdr in elf.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS)
+    {
+        let name: &str = elf.shdr_strtab.get_at(shdr.sh_name).unwrap_or("");
+
+        /* (PLT0 header, stub size), the AArch64 PLT0 is 32 bytes */
+        let (header, stub_size): (u64, u64) = match (name, elf.header.e_machine)
+        {
+            (".plt", EM_AARCH64) => (32, 16),
+            (".plt.got", EM_X86_64 | EM_386) if !has_plt_sec => (0, 8),
+            (".plt" | ".plt.sec" | ".plt.got", _) => (0, 16),
+
+            _ => continue
+        };
+
+        let code: &[u8] = match return_section_range(shdr, data)
+        {
+            Some(code) => code,
+            None => continue
+        };
+
+        let insns = match cs.disasm_all(code, shdr.sh_addr)
+        {
+            Ok(insns) => insns,
+            Err(_) => continue
+        };
+
+        for insn in insns.iter()
+        {
+            let operands: Vec<arch::ArchOperand> = match cs.insn_detail(insn)
+            {
+                Ok(detail) => detail.arch_detail().operands(),
+                Err(_) => continue
+            };
+
+            let offset: u64 = insn.address() - shdr.sh_addr;
+
+            if offset < header
+            {
+                continue;
+            }
+
+            /* AArch64 loads the slot with ldr x17, [x16, #lo12] after adrp x16, the page offset sits in the memory operand */
+            let slots: Vec<u64> = match operands.as_slice()
+            {
+                [_, arch::ArchOperand::Arm64Operand(memory)] if insn.mnemonic() == Some("ldr") => match &memory.op_type
+                {
+                    arch::arm64::Arm64OperandType::Mem(mem) => pages.get(&mem.base()).map(|page| page.wrapping_add(mem.disp() as u64)).into_iter().collect(),
+                    _ => Vec::new()
+                },
+
+                _ => return_operand_targets(insn, &operands, &mut pages)
+            };
+
+            if let Some(import) = slots.iter().find_map(|slot| return_symbol_reference(elf, *slot).filter(|name| name.ends_with("@got")))
+            {
+                let stub: u64 = shdr.sh_addr.wrapping_add(header + (offset - header) / stub_size * stub_size);
+
+                stubs.entry(stub).or_insert(import.trim_end_matches("@got").to_string());
+            }
+        }
+    }
+
+    stubs
+}
+
+
+/* Length of the ret/ret imm16/jmp reg/jmp [mem]/call reg instruction at the start of `code`, if it is one */
+fn return_gadget_terminator(cs: &Capstone, code: &[u8], addr: u64) -> Option<usize>
+{
+    /* Cheap opcode filter first, c3/c2 = ret, ff /2 and ff /4 = indirect call/jmp */
+    match code.first()?
+    {
+        0xc3 | 0xc2 => (),
+        0xff if matches!((code.get(1)? >> 3) & 0x7, 2 | 4) => (),
+
+        _ => return None
+    }
+
+    let insns = cs.disasm_count(code, addr, 1).ok()?;
+    let insn = insns.iter().next()?;
+
+    match insn.mnemonic()?
+    {
+        "ret" | "jmp" | "call" => Some(insn.len()),
+        _ => None
+    }
+}
+
+
+/* The instruction at `offset` of an executable section, None when the bytes there do not decode */
+fn return_gadget_instruction(cs: &Capstone, code: &[u8], offset: usize, section_addr: u64) -> Option<GadgetInstruction>
+{
+    let bytes: &[u8] = code.get(offset..(offset + X86_MAX_INSN_LEN).min(code.len()))?;
+    let insns = cs.disasm_count(bytes, section_addr.wrapping_add(offset as u64), 1).ok()?;
+    let insn = insns.iter().next()?;
+
+    let mnemonic: &str = insn.mnemonic()?;
+
+    Some(GadgetInstruction {
+        length: insn.len(),
+        text: match insn.op_str()
+        {
+            Some(op_str) if !op_str.is_empty() => format!("{mnemonic} {op_str}"),
+            _ => mnemonic.to_string()
+        },
+        is_branch: mnemonic.starts_with('j') || mnemonic.starts_with("ret") || mnemonic.starts_with("loop") || mnemonic == "call"
+    })
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /* C++ goes to cpp_demangle, legacy and v0 Rust to rustc-demangle, the symbol version survives and anything else is left alone */
+    #[test]
+    fn demangle_names()
+    {
+        let cases: [(&str, &str); 9] = [
+            ("_ZNK2ns3Foo3getEv", "ns::Foo::get() const"),
+            ("_ZN2ns3FooD0Ev", "ns::Foo::~Foo()"),
+            ("_ZStlsISt11char_traitsIcEERSt13basic_ostreamIcT_ES5_PKc", "std::basic_ostream<char, std::char_traits<char> >& std::operator<< <std::char_traits<char> >(std::basic_ostream<char, std::char_traits<char> >&, char const*)"),
+            ("_Z2fpPFviERA4_iPKPKc", "fp(void (*)(int), int (&) [4], char const* const*)"),
+            ("_ZZ9useLambdavENKUliE_clEi", "useLambda()::{lambda(int)#1}::operator()(int) const"),
+            ("_ZN4core3fmt5write17h0123456789abcdefE", "core::fmt::write::h0123456789abcdef"),
+            ("_ZN58_$LT$alloc..string..String$u20$as$u20$core..fmt..Debug$GT$3fmt17h0123456789abcdefE", "<alloc::string::String as core::fmt::Debug>::fmt::h0123456789abcdef"),
+            ("_RNvCs1234_7mycrate3foo", "mycrate[3c1c0]::foo"),
+            ("_Znwm@GLIBCXX_3.4", "operator new(unsigned long)@GLIBCXX_3.4")
+        ];
+
+        for (mangled, demangled) in cases
+        {
+            assert_eq!(return_demangled_name(mangled), demangled);
+        }
+
+        /* Plain C and malformed names stay as they are */
+        assert_eq!(return_demangled_name("printf"), "printf");
+        assert_eq!(return_demangled_name("_ZN3foo"), "_ZN3foo");
+        assert_eq!(return_demangled_name(&format!("_Z1f{}i", "P".repeat(10000))), format!("_Z1f{}i", "P".repeat(10000)));
+    }
+}
@@ -0,0 +1,817 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Dynamic linking: DT_NEEDED libraries, the dynamic section, the interpreter, load order, relocations and the PLT */
+
+use super::*;
+
+impl Arguments
+{
+    /* Return the DT_NEEDED libraries in load order, the order ld.so searches them for symbols */
+    pub(super) fn parse_dynamic_libs(&self, elf: &Elf) -> Vec<String>
+    {
+        let mut dyn_libs: Vec<String> = Vec::new();
+
+        for lib in &elf.libraries
+        {
+            let x: String = lib.to_string();
+
+            if !dyn_libs.contains(&x)
+            {
+                dyn_libs.push(x);
+            }
+        }
+
+        dyn_libs
+    }
+
+
+    pub(super) fn print_dynamic_libs(&self, elf_obj: &Elf, libs: Vec<String>)
+    {
+        if self.is_structured_output()
+        {
+            self.insert_document("libraries", &libs);
+
+            return;
+        }
+
+        println!("\n* Dynamic Libraries found:");
+
+        let search_dirs: Vec<PathBuf> = return_library_search_dirs(elf_obj, &self.file);
+
+        let mut libs_table: Table = self.new_table();
+
+        libs_table.set_header(vec![
+                Cell::new("#").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Library").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Found on System").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (index, lib) in libs.iter().enumerate()
+        {
+            let found: bool = return_needed_library(elf_obj, lib, &search_dirs).is_some();
+
+            libs_table.add_row(vec![
+                Cell::new(index + 1).fg(Color::Yellow),
+                Cell::new(lib).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+
+                match found
+                {
+                    true => Cell::new(CHECK).fg(Color::Green).add_attribute(Attribute::Bold),
+                    false => Cell::new(CROSS).fg(Color::Red).add_attribute(Attribute::Bold)
+                }
+            ]);
+        }
+
+        println!("\n{libs_table}");
+    }
+
+
+    pub(super) fn parse_dynamic_section(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let dyns = match &elf_obj.dynamic
+        {
+            Some(dynamic) => &dynamic.dyns,
+
+            None => {
+                println!("\n* No dynamic section present (statically linked binary).");
+                return;
+            }
+        };
+
+        let entries: Vec<DynamicEntryRecord> = dyns.iter().map(|d| DynamicEntryRecord {
+            tag: match d.d_tag
+            {
+                DT_GNU_PRELINKED => "DT_GNU_PRELINKED".to_string(),
+                DT_GNU_CONFLICT => "DT_GNU_CONFLICT".to_string(),
+                DT_GNU_CONFLICTSZ => "DT_GNU_CONFLICTSZ".to_string(),
+                DT_GNU_LIBLIST => "DT_GNU_LIBLIST".to_string(),
+                DT_GNU_LIBLISTSZ => "DT_GNU_LIBLISTSZ".to_string(),
+
+                tag => match elf_dynamic::tag_to_str(tag)
+                {
+                    "UNKNOWN_TAG" => format!("{:#x}", tag),
+                    name => name.to_string()
+                }
+            },
+
+            value: d.d_val,
+
+            string: match d.d_tag
+            {
+                DT_NEEDED | DT_SONAME | DT_RPATH | DT_RUNPATH => elf_obj.dynstrtab.get_at(d.d_val as usize).map(|s| s.to_string()),
+                _ => None
+            }
+        }).collect();
+
+        let prelink: Option<PrelinkRecord> = return_prelink_info(elf_obj, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("dynamic", &DynamicRecord { entries, prelinked: prelink.is_some(), prelink });
+            return;
+        }
+
+        println!("\nDynamic Section =>");
+
+        let mut dynamic_table: Table = self.new_table();
+
+        dynamic_table.set_header(vec![
+                Cell::new("Tag").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for entry in &entries
+        {
+            dynamic_table.add_row(vec![
+                match entry.tag.as_str()
+                {
+                    "DT_GNU_PRELINKED" => Cell::new(&entry.tag).fg(Color::Magenta).add_attribute(Attribute::Bold),
+                    _ => Cell::new(&entry.tag).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+                },
+
+                match &entry.string
+                {
+                    Some(string) => Cell::new(format!("{:#x} ({string})", entry.value)).fg(Color::Yellow),
+                    None => Cell::new(format!("{:#x}", entry.value))
+                }
+            ]);
+        }
+
+        println!("\n{dynamic_table}");
+        println!("\n{} dynamic entries.", entries.len());
+
+        if let Some(prelink) = prelink
+        {
+            println!("\n* PRELINKED binary:");
+            println!("\tTimestamp      >> {}", prelink.timestamp.map_or("Not defined".to_string(), |ts| format!("{ts} (unix time)")));
+            println!("\tUndo section   >> {}", prelink.undo_section_size.map_or("absent".to_string(), |sz| format!(".gnu.prelink_undo ({sz} bytes)")));
+            println!("\tEntry point    >> {:#x} (original: {})", prelink.prelinked_entry, prelink.original_entry.map_or("unknown".to_string(), |e| format!("{:#x}", e)));
+            println!("\tLoad base      >> {} (original: {})",
+                prelink.prelinked_base.map_or("unknown".to_string(), |b| format!("{:#x}", b)),
+                prelink.original_base.map_or("unknown".to_string(), |b| format!("{:#x}", b))
+            );
+        }
+    }
+
+
+    /* The dynamic linker named by PT_INTERP is read from the local filesystem, which may differ from the target system */
+    pub(super) fn parse_interp_elf(&self, elf_obj: &Elf)
+    {
+        let interpreter: &str = match elf_obj.interpreter
+        {
+            Some(interpreter) => interpreter,
+
+            None => {
+                eprintln!("Error - binary has no PT_INTERP segment (statically linked or not an executable)!");
+                std::process::exit(-1);
+            }
+        };
+
+        self.parse_file_header(elf_obj, "header");
+
+        if !self.is_structured_output()
+        {
+            println!("\n=== Interpreter: {interpreter} ===");
+        }
+
+        let interp_data: Vec<u8> = match fs::read(interpreter)
+        {
+            Ok(interp_data) => interp_data,
+
+            Err(err) => {
+                eprintln!("\n* Interpreter \"{interpreter}\" could not be read on this system ({err}), cross-compiled binary?");
+                return;
+            }
+        };
+
+        let interp_elf: Elf = match Elf::parse(&interp_data)
+        {
+            Ok(interp_elf) => interp_elf,
+
+            Err(err) => {
+                eprintln!("\n* Interpreter \"{interpreter}\" is not a valid ELF file: {err}");
+                return;
+            }
+        };
+
+        let interp_version: Option<String> = return_interp_version(&interp_elf);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("interpreter_path", &interpreter);
+            self.insert_document("interpreter_version", &interp_version);
+        }
+
+        self.parse_file_header(&interp_elf, "interpreter");
+
+        if !self.is_structured_output()
+        {
+            println!("\nLinker version: {}", interp_version.as_deref().unwrap_or("Unknown (no versioned symbol definitions)"));
+        }
+    }
+
+
+    pub(super) fn parse_plt_size(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let stub_size: u64 = match elf_obj.header.e_machine
+        {
+            EM_X86_64 | EM_386 | EM_AARCH64 => 16,
+            EM_ARM => 12,
+
+            _ => {
+                eprintln!("Error - PLT stub size is unknown for {}!", return_elf_emachine(elf_obj.header.e_machine));
+                std::process::exit(-1);
+            }
+        };
+
+        let section_size = |name: &str| -> Option<u64> {
+            elf_obj.section_headers.iter()
+                .find(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name) == Some(name))
+                .map(|shdr| shdr.sh_size)
+        };
+
+        /* With IBT (.plt.sec present) x86 .plt.got stubs grow from 8 to 16 bytes to make room for endbr64 */
+        let has_plt_sec: bool = section_size(".plt.sec").is_some();
+
+        let sections: Vec<PltSectionRecord> = [".plt", ".plt.sec", ".plt.got"].iter()
+            .filter_map(|&name| {
+                let size: u64 = section_size(name)?;
+
+                let stubs: u64 = match name
+                {
+                    /* PLT0 resolver trampoline is not an import stub */
+                    ".plt" => (size / stub_size).saturating_sub(1),
+                    ".plt.got" if !has_plt_sec && matches!(elf_obj.header.e_machine, EM_X86_64 | EM_386) => size / 8,
+
+                    _ => size / stub_size
+                };
+
+                Some(PltSectionRecord { name: name.to_string(), size, stubs })
+            })
+            .collect();
+
+        /* Every import owns a .plt entry and, with IBT, a .plt.sec entry as well, count it once */
+        let plt_entries: u64 = sections.iter()
+            .filter(|section| section.name != match has_plt_sec { true => ".plt", false => ".plt.sec" })
+            .map(|section| section.stubs)
+            .sum();
+
+        let total_plt_size: u64 = sections.iter().fold(0, |total, section| total.saturating_add(section.size));
+        let (code_bytes, _, _): (u64, u64, u64) = compute_code_data_sizes(elf_obj, data);
+
+        let plt_size: PltSizeRecord = PltSizeRecord {
+            total_plt_size,
+            plt_entries,
+            bytes_per_entry: match plt_entries { 0 => 0.0, _ => total_plt_size as f64 / plt_entries as f64 },
+            code_percent: match code_bytes { 0 => 0.0, _ => total_plt_size as f64 * 100.0 / code_bytes as f64 },
+            got_plt_size: section_size(".got.plt").unwrap_or(0),
+            sections
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("plt_size", &plt_size);
+            return;
+        }
+
+        println!("\nPLT Size =>");
+
+        if plt_size.sections.is_empty()
+        {
+            println!("\n* No PLT sections present (statically linked or built with -fno-plt).");
+            return;
+        }
+
+        let mut plt_table: Table = self.new_table();
+
+        plt_table.set_header(vec![
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Stubs").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for section in &plt_size.sections
+        {
+            plt_table.add_row(vec![
+                Cell::new(&section.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{} bytes", section.size)),
+                Cell::new(section.stubs).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{plt_table}");
+
+        println!("\nTotal PLT size: {} bytes | Entries: {} | Overhead per entry: {:.1} bytes | {:.2}% of code",
+            plt_size.total_plt_size,
+            plt_size.plt_entries,
+            plt_size.bytes_per_entry,
+            plt_size.code_percent
+        );
+
+        println!(".got.plt size: {} bytes", plt_size.got_plt_size);
+    }
+
+    pub(super) fn parse_relocation_counts(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let ctx: Ctx = return_elf_ctx(elf_obj);
+
+        let mut sections: Vec<String> = Vec::new();
+        let mut types: Vec<RelocationTypeRecord> = Vec::new();
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA)
+        {
+            let relocs: RelocSection = RelocSection::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx).unwrap_or_default();
+
+            sections.push(elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string());
+
+            for relocation in relocs.iter()
+            {
+                let relocation_type: String = reloc::r_to_str(relocation.r_type, elf_obj.header.e_machine).to_string();
+
+                match types.iter_mut().find(|record| record.relocation_type == relocation_type)
+                {
+                    Some(record) => {
+                        record.count += 1;
+                        record.symbol_lookup |= relocation.r_sym != 0;
+                    },
+
+                    None => types.push(RelocationTypeRecord { relocation_type, count: 1, symbol_lookup: relocation.r_sym != 0 })
+                }
+            }
+        }
+
+        types.sort_by_key(|record| std::cmp::Reverse(record.count));
+
+        let total: usize = types.iter().map(|record| record.count).sum();
+
+        let relocation_counts: RelocationCountRecord = RelocationCountRecord {
+            sections,
+            types,
+            total,
+            startup_cost: return_relocation_cost(total).to_string()
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("relocation_counts", &relocation_counts);
+            return;
+        }
+
+        println!("\nRelocation Counts =>");
+
+        if relocation_counts.sections.is_empty()
+        {
+            println!("\n* No SHT_REL/SHT_RELA sections present.");
+            return;
+        }
+
+        let mut reloc_table: Table = self.new_table();
+
+        reloc_table.set_header(vec![
+                Cell::new("Relocation Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Symbol Lookup").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &relocation_counts.types
+        {
+            reloc_table.add_row(vec![
+                match is_irelative(&record.relocation_type)
+                {
+                    true => Cell::new(&record.relocation_type).fg(Color::Magenta).add_attribute(Attribute::Bold),
+                    false => Cell::new(&record.relocation_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                },
+                Cell::new(record.count).fg(Color::Yellow),
+
+                match record.symbol_lookup
+                {
+                    true => Cell::new(CHECK).fg(Color::Red).add_attribute(Attribute::Bold),
+                    false => Cell::new(CROSS).fg(Color::Green).add_attribute(Attribute::Dim)
+                }
+            ]);
+        }
+
+        println!("\n{reloc_table}");
+
+        println!("\nSections: {}", relocation_counts.sections.join(", "));
+        println!("Total relocations: {} | Estimated startup cost: {}", relocation_counts.total, relocation_counts.startup_cost);
+    }
+
+    /* Kernel (binfmt_elf) steps first, then what the dynamic linker does before jumping to the entry point */
+    pub(super) fn parse_load_order(&self, elf_obj: &Elf)
+    {
+        if elf_obj.program_headers.iter().all(|phdr| phdr.p_type != PT_LOAD)
+        {
+            eprintln!("Error - binary has no PT_LOAD segment, {} files are not loaded by the kernel!", et_to_str(elf_obj.header.e_type));
+            std::process::exit(-1);
+        }
+
+        let mut steps: Vec<LoadStepRecord> = Vec::new();
+
+        let permissions = |p_flags: u32| -> String {
+            format!("{}{}{}",
+                match p_flags & program_header::PF_R != 0 { true => 'R', false => '-' },
+                match p_flags & program_header::PF_W != 0 { true => 'W', false => '-' },
+                match p_flags & program_header::PF_X != 0 { true => 'X', false => '-' }
+            )
+        };
+
+        let mut log = |stage: &str, action: String, range: Option<(u64, u64)>, permissions: String, detail: String| {
+            steps.push(LoadStepRecord {
+                step: steps.len() + 1,
+                stage: stage.to_string(),
+                action,
+                start: range.map(|(start, _)| start),
+                end: range.map(|(_, end)| end),
+                permissions,
+                detail
+            });
+        };
+
+        let find = |p_type: u32| -> Option<&ProgramHeader> { elf_obj.program_headers.iter().find(|phdr| phdr.p_type == p_type) };
+
+        let header_size: u64 = elf_obj.header.e_phnum as u64 * elf_obj.header.e_phentsize as u64;
+
+        match find(program_header::PT_PHDR)
+        {
+            Some(phdr) => log("kernel", String::from("read ELF header and program headers (PT_PHDR)"), Some((phdr.p_vaddr, phdr.p_vaddr.saturating_add(phdr.p_memsz))), permissions(phdr.p_flags),
+                format!("{} program headers at file offset {:#x}", elf_obj.header.e_phnum, elf_obj.header.e_phoff)),
+
+            None => log("kernel", String::from("read ELF header and program headers"), None, String::new(),
+                format!("no PT_PHDR, {} program headers read from file offset {:#x} ({header_size} bytes)", elf_obj.header.e_phnum, elf_obj.header.e_phoff))
+        }
+
+        for (index, phdr) in elf_obj.program_headers.iter().enumerate().filter(|(_, phdr)| phdr.p_type == PT_LOAD)
+        {
+            let start: u64 = phdr.p_vaddr & !(LOAD_PAGE_SIZE - 1);
+            let end: u64 = phdr.p_vaddr.saturating_add(phdr.p_memsz).saturating_add(LOAD_PAGE_SIZE - 1) & !(LOAD_PAGE_SIZE - 1);
+
+            log("kernel", format!("mmap PT_LOAD #{index}"), Some((start, end)), permissions(phdr.p_flags),
+                format!("file offset {:#x}, {:#x} file bytes", phdr.p_offset & !(LOAD_PAGE_SIZE - 1), phdr.p_filesz));
+
+            /* .bss: the tail of the last file page is cleared, whole pages past it come from anonymous memory */
+            if phdr.p_memsz > phdr.p_filesz
+            {
+                log("kernel", format!("zero fill PT_LOAD #{index} (.bss)"), Some((phdr.p_vaddr.saturating_add(phdr.p_filesz), phdr.p_vaddr.saturating_add(phdr.p_memsz))), permissions(phdr.p_flags),
+                    format!("{:#x} bytes of memory without file contents", phdr.p_memsz - phdr.p_filesz));
+            }
+        }
+
+        match find(program_header::PT_GNU_STACK)
+        {
+            Some(phdr) => log("kernel", String::from("set up the stack (PT_GNU_STACK)"), None, permissions(phdr.p_flags), String::from("argv, envp and auxv (AT_PHDR, AT_ENTRY, AT_BASE) pushed")),
+            None => log("kernel", String::from("set up the stack"), None, String::from("RWX"), String::from("no PT_GNU_STACK, executable stack by default on most architectures"))
+        }
+
+        let entry: String = format!("{:#x}", elf_obj.entry);
+
+        match elf_obj.interpreter
+        {
+            Some(interpreter) => {
+                log("kernel", format!("map PT_INTERP {interpreter} and jump to its entry"), None, String::new(), String::from("the dynamic linker runs before the program"));
+
+                log("ld.so", String::from("load the DT_NEEDED libraries"), None, String::new(),
+                    match elf_obj.libraries.is_empty() { true => String::from("none"), false => elf_obj.libraries.join(", ") });
+
+                if let Some(phdr) = find(program_header::PT_TLS)
+                {
+                    log("ld.so", String::from("allocate the TLS block (PT_TLS)"), Some((phdr.p_vaddr, phdr.p_vaddr.saturating_add(phdr.p_memsz))), permissions(phdr.p_flags),
+                        format!("{:#x} initialized + {:#x} zeroed bytes per thread", phdr.p_filesz, phdr.p_memsz.saturating_sub(phdr.p_filesz)));
+                }
+
+                log("ld.so", String::from("apply relocations"), None, String::new(), match has_bind_now(elf_obj)
+                {
+                    true => String::from("BIND_NOW: every symbol resolved up front, including the PLT"),
+                    false => String::from("lazy binding: JUMP_SLOT entries are resolved on first call")
+                });
+
+                if let Some(phdr) = find(PT_GNU_RELRO)
+                {
+                    let end: u64 = phdr.p_vaddr.saturating_add(phdr.p_memsz) & !(LOAD_PAGE_SIZE - 1);
+
+                    log("ld.so", String::from("mprotect PT_GNU_RELRO read-only"), Some((phdr.p_vaddr & !(LOAD_PAGE_SIZE - 1), end)), String::from("R--"),
+                        String::from("after relocation, the GOT (and .init_array, .dynamic, ...) can no longer be overwritten"));
+                }
+
+                log("ld.so", String::from("run the initializers"), None, String::new(), String::from("DT_INIT and DT_INIT_ARRAY of every library, then of the program"));
+                log("ld.so", String::from("jump to the program entry point"), Some((elf_obj.entry, elf_obj.entry)), String::new(), format!("e_entry {entry}"));
+            },
+
+            None => {
+                if let Some(phdr) = find(PT_GNU_RELRO)
+                {
+                    log("startup", String::from("mprotect PT_GNU_RELRO read-only (static startup code)"), Some((phdr.p_vaddr & !(LOAD_PAGE_SIZE - 1), phdr.p_vaddr.saturating_add(phdr.p_memsz) & !(LOAD_PAGE_SIZE - 1))),
+                        String::from("R--"), String::from("static PIE/glibc startup applies its own relocations first"));
+                }
+
+                log("kernel", String::from("jump to the program entry point"), Some((elf_obj.entry, elf_obj.entry)), String::new(), format!("no PT_INTERP, e_entry {entry} runs first"));
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("load_order", &steps);
+            return;
+        }
+
+        println!("\nLoad Order =>");
+
+        let mut load_table: Table = self.new_table();
+
+        load_table.set_header(vec![
+                Cell::new("#").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Stage").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Action").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address Range").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Perms").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Details").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &steps
+        {
+            let range: String = match (record.start, record.end)
+            {
+                (Some(start), Some(end)) if start == end => format!("{start:#x}"),
+                (Some(start), Some(end)) => format!("{start:#x}-{end:#x}"),
+                _ => String::new()
+            };
+
+            load_table.add_row(vec![
+                Cell::new(record.step),
+                Cell::new(&record.stage).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&record.action).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(range).fg(Color::Yellow),
+                Cell::new(&record.permissions).fg(Color::Yellow),
+                Cell::new(&record.detail)
+            ]);
+        }
+
+        println!("\n{load_table}");
+
+        if elf_obj.header.e_type == ET_DYN
+        {
+            println!("\nET_DYN: addresses are offsets from the load base the kernel picks at random (ASLR).");
+        }
+    }
+
+    pub(super) fn parse_reloc_stats(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let ns_per_relocation: u64 = self.reloc_ns;
+
+        let ctx: Ctx = return_elf_ctx(elf_obj);
+        let bind_now: bool = has_bind_now(elf_obj);
+
+        let mut stats: RelocStatsRecord = RelocStatsRecord {
+            relative: 0,
+            copy: 0,
+            jump_slot: 0,
+            glob_dat: 0,
+            tls: 0,
+            other: 0,
+            bind_now,
+            symbolic: 0,
+            ns_per_relocation,
+            estimated_overhead_ms: 0.0
+        };
+
+        /* Only the allocated (dynamic) relocation sections are processed by ld.so, .rela.text & co. are for the static linker */
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| (shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA) && shdr.sh_flags & SHF_ALLOC as u64 != 0)
+        {
+            let relocs: RelocSection = RelocSection::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx).unwrap_or_default();
+
+            for relocation in relocs.iter()
+            {
+                let relocation_type: &str = reloc::r_to_str(relocation.r_type, elf_obj.header.e_machine);
+
+                let lazy: bool = match relocation_type
+                {
+                    name if name.ends_with("_RELATIVE") && !name.ends_with("_IRELATIVE") => { stats.relative += 1; continue; },
+                    name if name.ends_with("_COPY") => { stats.copy += 1; false },
+                    name if name.ends_with("_JUMP_SLOT") || name.ends_with("_JMP_SLOT") => { stats.jump_slot += 1; !bind_now },
+                    name if name.ends_with("_GLOB_DAT") => { stats.glob_dat += 1; false },
+                    name if name.contains("TPOFF") || name.contains("DTPMOD") || name.contains("TLSDESC") => { stats.tls += 1; false },
+
+                    _ => { stats.other += 1; false }
+                };
+
+                if relocation.r_sym != 0 && !lazy
+                {
+                    stats.symbolic += 1;
+                }
+            }
+        }
+
+        stats.estimated_overhead_ms = (stats.symbolic as u64).saturating_mul(ns_per_relocation) as f64 / 1_000_000.0;
+
+        if self.is_structured_output()
+        {
+            self.insert_document("reloc_stats", &stats);
+            return;
+        }
+
+        println!("\nRelocation Startup Statistics =>");
+
+        let mut stats_table: Table = self.new_table();
+
+        stats_table.set_header(vec![
+                Cell::new("Category").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Cost").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (category, count, cost) in [
+            ("R_*_RELATIVE", stats.relative, "cheap, no symbol lookup"),
+            ("R_*_COPY", stats.copy, "rare, expensive (lookup + copy)"),
+            ("R_*_JUMP_SLOT", stats.jump_slot, match bind_now { true => "startup (BIND_NOW)", false => "lazy, on first call" }),
+            ("R_*_GLOB_DAT", stats.glob_dat, "eager, startup"),
+            ("R_*_TPOFF/DTPMOD (TLS)", stats.tls, "once per thread"),
+            ("Other", stats.other, "startup when symbolic")
+        ]
+        {
+            stats_table.add_row(vec![
+                Cell::new(category).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(count).fg(Color::Yellow),
+                Cell::new(cost).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+            ]);
+        }
+
+        println!("\n{stats_table}");
+        println!("\nEstimated startup overhead: ~{:.3}ms from {} symbolic relocations ({} ns each).", stats.estimated_overhead_ms, stats.symbolic, ns_per_relocation);
+    }
+}
+
+
+/* Startup cost buckets of --count-relocations (total relocations processed by the dynamic linker) */
+const RELOC_COST_LOW_LIMIT: usize = 1_000;
+const RELOC_COST_MEDIUM_LIMIT: usize = 10_000;
+
+/* GNU prelink dynamic tags (not exported by goblin) */
+const DT_GNU_PRELINKED: u64 = 0x6fff_fdf5;
+const DT_GNU_CONFLICTSZ: u64 = 0x6fff_fdf6;
+const DT_GNU_LIBLISTSZ: u64 = 0x6fff_fdf7;
+const DT_GNU_CONFLICT: u64 = 0x6fff_fef8;
+const DT_GNU_LIBLIST: u64 = 0x6fff_fef9;
+
+#[derive(Serialize)]
+struct PltSectionRecord
+{
+    name: String,
+    size: u64,
+    stubs: u64
+}
+
+#[derive(Serialize)]
+struct PltSizeRecord
+{
+    sections: Vec<PltSectionRecord>,
+    total_plt_size: u64,
+    plt_entries: u64,
+    bytes_per_entry: f64,
+    code_percent: f64,
+    got_plt_size: u64
+}
+
+#[derive(Serialize)]
+struct RelocationTypeRecord
+{
+    relocation_type: String,
+    count: usize,
+    /* At least one relocation of this type references a symbol the dynamic linker has to look up */
+    symbol_lookup: bool
+}
+
+#[derive(Serialize)]
+struct RelocStatsRecord
+{
+    relative: usize,
+    copy: usize,
+    jump_slot: usize,
+    glob_dat: usize,
+    tls: usize,
+    other: usize,
+    bind_now: bool,
+    /* Relocations needing a symbol lookup before main (JUMP_SLOT only with BIND_NOW) */
+    symbolic: usize,
+    ns_per_relocation: u64,
+    estimated_overhead_ms: f64
+}
+
+#[derive(Serialize)]
+struct RelocationCountRecord
+{
+    sections: Vec<String>,
+    types: Vec<RelocationTypeRecord>,
+    total: usize,
+    startup_cost: String
+}
+
+#[derive(Serialize)]
+struct LoadStepRecord
+{
+    step: usize,
+    stage: String,
+    action: String,
+    start: Option<u64>,
+    end: Option<u64>,
+    permissions: String,
+    detail: String
+}
+
+#[derive(Serialize)]
+struct DynamicEntryRecord
+{
+    tag: String,
+    value: u64,
+    string: Option<String>
+}
+
+#[derive(Serialize)]
+struct PrelinkRecord
+{
+    timestamp: Option<u64>,
+    undo_section_size: Option<u64>,
+    original_entry: Option<u64>,
+    prelinked_entry: u64,
+    original_base: Option<u64>,
+    prelinked_base: Option<u64>
+}
+
+#[derive(Serialize)]
+struct DynamicRecord
+{
+    entries: Vec<DynamicEntryRecord>,
+    prelinked: bool,
+    prelink: Option<PrelinkRecord>
+}
+
+
+/* Prelink stores the pre-prelink ELF header and program headers in .gnu.prelink_undo */
+fn return_prelink_info(elf: &Elf, data: &[u8]) -> Option<PrelinkRecord>
+{
+    let timestamp: Option<u64> = elf.dynamic.as_ref()
+        .and_then(|dynamic| dynamic.dyns.iter().find(|d| d.d_tag == DT_GNU_PRELINKED))
+        .map(|d| d.d_val);
+
+    let undo: Option<&[u8]> = return_section_data(elf, data, ".gnu.prelink_undo");
+
+    if timestamp.is_none() && undo.is_none()
+    {
+        return None;
+    }
+
+    let original_hdr: Option<Header> = undo.and_then(|undo| Elf::parse_header(undo).ok());
+
+    let original_phdrs: Option<Vec<ProgramHeader>> = match (undo, &original_hdr)
+    {
+        (Some(undo), Some(hdr)) => {
+            let container: Container = match hdr.e_ident[EI_CLASS] { ELFCLASS64 => Container::Big, _ => Container::Little };
+            let ctx: Ctx = Ctx::new(container, hdr.endianness().ok()?);
+
+            ProgramHeader::parse(undo, hdr.e_ehsize as usize, hdr.e_phnum as usize, ctx).ok()
+        },
+
+        _ => None
+    };
+
+    Some(PrelinkRecord {
+        timestamp,
+        undo_section_size: undo.map(|undo| undo.len() as u64),
+        original_entry: original_hdr.map(|hdr| hdr.e_entry),
+        prelinked_entry: elf.entry,
+        original_base: original_phdrs.and_then(|phdrs| return_load_base(&phdrs)),
+        prelinked_base: return_load_base(&elf.program_headers)
+    })
+}
+
+
+/* Highest numbered version definition (GLIBC_2.39 for glibc's ld.so), the base entry carrying the SONAME is skipped */
+fn return_interp_version(elf: &Elf) -> Option<String>
+{
+    let version_number = |name: &str| -> Option<Vec<u32>> {
+        let (_, number) = name.rsplit_once('_')?;
+
+        number.split('.').map(|part| part.parse::<u32>().ok()).collect()
+    };
+
+    elf.verdef.as_ref()?.iter()
+        .filter(|verdef| verdef.vd_flags & VER_FLG_BASE == 0)
+        .filter_map(|verdef| elf.dynstrtab.get_at(verdef.iter().next()?.vda_name))
+        .filter_map(|name| Some((version_number(name)?, name)))
+        .max()
+        .map(|(_, name)| name.to_string())
+}
+
+fn return_relocation_cost(total: usize) -> &'static str
+{
+    match total
+    {
+        0..RELOC_COST_LOW_LIMIT => "LOW",
+        RELOC_COST_LOW_LIMIT..RELOC_COST_MEDIUM_LIMIT => "MEDIUM",
+
+        _ => "HIGH"
+    }
+}
@@ -0,0 +1,1035 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* ELF header, architecture, ABI tag, kernel module and core file reports */
+
+use super::*;
+
+impl Arguments
+{
+    /* goblin reads both classes transparently, so say when the addresses are not the host's pointer width */
+    pub(crate) fn print_elf_class_notice(&self, elf_obj: &Elf)
+    {
+        if let Some(notice) = check_elf_class_vs_host(elf_obj)
+        {
+            match self.is_structured_output()
+            {
+                true => eprintln!("{notice}"),
+                false => println!("\n{notice}")
+            }
+        }
+    }
+
+
+    pub(super) fn parse_arch_info(&self, elf_obj: &Elf)
+    {
+        let arch_flags: Vec<(String, String)> = return_elf_eflags(elf_obj.header.e_machine, elf_obj.header.e_flags);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("arch_info", &arch_flags.into_iter().map(|(k, v)| (Value::String(k), Value::String(v))).collect::<Mapping>());
+
+            return;
+        }
+
+        println!("\nArchitecture Information =>");
+
+        let mut arch_table: Table = self.new_table();
+
+        arch_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (property, value) in &arch_flags
+        {
+            arch_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{arch_table}");
+    }
+
+
+    pub(super) fn check_elf_class(&self, elf_obj: &Elf)
+    {
+        match check_elf_class_vs_host(elf_obj)
+        {
+            Some(notice) => {
+                println!("{notice}");
+                std::process::exit(1);
+            },
+
+            None => {
+                println!("ELF class matches the {}-bit host.", usize::BITS);
+                std::process::exit(0);
+            }
+        }
+    }
+
+
+    /* ELF identification and header summary, also reused for the interpreter by --interp-elf */
+    pub(crate) fn parse_file_header(&self, elf_obj: &Elf, document_key: &str)
+    {
+        let elf_sz: u16 = elf_obj.header.e_ehsize;
+
+        let elf_end: Endian = elf_obj.header.endianness().expect("Failed to obtain endianness of binary!");
+        let elf_ident: [u8; SIZEOF_IDENT] = elf_obj.header.e_ident;
+
+        let elf_emachine: u16 = elf_obj.header.e_machine;
+        let elf_eversion: u32 = elf_obj.header.e_version;
+
+        let ident: ElfIdent = parse_elf_ident(&elf_ident);
+
+        if self.is_structured_output()
+        {
+            self.insert_document(document_key, &HeaderRecord {
+                header_size: elf_sz,
+                magic: ident.magic_hex,
+                class: ident.class,
+                data: ident.data,
+                version: ident.version,
+                osabi: ident.osabi,
+                abi_version: ident.abiversion,
+                endian: Some(format!("{:?}", elf_end)),
+                e_type: return_elf_etype(elf_obj),
+                e_machine: return_elf_emachine(elf_emachine),
+                e_version: elf_eversion,
+                entry: elf_obj.entry,
+                e_shstrndx: return_shstrndx_label(elf_obj)
+            });
+
+            return;
+        }
+
+        if !self.quiet
+        {
+            println!("\n* Packing ELFMAG0 => {}", elf_ident[0]);
+
+            for (index, byte) in elf_ident.iter().enumerate().take(ELF_MAGIC_LEN).skip(1)
+            {
+                println!("* Packing ELFMAG{index} => {byte} ({})", *byte as char);
+            }
+
+            println!();
+        }
+
+        let msg: String = format!(r###"
+            FILE HEADER/MAGIC INFORMATION
+            =============================
+
+            ARCH   : {elf_sz}-bit binary
+            MAGIC  : {}
+                     CLASS={} | DATA={} | VERSION={} | OSABI={} | ABIVERSION={}
+
+            ENDIAN : {:#?}
+            E_TYPE : {}
+            E_MACH : {}
+            E_VERS : {}
+            E_ENTR : {}
+            E_PHOF : {}
+            E_SHOF : {}
+            E_SHST : {}
+            ________________________
+        "###, 
+            ident.magic_hex,                    /* MAGIC */
+            match ident.class                   /* CLASS TYPE */
+            {
+                ELFCLASSNONE => format!("{ELFCLASSNONE} (NONE)"), 
+                ELFCLASS32 => format!("{ELFCLASS32} (32 BIT)"),
+                ELFCLASS64 => format!("{ELFCLASS64} (64 BIT)"), 
+                
+                _ => String::from("UNKNOWN")
+            },
+            
+            match ident.data                    /* DATA TYPE */
+            {
+                ELFDATANONE => format!("{ELFDATANONE} (Invalid data encoding)"),
+                ELFDATA2LSB => format!("{ELFDATA2LSB} (LE with 2\'s complement)"),
+                ELFDATA2MSB => format!("{ELFDATA2MSB} (BE with 2\'s compliment)"),
+           
+                _ => String::from("UNKNOWN")
+            },
+
+            ident.version,
+            ident.osabi,
+            ident.abiversion,
+            elf_end,                            /* ENDIAN TYPE */
+            return_elf_etype(elf_obj),         /* E_TYPE (Object file type) */
+            return_elf_emachine(elf_emachine),  /* E_MACH (CPU Architecture)*/
+            
+            match elf_eversion           /* E_VERS */
+            { 
+                0 => format!("{elf_eversion} (EV_NONE)"), 
+                1 => format!("{elf_eversion} (EV_CURRENT)"),
+
+                _ => String::from("UNKNOWN")
+            },
+
+            self.number_format.format(elf_obj.entry),             /* ENTRY POINT */
+            self.number_format.format(elf_obj.header.e_phoff),    /* E_PHOF (Program header table offset) */
+            self.number_format.format(elf_obj.header.e_shoff),    /* E_SHOF (Section header table offset) */
+            return_shstrndx_label(elf_obj),    /* E_SHST (Section name string table index) */
+        );
+
+        print!("{}", msg.unindent());
+    }
+
+
+    pub(super) fn parse_arch_compat(&self, elf_obj: &Elf)
+    {
+        let (compatible, verdict): (bool, String) = check_arch_compat(elf_obj);
+
+        let record: ArchCompatRecord = ArchCompatRecord {
+            binary_machine: machine_to_str(elf_obj.header.e_machine).to_string(),
+            binary_class: match elf_obj.header.e_ident[EI_CLASS] == ELFCLASS64 { true => "ELF64", false => "ELF32" }.to_string(),
+            binary_endianness: match elf_obj.header.e_ident[EI_DATA] == ELFDATA2LSB { true => "Little", false => "Big" }.to_string(),
+            host_machine: machine_to_str(return_host_machine()).to_string(),
+            host_class: match cfg!(target_pointer_width = "64") { true => "ELF64", false => "ELF32" }.to_string(),
+            host_endianness: match cfg!(target_endian = "little") { true => "Little", false => "Big" }.to_string(),
+            compatible,
+            verdict,
+            interpreter: elf_obj.interpreter.map(|interpreter| interpreter.to_string()),
+            /* A missing PT_INTERP loader is the usual "No such file or directory" on a cross-compiled binary */
+            interpreter_found: elf_obj.interpreter.map(|interpreter| Path::new(interpreter).exists())
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("arch_compat", &record);
+            return;
+        }
+
+        println!("\nArchitecture Compatibility =>");
+
+        let mut compat_table: Table = self.new_table();
+
+        compat_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Binary").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Host").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (property, binary, host) in [
+            ("Machine", &record.binary_machine, &record.host_machine),
+            ("Class", &record.binary_class, &record.host_class),
+            ("Endianness", &record.binary_endianness, &record.host_endianness)
+        ]
+        {
+            compat_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match binary == host
+                {
+                    true => Cell::new(binary).fg(Color::Yellow),
+                    false => Cell::new(binary).fg(Color::Red)
+                },
+                Cell::new(host).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{compat_table}");
+
+        match record.compatible
+        {
+            true => println!("\n{ANSI_GREEN}{}{ANSI_RESET}", record.verdict),
+            false => println!("\n{ANSI_RED}{}{ANSI_RESET}", record.verdict)
+        }
+
+        match (&record.interpreter, record.interpreter_found)
+        {
+            (Some(interpreter), Some(true)) => println!("Interpreter {interpreter} is present on this system."),
+            (Some(interpreter), _) => println!("Interpreter {interpreter} is missing on this system, exec fails with \"No such file or directory\"."),
+            (None, _) if elf_obj.header.e_type == ET_REL || elf_obj.header.e_type == ET_CORE => println!("Not an executable ({}), the kernel cannot run it directly.", et_to_str(elf_obj.header.e_type)),
+            (None, _) => println!("No PT_INTERP, statically linked (no dynamic linker needed).")
+        }
+    }
+
+
+    /* A kernel module is an ET_REL object carrying a .modinfo section of NUL separated key=value strings */
+    pub(super) fn parse_kernel_module(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let modinfo: &[u8] = match (elf_obj.header.e_type, return_section_data(elf_obj, data, ".modinfo"))
+        {
+            (ET_REL, Some(modinfo)) => modinfo,
+
+            _ => {
+                eprintln!("Error - binary is not a kernel module (ET_REL with a .modinfo section)!");
+                std::process::exit(-1);
+            }
+        };
+
+        let fields: Vec<(String, String)> = modinfo.split(|&b| b == 0)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let entry: String = String::from_utf8_lossy(entry).to_string();
+                let (key, value) = entry.split_once('=')?;
+
+                Some((key.to_string(), value.to_string()))
+            })
+            .collect();
+
+        /* __ksymtab_strings holds the names behind every EXPORT_SYMBOL()/EXPORT_SYMBOL_GPL() */
+        let exported_symbols: Vec<String> = return_section_data(elf_obj, data, "__ksymtab_strings").unwrap_or(&[])
+            .split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).to_string())
+            .collect();
+
+        let module: KernelModuleRecord = KernelModuleRecord {
+            vermagic: fields.iter().find(|(key, _)| key == "vermagic").map(|(_, value)| value.clone()),
+            modinfo: fields,
+            exported_symbols
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("kernel_module", &module);
+            return;
+        }
+
+        println!("\nKernel Module =>");
+
+        /* Has to match the running kernel exactly or the module is refused at insmod time */
+        println!("\nVERMAGIC : {}", module.vermagic.as_deref().unwrap_or("Not present (module will not load on a stock kernel)"));
+
+        let mut modinfo_table: Table = self.new_table();
+
+        modinfo_table.set_header(vec![
+                Cell::new("Key").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (key, value) in &module.modinfo
+        {
+            let value_cell: Cell = match key.as_str()
+            {
+                "vermagic" => Cell::new(value).fg(Color::Red).add_attribute(Attribute::Bold),
+                _ => Cell::new(value).fg(Color::Yellow)
+            };
+
+            modinfo_table.add_row(vec![
+                Cell::new(key).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                value_cell
+            ]);
+        }
+
+        println!("\n{modinfo_table}");
+
+        println!("\nExported Kernel Symbols =>");
+
+        if module.exported_symbols.is_empty()
+        {
+            println!("\n* Module exports no symbols (__ksymtab_strings missing or empty).");
+            return;
+        }
+
+        let mut ksym_table: Table = self.new_table();
+
+        ksym_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for name in &module.exported_symbols
+        {
+            ksym_table.add_row(vec![
+                Cell::new(name).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+            ]);
+        }
+
+        println!("\n{ksym_table}");
+    }
+
+    pub(crate) fn parse_core_file(&self, elf_obj: &Elf, data: &[u8])
+    {
+        if elf_obj.header.e_type != ET_CORE
+        {
+            eprintln!("Error - --core expects an ELF core dump (ET_CORE), this binary is {}!", et_to_str(elf_obj.header.e_type));
+            std::process::exit(-1);
+        }
+
+        let core: CoreReportRecord = return_core_report(elf_obj, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("core", &core);
+            return;
+        }
+
+        println!("\nCore Dump =>");
+
+        if let Some(process) = &core.process
+        {
+            let mut process_table: Table = self.new_table();
+
+            process_table.set_header(vec![
+                    Cell::new("Process (NT_PRPSINFO)").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for (field, value) in [
+                ("Executable", process.executable.clone()),
+                ("Arguments", process.arguments.clone()),
+                ("PID", process.pid.to_string()),
+                ("PPID", process.ppid.to_string()),
+                ("UID/GID", format!("{}/{}", process.uid, process.gid)),
+                ("State", process.state.clone())
+            ]
+            {
+                process_table.add_row(vec![
+                    Cell::new(field).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(value).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("\n{process_table}");
+        }
+
+        match &core.signal
+        {
+            Some(signal) => {
+                print!("\nKilled by signal {} ({}), si_code {}", signal.number, signal.name, signal.code);
+
+                match signal.fault_address
+                {
+                    Some(address) => println!(", fault address {address:#x}"),
+                    None => println!()
+                }
+            },
+
+            None => println!("\nNo crash signal recorded (NT_SIGINFO/NT_PRSTATUS missing).")
+        }
+
+        for thread in &core.threads
+        {
+            let mut register_table: Table = self.new_table();
+
+            register_table.set_header(vec![
+                    Cell::new(format!("Thread {} (NT_PRSTATUS)", thread.pid)).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for register in &thread.registers
+            {
+                register_table.add_row(vec![
+                    Cell::new(&register.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(format!("{:#018x}", register.value)).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("\n{register_table}");
+        }
+
+        if !core.mapped_files.is_empty()
+        {
+            let mut file_table: Table = self.new_table();
+
+            file_table.set_header(vec![
+                    Cell::new("Mapped File (NT_FILE)").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Address Range").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("File Offset").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for file in &core.mapped_files
+            {
+                file_table.add_row(vec![
+                    Cell::new(&file.path).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(format!("{:#x}-{:#x}", file.start, file.end)).fg(Color::Yellow),
+                    Cell::new(format!("{:#x}", file.offset)).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("\n{file_table}");
+        }
+
+        println!("\n{} thread(s), {} file mapping(s).", core.threads.len(), core.mapped_files.len());
+    }
+
+    pub(crate) fn parse_abi_tag(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let abi_tag: Option<AbiTagRecord> = return_abi_tag(elf_obj, data).map(|(os, major, minor, subminor)| AbiTagRecord {
+            os: return_abi_tag_os(os),
+            kernel_version: format!("{major}.{minor}.{subminor}")
+        });
+
+        if self.is_structured_output()
+        {
+            self.insert_document("abi_tag", &abi_tag);
+            return;
+        }
+
+        match abi_tag
+        {
+            Some(abi_tag) => println!("\nRequires {} kernel >= {}", abi_tag.os, abi_tag.kernel_version),
+            None => println!("\nNo ABI tag (minimum kernel version unspecified).")
+        }
+    }
+}
+
+
+/* Architecture specific e_flags masks (ARM, MIPS, RISC-V psABI) */
+const EF_ARM_EABIMASK: u32 = 0xff00_0000;
+const EF_ARM_INTERWORK: u32 = 0x0000_0004;
+const EF_ARM_ABI_FLOAT_SOFT: u32 = 0x0000_0200;
+
+const EF_ARM_BE8: u32 = 0x0080_0000;
+
+const EF_MIPS_NOREORDER: u32 = 0x0000_0001;
+const EF_MIPS_PIC: u32 = 0x0000_0002;
+const EF_MIPS_CPIC: u32 = 0x0000_0004;
+const EF_MIPS_ABI2: u32 = 0x0000_0020;
+const EF_MIPS_ABI: u32 = 0x0000_f000;
+const EF_MIPS_ARCH: u32 = 0xf000_0000;
+
+const EF_RISCV_RVC: u32 = 0x0001;
+const EF_RISCV_FLOAT_ABI: u32 = 0x0006;
+const EF_RISCV_RVE: u32 = 0x0008;
+const EF_RISCV_TSO: u32 = 0x0010;
+
+const ELF_MAGIC_LEN: usize = 4;
+
+enum ElfObjectType
+{
+    None,
+    Rel,
+    Exec,
+    Dyn,
+    Core
+}
+
+impl ElfObjectType
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            ElfObjectType::None => String::from("ET_NONE (No file type)"),
+            ElfObjectType::Rel => String::from("ET_REL (Relocatable file)"),
+            ElfObjectType::Exec => String::from("ET_EXEC (Executable file)"),
+            ElfObjectType::Dyn => String::from("ET_DYN (Shared object file)"),
+            ElfObjectType::Core => String::from("ET_CORE (Core file)")
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct KernelModuleRecord
+{
+    modinfo: Vec<(String, String)>,
+    vermagic: Option<String>,
+    exported_symbols: Vec<String>
+}
+
+#[derive(Serialize)]
+struct CoreProcessRecord
+{
+    pid: i32,
+    ppid: i32,
+    uid: u32,
+    gid: u32,
+    state: String,
+    executable: String,
+    arguments: String
+}
+
+#[derive(Serialize)]
+struct CoreSignalRecord
+{
+    number: i32,
+    name: String,
+    code: i32,
+    /* si_addr, only meaningful for the fault signals (SIGSEGV, SIGBUS, SIGILL, SIGFPE) */
+    fault_address: Option<u64>
+}
+
+#[derive(Serialize)]
+struct CoreRegisterRecord
+{
+    name: String,
+    value: u64
+}
+
+#[derive(Serialize)]
+struct CoreThreadRecord
+{
+    pid: i32,
+    signal: i32,
+    registers: Vec<CoreRegisterRecord>
+}
+
+#[derive(Serialize)]
+struct CoreMappedFileRecord
+{
+    start: u64,
+    end: u64,
+    /* File offset in bytes (NT_FILE stores it in pages) */
+    offset: u64,
+    path: String
+}
+
+#[derive(Serialize)]
+struct CoreReportRecord
+{
+    process: Option<CoreProcessRecord>,
+    signal: Option<CoreSignalRecord>,
+    threads: Vec<CoreThreadRecord>,
+    mapped_files: Vec<CoreMappedFileRecord>
+}
+
+#[derive(Serialize)]
+struct ArchCompatRecord
+{
+    binary_machine: String,
+    binary_class: String,
+    binary_endianness: String,
+    host_machine: String,
+    host_class: String,
+    host_endianness: String,
+    compatible: bool,
+    verdict: String,
+    interpreter: Option<String>,
+    interpreter_found: Option<bool>
+}
+
+#[derive(Serialize)]
+struct AbiTagRecord
+{
+    os: String,
+    kernel_version: String
+}
+
+/* Decoded e_ident, see parse_elf_ident() */
+struct ElfIdent
+{
+    magic_hex: String,
+    class: u8,
+    data: u8,
+    version: u8,
+    osabi: u8,
+    abiversion: u8
+}
+
+#[derive(Serialize)]
+struct HeaderRecord
+{
+    header_size: u16,
+    magic: String,
+    class: u8,
+    data: u8,
+    version: u8,
+    osabi: u8,
+    abi_version: u8,
+    endian: Option<String>,
+    e_type: String,
+    e_machine: String,
+    e_version: u32,
+    entry: u64,
+    e_shstrndx: String
+}
+
+
+/* Register names of the elf_prstatus pr_reg array (struct user_regs_struct order), None for unsupported machines */
+fn return_core_register_names(machine: u16) -> Option<Vec<String>>
+{
+    let names: Vec<String> = match machine
+    {
+        EM_X86_64 => ["r15", "r14", "r13", "r12", "rbp", "rbx", "r11", "r10", "r9", "r8", "rax", "rcx", "rdx", "rsi", "rdi", "orig_rax",
+            "rip", "cs", "eflags", "rsp", "ss", "fs_base", "gs_base", "ds", "es", "fs", "gs"].iter().map(|name| name.to_string()).collect(),
+        EM_386 => ["ebx", "ecx", "edx", "esi", "edi", "ebp", "eax", "ds", "es", "fs", "gs", "orig_eax", "eip", "cs", "eflags", "esp", "ss"]
+            .iter().map(|name| name.to_string()).collect(),
+        EM_AARCH64 => (0..31).map(|index| format!("x{index}")).chain(["sp", "pc", "pstate"].iter().map(|name| name.to_string())).collect(),
+
+        _ => return None
+    };
+
+    Some(names)
+}
+
+
+fn return_signal_name(signal: i32) -> String
+{
+    match signal
+    {
+        1 => String::from("SIGHUP"),
+        2 => String::from("SIGINT"),
+        3 => String::from("SIGQUIT"),
+        4 => String::from("SIGILL"),
+        5 => String::from("SIGTRAP"),
+        6 => String::from("SIGABRT"),
+        7 => String::from("SIGBUS"),
+        8 => String::from("SIGFPE"),
+        9 => String::from("SIGKILL"),
+        11 => String::from("SIGSEGV"),
+        13 => String::from("SIGPIPE"),
+        14 => String::from("SIGALRM"),
+        15 => String::from("SIGTERM"),
+        24 => String::from("SIGXCPU"),
+        25 => String::from("SIGXFSZ"),
+        31 => String::from("SIGSYS"),
+
+        _ => format!("signal {signal}")
+    }
+}
+
+
+/*
+    Linux core dump notes (PT_NOTE, owner "CORE"), layouts from <linux/elfcore.h>:
+    NT_PRSTATUS per thread, NT_PRPSINFO and NT_SIGINFO for the crashing process, NT_FILE for the file backed mappings.
+*/
+fn return_core_report(elf: &Elf, data: &[u8]) -> CoreReportRecord
+{
+    /* Word sized fields follow the ELF class, note contents are in the file byte order */
+    let read = |bytes: &[u8], offset: usize, size: usize| -> Option<u64> {
+        let field: &[u8] = bytes.get(offset..offset + size)?;
+
+        Some(match elf.little_endian
+        {
+            true => field.iter().rev().fold(0, |value, &b| value << 8 | b as u64),
+            false => field.iter().fold(0, |value, &b| value << 8 | b as u64)
+        })
+    };
+
+    let word_size: usize = match elf.is_64 { true => 8, false => 4 };
+    let c_string = |bytes: &[u8]| -> String { String::from_utf8_lossy(bytes.split(|&b| b == 0).next().unwrap_or(&[])).trim_end().to_string() };
+
+    let mut report: CoreReportRecord = CoreReportRecord { process: None, signal: None, threads: Vec::new(), mapped_files: Vec::new() };
+
+    /* elf_prstatus: pr_pid sits after the siginfo/cursig/sigpend/sighold block, pr_reg after the 4 timevals */
+    let (pid_offset, reg_offset): (usize, usize) = match elf.is_64 { true => (32, 112), false => (24, 72) };
+
+    let notes = match elf.iter_note_headers(data)
+    {
+        Some(notes) => notes,
+        None => return report
+    };
+
+    for note in notes.flatten().filter(|note| note.name == "CORE" || note.name == "LINUX")
+    {
+        let desc: &[u8] = note.desc;
+
+        match note.n_type
+        {
+            NT_PRSTATUS => {
+                let registers: Vec<CoreRegisterRecord> = return_core_register_names(elf.header.e_machine).unwrap_or_default().into_iter().enumerate()
+                    .filter_map(|(index, name)| Some(CoreRegisterRecord { value: read(desc, reg_offset + index * word_size, word_size)?, name }))
+                    .collect();
+
+                report.threads.push(CoreThreadRecord {
+                    pid: read(desc, pid_offset, 4).unwrap_or(0) as i32,
+                    signal: read(desc, 12, 2).unwrap_or(0) as i16 as i32,
+                    registers
+                });
+            },
+
+            NT_PRPSINFO => {
+                /* pr_uid/pr_gid are 16-bit on the 32-bit ABIs */
+                let (uid, gid, pid, name, args): (Option<u64>, Option<u64>, usize, usize, usize) = match elf.is_64
+                {
+                    true => (read(desc, 16, 4), read(desc, 20, 4), 24, 40, 56),
+                    false => (read(desc, 8, 2), read(desc, 10, 2), 12, 28, 44)
+                };
+
+                report.process = Some(CoreProcessRecord {
+                    pid: read(desc, pid, 4).unwrap_or(0) as i32,
+                    ppid: read(desc, pid + 4, 4).unwrap_or(0) as i32,
+                    uid: uid.unwrap_or(0) as u32,
+                    gid: gid.unwrap_or(0) as u32,
+                    state: match desc.get(1)
+                    {
+                        Some(&sname) if sname != 0 => (sname as char).to_string(),
+                        _ => String::from("?")
+                    },
+                    executable: c_string(desc.get(name..name + 16).unwrap_or(&[])),
+                    arguments: c_string(desc.get(args..args + 80).unwrap_or(&[]))
+                });
+            },
+
+            NT_SIGINFO => {
+                let number: i32 = read(desc, 0, 4).unwrap_or(0) as i32;
+
+                /* si_addr follows the 3 int header, aligned to the pointer size */
+                let fault_address: Option<u64> = match number
+                {
+                    4 | 7 | 8 | 11 => read(desc, match elf.is_64 { true => 16, false => 12 }, word_size),
+                    _ => None
+                };
+
+                report.signal = Some(CoreSignalRecord {
+                    number,
+                    name: return_signal_name(number),
+                    code: read(desc, 8, 4).unwrap_or(0) as i32,
+                    fault_address
+                });
+            },
+
+            NT_FILE => {
+                /* The count is file data, never trust more entries than the descriptor can hold */
+                let count: usize = (read(desc, 0, word_size).unwrap_or(0) as usize).min((desc.len() / word_size).saturating_sub(2) / 3);
+                let page_size: u64 = read(desc, word_size, word_size).unwrap_or(0);
+                let names_offset: usize = word_size * (2 + count * 3);
+
+                let mut names = desc.get(names_offset..).unwrap_or(&[]).split(|&b| b == 0);
+
+                for index in 0..count
+                {
+                    let entry: usize = word_size * (2 + index * 3);
+
+                    report.mapped_files.push(CoreMappedFileRecord {
+                        start: read(desc, entry, word_size).unwrap_or(0),
+                        end: read(desc, entry + word_size, word_size).unwrap_or(0),
+                        offset: read(desc, entry + 2 * word_size, word_size).and_then(|pages| pages.checked_mul(page_size)).unwrap_or(0),
+                        path: String::from_utf8_lossy(names.next().unwrap_or(&[])).to_string()
+                    });
+                }
+            },
+
+            _ => ()
+        }
+    }
+
+    /* Cores written without NT_SIGINFO (older kernels) still carry the signal in pr_cursig */
+    if report.signal.is_none()
+    {
+        if let Some(thread) = report.threads.first().filter(|thread| thread.signal != 0)
+        {
+            report.signal = Some(CoreSignalRecord { number: thread.signal, name: return_signal_name(thread.signal), code: 0, fault_address: None });
+        }
+    }
+
+    report
+}
+
+
+/* Decode the processor specific e_flags word into (property, value) pairs */
+fn return_elf_eflags(emachine_id: u16, e_flags: u32) -> Vec<(String, String)>
+{
+    let mut arch_flags: Vec<(String, String)> = vec![("Machine".to_string(), return_elf_emachine(emachine_id))];
+
+    /* x86 defines no e_flags at all, showing a raw 0x0 there is just noise */
+    if emachine_id != EM_X86_64 && emachine_id != EM_386
+    {
+        arch_flags.push(("e_flags".to_string(), format!("{:#010x}", e_flags)));
+    }
+
+    let flag_str = |set: bool| -> String { match set { true => "Yes".to_string(), false => "No".to_string() } };
+
+    match emachine_id
+    {
+        EM_ARM => {
+            let eabi_version: u32 = (e_flags & EF_ARM_EABIMASK) >> 24;
+
+            arch_flags.push(("EABI version".to_string(), match eabi_version { 0 => "Unknown (GNU/legacy)".to_string(), v => format!("EABI{v}") }));
+            arch_flags.push(("Float ABI".to_string(), match (e_flags & EF_ARM_ABI_FLOAT_HARD != 0, e_flags & EF_ARM_ABI_FLOAT_SOFT != 0)
+            {
+                (true, _) => "Hard-float (VFP registers)".to_string(),
+                (false, true) => "Soft-float".to_string(),
+                (false, false) => "Unspecified".to_string()
+            }));
+
+            /* EF_ARM_INTERWORK is only meaningful for pre-EABI objects, EABI mandates interworking */
+            arch_flags.push(("Thumb interworking".to_string(), flag_str(e_flags & EF_ARM_INTERWORK != 0 || eabi_version >= 4)));
+            arch_flags.push(("AAPCS compliant".to_string(), flag_str(eabi_version >= 4)));
+            arch_flags.push(("BE8 byte order".to_string(), flag_str(e_flags & EF_ARM_BE8 != 0)));
+        },
+
+        EM_MIPS | EM_MIPS_RS3_LE => {
+            arch_flags.push(("ISA level".to_string(), match (e_flags & EF_MIPS_ARCH) >> 28
+            {
+                0 => "MIPS I".to_string(),
+                1 => "MIPS II".to_string(),
+                2 => "MIPS III".to_string(),
+                3 => "MIPS IV".to_string(),
+                4 => "MIPS V".to_string(),
+                5 => "MIPS32".to_string(),
+                6 => "MIPS64".to_string(),
+                7 => "MIPS32 Release 2".to_string(),
+                8 => "MIPS64 Release 2".to_string(),
+                9 => "MIPS32 Release 6".to_string(),
+                10 => "MIPS64 Release 6".to_string(),
+
+                level => format!("Unknown ({level})")
+            }));
+
+            arch_flags.push(("ABI".to_string(), match ((e_flags & EF_MIPS_ABI) >> 12, e_flags & EF_MIPS_ABI2 != 0)
+            {
+                (_, true) => "N32".to_string(),
+                (1, _) => "O32".to_string(),
+                (2, _) => "O64".to_string(),
+                (3, _) => "EABI32".to_string(),
+                (4, _) => "EABI64".to_string(),
+
+                /* 64-bit objects without an ABI field use N64 */
+                _ => "Unspecified (N64 for ELF64)".to_string()
+            }));
+
+            arch_flags.push(("PIC".to_string(), flag_str(e_flags & EF_MIPS_PIC != 0)));
+            arch_flags.push(("CPIC (calls PIC code)".to_string(), flag_str(e_flags & EF_MIPS_CPIC != 0)));
+            arch_flags.push(("No reorder".to_string(), flag_str(e_flags & EF_MIPS_NOREORDER != 0)));
+        },
+
+        EM_RISCV => {
+            arch_flags.push(("Compressed (RVC)".to_string(), flag_str(e_flags & EF_RISCV_RVC != 0)));
+            arch_flags.push(("Float ABI".to_string(), match e_flags & EF_RISCV_FLOAT_ABI
+            {
+                0x0 => "None (soft-float)".to_string(),
+                0x2 => "Single".to_string(),
+                0x4 => "Double".to_string(),
+
+                _ => "Quad".to_string()
+            }));
+
+            arch_flags.push(("Reduced registers (RVE)".to_string(), flag_str(e_flags & EF_RISCV_RVE != 0)));
+            arch_flags.push(("Total store ordering (TSO)".to_string(), flag_str(e_flags & EF_RISCV_TSO != 0)));
+        },
+
+        EM_X86_64 | EM_386 => arch_flags.push(("Flags".to_string(), "No machine-specific flags".to_string())),
+
+        _ => arch_flags.push(("Flags".to_string(), "Decoding not supported for this architecture".to_string()))
+    }
+
+    arch_flags
+}
+
+
+fn return_elf_etype(elf: &Elf) -> String 
+{
+    let hdr_etype: u16 = elf.header.e_type;
+            
+    let etype_variant: ElfObjectType = match hdr_etype
+    {
+        0 => ElfObjectType::None,
+        1 => ElfObjectType::Rel,
+        2 => ElfObjectType::Exec,
+        3 => ElfObjectType::Dyn,
+        4 => ElfObjectType::Core,
+
+        _ => ElfObjectType::None
+    };
+
+    etype_variant.get_type()
+}
+
+/* e_machine of the architecture this binarymagic build runs on, EM_NONE for targets without a mapping */
+fn return_host_machine() -> u16
+{
+    match std::env::consts::ARCH
+    {
+        "x86_64" => EM_X86_64,
+        "x86" => EM_386,
+        "aarch64" => EM_AARCH64,
+        "arm" => EM_ARM,
+        "riscv64" | "riscv32" => EM_RISCV,
+        "powerpc64" => EM_PPC64,
+        "powerpc" => EM_PPC,
+        "mips" | "mips64" => EM_MIPS,
+        "s390x" => EM_S390,
+        "sparc64" => EM_SPARCV9,
+
+        _ => EM_NONE
+    }
+}
+
+
+/* (compatible, verdict) of e_machine/EI_CLASS/EI_DATA against the host */
+fn check_arch_compat(elf: &Elf) -> (bool, String)
+{
+    let host_machine: u16 = return_host_machine();
+    let host_is_64: bool = cfg!(target_pointer_width = "64");
+    let host_is_little: bool = cfg!(target_endian = "little");
+
+    let binary_machine: u16 = elf.header.e_machine;
+    let binary_is_64: bool = elf.header.e_ident[EI_CLASS] == ELFCLASS64;
+    let binary_is_little: bool = elf.header.e_ident[EI_DATA] == ELFDATA2LSB;
+
+    match (binary_machine, host_machine)
+    {
+        /* 64-bit CPUs that also execute the 32-bit instruction set of their family */
+        (EM_386, EM_X86_64) => (false, String::from("32-bit binary on 64-bit host (may work via multilib, needs the i386 libraries)")),
+        (EM_ARM, EM_AARCH64) => (false, String::from("32-bit ARM binary on AArch64 host (may work if the CPU and kernel support AArch32)")),
+
+        (binary, host) if binary != host => (false, format!("Incompatible architecture: binary is {}, host is {}", machine_to_str(binary), machine_to_str(host))),
+
+        _ if binary_is_little != host_is_little => (false, format!("Incompatible endianness: binary is {}-endian, host is {}-endian",
+            match binary_is_little { true => "little", false => "big" },
+            match host_is_little { true => "little", false => "big" }
+        )),
+
+        (EM_X86_64, _) if !binary_is_64 && host_is_64 => (false, String::from("x32 ABI binary (ILP32 on x86-64, needs CONFIG_X86_X32_ABI)")),
+        _ if !binary_is_64 && host_is_64 => (false, String::from("32-bit binary on 64-bit host (may work via multilib)")),
+        _ if binary_is_64 && !host_is_64 => (false, String::from("64-bit binary on 32-bit host (cannot run)")),
+
+        _ => (true, String::from("Compatible (same arch, class, endianness)"))
+    }
+}
+
+
+/* EI_CLASS against the host pointer width, None when they agree */
+fn check_elf_class_vs_host(elf: &Elf) -> Option<String>
+{
+    let elf_bits: u32 = match elf.header.e_ident[EI_CLASS]
+    {
+        ELFCLASS32 => 32,
+        ELFCLASS64 => 64,
+
+        _ => return None
+    };
+
+    match (elf_bits, usize::BITS)
+    {
+        (32, 64) => Some(String::from("Note: Analyzing a 32-bit ELF on a 64-bit host \u{2014} addresses truncated to 32 bits.")),
+        (64, 32) => Some(String::from("Note: Analyzing a 64-bit ELF on a 32-bit host \u{2014} addresses wider than the host pointer size.")),
+
+        _ => None
+    }
+}
+
+/* .note.ABI-tag descriptor: OS, then the major/minor/subminor kernel version, in file byte order */
+fn return_abi_tag(elf_obj: &Elf, data: &[u8]) -> Option<(u32, u32, u32, u32)>
+{
+    let note = elf_obj.iter_note_sections(data, Some(".note.ABI-tag"))?
+        .flatten()
+        .find(|note| note.n_type == NT_GNU_ABI_TAG && note.name == "GNU" && note.desc.len() >= 16)?;
+
+    let word = |index: usize| -> u32 {
+        let bytes: [u8; 4] = [note.desc[index * 4], note.desc[index * 4 + 1], note.desc[index * 4 + 2], note.desc[index * 4 + 3]];
+
+        match elf_obj.little_endian
+        {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes)
+        }
+    };
+
+    Some((word(0), word(1), word(2), word(3)))
+}
+
+fn return_abi_tag_os(os: u32) -> String
+{
+    match os
+    {
+        ELF_NOTE_OS_LINUX => String::from("Linux"),
+        ELF_NOTE_OS_GNU => String::from("GNU/Hurd"),
+        ELF_NOTE_OS_SOLARIS2 => String::from("Solaris"),
+        ELF_NOTE_OS_FREEBSD => String::from("FreeBSD"),
+
+        _ => format!("Unknown OS ({os})")
+    }
+}
+
+
+/* e_ident[EI_MAG0..EI_ABIVERSION] without any output, the caller decides what to print */
+fn parse_elf_ident(magic: &[u8; 16]) -> ElfIdent
+{
+    ElfIdent {
+        magic_hex: magic.iter().map(|byte| format!("{:02x?}", byte)).collect::<Vec<String>>().join(" "),
+        class: magic[4],
+        data: magic[5],
+        version: magic[6],
+        osabi: magic[7],
+        abiversion: magic[8]
+    }
+}
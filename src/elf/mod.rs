@@ -0,0 +1,721 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* ELF analysis, one submodule per area. The method dispatch and the helpers shared between areas live here */
+
+mod code;
+mod dynamic;
+mod info;
+mod sections;
+mod security;
+mod symbols;
+
+use super::*;
+
+impl ProgramArgumentMethod
+{
+    pub(crate) fn start_method_selector(&self, args: &Arguments, elf_obj: &Elf, data: &[u8], flag: &str)
+    {
+        match &self
+        {
+            ProgramArgumentMethod::Sections => args.parse_header_sections(elf_obj, data),
+            ProgramArgumentMethod::DynamicSymbols => args.parse_dynamic_syms(elf_obj),
+
+            ProgramArgumentMethod::DynamicLibraries => { 
+                let libs: Vec<String> = args.parse_dynamic_libs(elf_obj);
+                
+                args.print_dynamic_libs(elf_obj, libs);
+            },
+
+            ProgramArgumentMethod::Security => args.parse_security(elf_obj, data),
+            ProgramArgumentMethod::SymTabAnnotated => args.parse_annotated_symtab(elf_obj, data),
+            ProgramArgumentMethod::SizeBreakdown => args.parse_size_breakdown(elf_obj, data),
+            ProgramArgumentMethod::ArchInfo => args.parse_arch_info(elf_obj),
+            ProgramArgumentMethod::CheckStripped => args.check_stripped(elf_obj, true),
+            ProgramArgumentMethod::CheckNotStripped => args.check_stripped(elf_obj, false),
+            ProgramArgumentMethod::Dynamic => args.parse_dynamic_section(elf_obj, data),
+            ProgramArgumentMethod::ImportsCount => args.print_symbol_count(elf_obj, true),
+            ProgramArgumentMethod::ExportsCount => args.print_symbol_count(elf_obj, false),
+            ProgramArgumentMethod::SectionContent => args.parse_section_content(elf_obj, data),
+            ProgramArgumentMethod::CheckPie => args.check_pie(elf_obj),
+            ProgramArgumentMethod::DumpSectionRaw => args.dump_section_raw(elf_obj, data),
+            ProgramArgumentMethod::HashStats => args.parse_sysv_hash(elf_obj, data),
+            ProgramArgumentMethod::PerFileSymbols => args.parse_per_file_symbols(elf_obj),
+            ProgramArgumentMethod::RebaseSections => args.parse_rebased_addresses(elf_obj),
+            ProgramArgumentMethod::FindGadgets => args.find_rop_gadgets(elf_obj, data),
+            ProgramArgumentMethod::SectionGaps => args.parse_section_gaps(elf_obj),
+            ProgramArgumentMethod::InterpElf => args.parse_interp_elf(elf_obj),
+            ProgramArgumentMethod::PltSize => args.parse_plt_size(elf_obj, data),
+            ProgramArgumentMethod::KernelModule => args.parse_kernel_module(elf_obj, data),
+            ProgramArgumentMethod::ReconstructImports => args.reconstruct_imports(elf_obj),
+            ProgramArgumentMethod::HashCompare => args.hash_compare(Some(elf_obj), data),
+            ProgramArgumentMethod::CompressedSections => args.parse_compressed_sections(elf_obj, data),
+            ProgramArgumentMethod::CountRelocations => args.parse_relocation_counts(elf_obj, data),
+            ProgramArgumentMethod::CheckNx => args.check_nx(elf_obj),
+            ProgramArgumentMethod::FindString => args.find_string(elf_obj, data),
+            ProgramArgumentMethod::TimelineJson => args.parse_timeline_json(elf_obj, data),
+            ProgramArgumentMethod::ListSectionTypes => args.list_section_types(elf_obj),
+            ProgramArgumentMethod::SegmentSections => args.parse_segment_section_mapping(elf_obj),
+            ProgramArgumentMethod::CheckFortify => args.parse_fortify_level(elf_obj),
+            ProgramArgumentMethod::StripDetectLevel => args.parse_stripping_level(elf_obj),
+            ProgramArgumentMethod::AbiTag => args.parse_abi_tag(elf_obj, data),
+            ProgramArgumentMethod::CheckRelro => args.check_relro(elf_obj),
+            ProgramArgumentMethod::CrossSectionRefs => args.parse_cross_section_refs(elf_obj, data),
+            ProgramArgumentMethod::UnresolvedWeak => args.parse_unresolved_weak(elf_obj),
+            ProgramArgumentMethod::ElfClassCheck => args.check_elf_class(elf_obj),
+            ProgramArgumentMethod::RelocStats => args.parse_reloc_stats(elf_obj, data),
+            ProgramArgumentMethod::HashTableStats => args.compare_hash_tables(elf_obj, data),
+            ProgramArgumentMethod::CountPerType => args.parse_count_per_type(elf_obj),
+            ProgramArgumentMethod::CheckCanary => args.check_canary(elf_obj),
+            ProgramArgumentMethod::ArchCompat => args.parse_arch_compat(elf_obj),
+            ProgramArgumentMethod::Ifunc => args.parse_ifunc(elf_obj, data),
+            ProgramArgumentMethod::ShowAbsentFlags => args.parse_absent_flags(elf_obj),
+            ProgramArgumentMethod::LoadOrder => args.parse_load_order(elf_obj),
+            ProgramArgumentMethod::CheckTextrel => args.check_textrel(elf_obj),
+            ProgramArgumentMethod::CheckBindnow => args.check_bindnow(elf_obj),
+            ProgramArgumentMethod::ExecSections => args.parse_exec_sections(elf_obj),
+            ProgramArgumentMethod::WritableSections => args.parse_writable_sections(elf_obj),
+            ProgramArgumentMethod::XrefString => args.xref_string(elf_obj, data),
+            ProgramArgumentMethod::StrippedFunctions => args.parse_stripped_functions(elf_obj, data),
+            ProgramArgumentMethod::EmitIdaScript => args.emit_ida_script(elf_obj),
+            ProgramArgumentMethod::EmitGhidraScript => args.emit_ghidra_script(elf_obj, data),
+            ProgramArgumentMethod::ShowEntryPointContext => args.show_entry_point_context(elf_obj, data),
+            ProgramArgumentMethod::Core => args.parse_core_file(elf_obj, data),
+            ProgramArgumentMethod::ExportSymbols => args.export_symbols(elf_obj),
+            ProgramArgumentMethod::CheckSafestack => args.check_safe_stack(elf_obj, data),
+            ProgramArgumentMethod::PatchByte => args.patch_byte(elf_obj, data),
+            ProgramArgumentMethod::CheckShadowStack => args.check_cet_shadow_stack(elf_obj, data),
+            ProgramArgumentMethod::FindFormatStrings => args.find_format_strings(elf_obj, data),
+            ProgramArgumentMethod::UniqueImports => args.parse_unique_imports(elf_obj),
+            ProgramArgumentMethod::SectionTypeExtensions => args.parse_section_type_extensions(elf_obj),
+            ProgramArgumentMethod::EntryToMain => args.trace_entry_to_main(elf_obj, data),
+            ProgramArgumentMethod::OverlapCheck => args.check_segment_overlaps(elf_obj),
+            ProgramArgumentMethod::AuditImports => args.audit_imports(elf_obj),
+
+            _ => {
+                eprintln!("Error - option \"{}\" is not supported for ELF binaries!", flag);
+                std::process::exit(-1);
+            }
+        }
+    }
+}
+
+
+const EF_ARM_ABI_FLOAT_HARD: u32 = 0x0000_0400;
+
+/* Granularity the kernel and ld.so map and mprotect at (4 KiB on the common targets) */
+const LOAD_PAGE_SIZE: u64 = 0x1000;
+
+/* User section type names, relative to $XDG_CONFIG_HOME (or ~/.config) */
+const SECTION_TYPES_CONFIG: &str = "binarymagic/section_types.toml";
+
+/* Filled once from SECTION_TYPES_CONFIG, consulted for every sh_type without a built-in decoder */
+static CUSTOM_SECTION_TYPES: OnceLock<HashMap<u32, String>> = OnceLock::new();
+
+
+enum ElfSectionType
+{
+    ShtNull,            /* 0 = marks the section header as inactive */
+    ShtProgBits,        /* 1 = holds information defined by the program, whose format and meaning are determined solely by the program */
+    ShtSymTab,          /* 2 = hold a symbol table */
+    ShtStrTab,          /* 3 = section holds a string table. An object file may have multiple string table sections */
+    ShtRela,            /* 4 = section holds relocation entries with explicit addends, such as type Elf32_Rela for the 32-bit class of object files or type Elf64_Rela for the 64-bit class of object files */
+    ShtHash,            /* 5 = section holds a symbol hash table. Currently, an object file may have only one hash table*/
+    ShtDynamic,         /* 6 = section holds information for dynamic linking. Currently, an object file may have only one dynamic section */
+    ShtNote,            /* 7 = section holds information that marks the file in some way */
+    ShtNoBits,          /* 8 = section of this type occupies no space in the file but otherwise resembles ShtProgBits */
+    ShtRel,             /* 9 = section holds relocation entries without explicit addends. An object file may have multiple relocation sections */
+    ShtShLib,           /* 10 = section type is reserved but has unspecified semantics */
+    ShtDynSym,          /* 11 = hold a symbol table */
+    ShtInitArray,       /* 14 = section contains an array of pointers to initialization functions */
+    ShtFiniArray,       /* 15 = section contains an array of pointers to termination functions */
+    ShtPreInitArray,    /* 16 = section contains an array of pointers to functions that are invoked before all other initialization functions */
+    ShtGroup,           /* 17 = section defines a section group. A section group is a set of sections that are related and that must be treated specially by the linker */
+    ShtSymTabShndx,    /* 18 = section is associated with a section of type ShtSymTab and is required if any of the section header indexes referenced by that symbol table contain the escape value SHN_XINDEX */
+    ShtCustom(String),  /* user registered name from the section_types.toml config, for values BinaryMagic does not decode itself */
+    Unknown(u32),       /* any other sh_type, keeps the raw value so it is never mistaken for SHT_NULL */
+
+    // ShtLoos,            /* 0x60000000 = values in this inclusive range are reserved for operating system-specific semantics */
+    // ShtHios,            /* 0x6fffffff = values in this inclusive range are reserved for operating system-specific semantics */
+    // ShtLoProc,          /* 0x70000000 = values in this inclusive range are reserved for processor-specific semantics */
+    // ShtHiProc,          /* 0x7fffffff = values in this inclusive range are reserved for processor-specific semantics */    
+    // ShtLoUser,          /* 0x80000000 = this value specifies the lower bound of the range of indexes reserved for application programs */
+    // ShtHiUser           /* 0xffffffff = this value specifies the upper bound of the range of indexes reserved for application programs */
+}
+
+impl ElfSectionType 
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            ElfSectionType::ShtNull => "SHT_NULL".to_string(),
+            ElfSectionType::ShtProgBits => "SHT_PROGBITS".to_string(),
+            ElfSectionType::ShtSymTab => "SHT_SYMTAB".to_string(),
+            ElfSectionType::ShtStrTab => "SHT_STRTAB".to_string(),
+            ElfSectionType::ShtRela => "SHT_RELA".to_string(),
+            ElfSectionType::ShtHash => "SHT_HASH".to_string(),
+            ElfSectionType::ShtDynamic => "SHT_DYNAMIC".to_string(),
+            ElfSectionType::ShtNote => "SHT_NOTE".to_string(),
+            ElfSectionType::ShtNoBits => "SHT_NOBITS".to_string(),
+            ElfSectionType::ShtRel => "SHT_REL".to_string(),
+            ElfSectionType::ShtShLib => "SHT_SHLIB".to_string(),
+            ElfSectionType::ShtDynSym => "SHT_DYNSYM".to_string(),
+            ElfSectionType::ShtInitArray => "SHT_INIT_ARRAY".to_string(),
+            ElfSectionType::ShtFiniArray => "SHT_FINI_ARRAY".to_string(),
+            ElfSectionType::ShtPreInitArray => "SHT_PREINIT_ARRAY".to_string(),
+            ElfSectionType::ShtGroup => "SHT_GROUP".to_string(),
+            ElfSectionType::ShtSymTabShndx => "SHT_SYMTAB_SHNDX".to_string(),
+            ElfSectionType::ShtCustom(ref name) => name.clone(),
+            ElfSectionType::Unknown(sh_type) => format!("SHT_UNKNOWN({:#x})", sh_type)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AbsentFlagRecord
+{
+    kind: String,
+    name: String,
+    flags: String,
+    /* W^X violations also fail --check-nx */
+    wx_violation: bool,
+    concern: String
+}
+
+
+fn return_elf_section_type(sh_type: u32) -> ElfSectionType
+{
+    match sh_type
+    {
+        SHT_NULL => ElfSectionType::ShtNull,
+        SHT_PROGBITS => ElfSectionType::ShtProgBits,
+        SHT_SYMTAB => ElfSectionType::ShtSymTab,
+        SHT_STRTAB => ElfSectionType::ShtStrTab,
+        SHT_RELA => ElfSectionType::ShtRela,
+        SHT_HASH => ElfSectionType::ShtHash,
+        SHT_DYNAMIC => ElfSectionType::ShtDynamic,
+        SHT_NOTE => ElfSectionType::ShtNote,
+        SHT_NOBITS => ElfSectionType::ShtNoBits,
+        SHT_REL => ElfSectionType::ShtRel,
+        SHT_SHLIB => ElfSectionType::ShtShLib,
+        SHT_DYNSYM => ElfSectionType::ShtDynSym,
+        SHT_INIT_ARRAY => ElfSectionType::ShtInitArray,
+        SHT_FINI_ARRAY => ElfSectionType::ShtFiniArray,
+        SHT_PREINIT_ARRAY => ElfSectionType::ShtPreInitArray,
+        SHT_GROUP => ElfSectionType::ShtGroup,
+        SHT_SYMTAB_SHNDX => ElfSectionType::ShtSymTabShndx,
+
+        sh_type => match CUSTOM_SECTION_TYPES.get_or_init(load_custom_section_types).get(&sh_type)
+        {
+            Some(name) => ElfSectionType::ShtCustom(name.clone()),
+            None => ElfSectionType::Unknown(sh_type)
+        }
+    }
+}
+
+
+fn return_section_types_config_path() -> Option<PathBuf>
+{
+    std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join(SECTION_TYPES_CONFIG))
+}
+
+
+/* Only [section_types] is read, other tables are left for future settings */
+#[derive(Deserialize)]
+struct SectionTypesConfig
+{
+    #[serde(default)]
+    section_types: HashMap<String, String>
+}
+
+/* A missing file means no custom types, a file that is not valid TOML is reported and ignored */
+fn load_custom_section_types() -> HashMap<u32, String>
+{
+    let (path, contents): (PathBuf, String) = match return_section_types_config_path().and_then(|path| fs::read_to_string(&path).ok().map(|contents| (path, contents)))
+    {
+        Some(config) => config,
+        None => return HashMap::new()
+    };
+
+    match return_custom_section_types(&contents)
+    {
+        Ok((section_types, skipped)) => {
+            for key in skipped
+            {
+                eprintln!("Warning - {}: expected `<sh_type> = \"NAME\"`, skipping \"{key}\"", path.display());
+            }
+
+            section_types
+        },
+
+        Err(error) => {
+            eprintln!("Warning - {}: no custom section types loaded, {error}", path.display());
+            HashMap::new()
+        }
+    }
+}
+
+/* (sh_type to name, keys skipped for not being a hex or decimal sh_type or for an empty name) */
+fn return_custom_section_types(contents: &str) -> Result<(HashMap<u32, String>, Vec<String>), toml::de::Error>
+{
+    let config: SectionTypesConfig = toml::from_str(contents)?;
+
+    let mut section_types: HashMap<u32, String> = HashMap::new();
+    let mut skipped: Vec<String> = Vec::new();
+
+    for (key, name) in config.section_types
+    {
+        let sh_type: Option<u32> = match key.strip_prefix("0x").or_else(|| key.strip_prefix("0X"))
+        {
+            Some(hex) => u32::from_str_radix(&hex.replace('_', ""), 16).ok(),
+            None => key.replace('_', "").parse::<u32>().ok()
+        };
+
+        match sh_type
+        {
+            Some(sh_type) if !name.is_empty() => { section_types.insert(sh_type, name); },
+            _ => skipped.push(key)
+        }
+    }
+
+    skipped.sort();
+
+    Ok((section_types, skipped))
+}
+
+
+/* R_X86_64_IRELATIVE, R_386_IRELATIVE, R_AARCH64_IRELATIVE, ... */
+fn is_irelative(relocation_type: &str) -> bool
+{
+    relocation_type.ends_with("_IRELATIVE")
+}
+
+
+/* Sections and PT_LOAD segments missing the attributes their contents call for */
+fn return_absent_flags(elf: &Elf) -> Vec<AbsentFlagRecord>
+{
+    let mut records: Vec<AbsentFlagRecord> = Vec::new();
+
+    for shdr in elf.section_headers.iter().filter(|shdr| shdr.sh_flags & SHF_EXECINSTR as u64 != 0)
+    {
+        let name: String = elf.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string();
+
+        if shdr.sh_flags & SHF_ALLOC as u64 == 0
+        {
+            records.push(AbsentFlagRecord {
+                kind: String::from("section"),
+                name: name.clone(),
+                flags: return_section_flag_letters(shdr.sh_flags),
+                wx_violation: false,
+                concern: String::from("executable code that is never mapped (no SHF_ALLOC), a hidden payload or a tampered header")
+            });
+        }
+
+        if shdr.sh_flags & SHF_WRITE as u64 != 0
+        {
+            records.push(AbsentFlagRecord {
+                kind: String::from("section"),
+                name,
+                flags: return_section_flag_letters(shdr.sh_flags),
+                wx_violation: true,
+                concern: String::from("writable and executable, injected bytes can be run in place")
+            });
+        }
+    }
+
+    for (index, phdr) in elf.program_headers.iter().enumerate().filter(|(_, phdr)| phdr.p_type == PT_LOAD)
+    {
+        if phdr.p_flags & program_header::PF_W != 0 && phdr.p_flags & program_header::PF_X != 0
+        {
+            records.push(AbsentFlagRecord {
+                kind: String::from("segment"),
+                name: format!("PT_LOAD #{index}"),
+                flags: format!("{}WX", match phdr.p_flags & program_header::PF_R != 0 { true => 'R', false => '-' }),
+                wx_violation: true,
+                concern: String::from("mapped writable and executable, defeats NX for everything the segment covers")
+            });
+        }
+    }
+
+    records
+}
+
+
+/* STT_FUNC symbol (.symtab, then .dynsym) covering the address, as name+offset */
+fn return_function_at(elf: &Elf, address: u64) -> Option<String>
+{
+    elf.syms.iter().map(|symbol| (symbol, &elf.strtab))
+        .chain(elf.dynsyms.iter().map(|symbol| (symbol, &elf.dynstrtab)))
+        .filter(|(symbol, _)| symbol.st_type() == STT_FUNC && symbol.st_value <= address && address < symbol.st_value + symbol.st_size.max(1))
+        .find_map(|(symbol, strtab)| {
+            let name: &str = strtab.get_at(symbol.st_name).filter(|name| !name.is_empty())?;
+
+            match address - symbol.st_value
+            {
+                0 => Some(name.to_string()),
+                offset => Some(format!("{name}+{offset:#x}"))
+            }
+        })
+}
+
+
+/* readelf style flag key (W write, A alloc, X execute, M merge, S strings, I info, L link order, O OS, G group, T TLS, C compressed) */
+fn return_section_flag_letters(sh_flags: u64) -> String
+{
+    [
+        (SHF_WRITE, 'W'),
+        (SHF_ALLOC, 'A'),
+        (SHF_EXECINSTR, 'X'),
+        (SHF_MERGE, 'M'),
+        (SHF_STRINGS, 'S'),
+        (SHF_INFO_LINK, 'I'),
+        (SHF_LINK_ORDER, 'L'),
+        (SHF_OS_NONCONFORMING, 'O'),
+        (SHF_GROUP, 'G'),
+        (SHF_TLS, 'T'),
+        (SHF_COMPRESSED, 'C')
+    ]
+    .iter()
+    .filter(|(flag, _)| sh_flags & *flag as u64 != 0)
+    .map(|(_, letter)| *letter)
+    .collect()
+}
+
+
+fn return_elf_emachine(emachine_id: u16) -> String
+{
+    match emachine_id
+    {
+        EM_NONE => "No machine".to_string(),
+        EM_MIPS => "MIPS I Architecture".to_string(),
+        EM_PPC | EM_PPC64 => "PowerPC 32/64 bit".to_string(),
+        EM_X86_64 => "Intel/AMD 64-bit".to_string(),
+
+        _ => "Unknown".to_string()
+    }
+}
+
+/* Contents of the first candidate ld.so would accept, a file of another class or machine is skipped like ld.so skips it */
+fn return_needed_library(elf: &Elf, library: &str, search_dirs: &[PathBuf]) -> Option<Vec<u8>>
+{
+    /* A DT_NEEDED entry with a slash is a path, the search dirs are not consulted */
+    let candidates: Vec<PathBuf> = match library.contains('/')
+    {
+        true => vec![PathBuf::from(library)],
+        false => search_dirs.iter().map(|dir| dir.join(library)).collect()
+    };
+
+    candidates.iter()
+        .filter_map(|candidate| fs::read(candidate).ok())
+        .find(|lib_data| Elf::parse(lib_data).is_ok_and(|lib_elf| {
+            lib_elf.is_64 == elf.is_64 && lib_elf.little_endian == elf.little_endian && lib_elf.header.e_machine == elf.header.e_machine
+        }))
+}
+
+/* RUNPATH/RPATH with $ORIGIN expanded to the directory of the binary, then the system dirs for its class and machine */
+fn return_library_search_dirs(elf: &Elf, file: &str) -> Vec<PathBuf>
+{
+    let origin: PathBuf = fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file))
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map_or_else(|| PathBuf::from("."), |dir| dir.to_path_buf());
+
+    let origin: String = origin.display().to_string();
+
+    /* Debian multiarch dirs first, then the multilib dirs of RPM based distributions, then the plain ones */
+    let triplets: &[&str] = match (elf.header.e_machine, elf.is_64, elf.little_endian)
+    {
+        (EM_X86_64, true, _) => &["x86_64-linux-gnu"],
+        (EM_X86_64, false, _) => &["x86_64-linux-gnux32"],
+        (EM_386, _, _) => &["i386-linux-gnu"],
+        (EM_AARCH64, _, true) => &["aarch64-linux-gnu"],
+        (EM_AARCH64, _, false) => &["aarch64_be-linux-gnu"],
+        (EM_ARM, _, _) if elf.header.e_flags & EF_ARM_ABI_FLOAT_HARD != 0 => &["arm-linux-gnueabihf", "arm-linux-gnueabi"],
+        (EM_ARM, _, _) => &["arm-linux-gnueabi", "arm-linux-gnueabihf"],
+        (EM_RISCV, true, _) => &["riscv64-linux-gnu"],
+        (EM_PPC64, _, true) => &["powerpc64le-linux-gnu"],
+        (EM_PPC64, _, false) => &["powerpc64-linux-gnu"],
+        (EM_PPC, _, _) => &["powerpc-linux-gnu"],
+        (EM_S390, _, _) => &["s390x-linux-gnu"],
+        (EM_SPARCV9, _, _) => &["sparc64-linux-gnu"],
+        (EM_MIPS, true, true) => &["mips64el-linux-gnuabi64"],
+        (EM_MIPS, true, false) => &["mips64-linux-gnuabi64"],
+        (EM_MIPS, false, true) => &["mipsel-linux-gnu"],
+        (EM_MIPS, false, false) => &["mips-linux-gnu"],
+
+        _ => &[]
+    };
+
+    let multilib: &[&str] = match (elf.header.e_machine, elf.is_64)
+    {
+        (EM_X86_64, false) => &["/libx32", "/usr/libx32"],
+        (_, true) => &["/lib64", "/usr/lib64"],
+        (_, false) => &["/lib32", "/usr/lib32"]
+    };
+
+    let mut search_dirs: Vec<PathBuf> = elf.runpaths.iter().chain(elf.rpaths.iter())
+        .flat_map(|paths| paths.split(':'))
+        .map(|dir| PathBuf::from(dir.replace("${ORIGIN}", &origin).replace("$ORIGIN", &origin)))
+        .collect();
+
+    for triplet in triplets
+    {
+        search_dirs.push(Path::new("/lib").join(triplet));
+        search_dirs.push(Path::new("/usr/lib").join(triplet));
+    }
+
+    search_dirs.extend(multilib.iter().chain(["/lib", "/usr/lib"].iter()).map(PathBuf::from));
+
+    search_dirs
+}
+
+
+/* Lowest PT_LOAD virtual address, the address the image expects to be mapped at */
+fn return_load_base(phdrs: &[ProgramHeader]) -> Option<u64>
+{
+    phdrs.iter().filter(|phdr| phdr.p_type == PT_LOAD).map(|phdr| phdr.p_vaddr).min()
+}
+
+
+/* e_shnum is 0 when the section count does not fit in 16 bits, the real count is then section_headers[0].sh_size */
+fn get_true_shnum(elf: &Elf) -> usize
+{
+    match elf.header.e_shnum
+    {
+        0 => elf.section_headers.first().map_or(0, |shdr| shdr.sh_size as usize),
+        shnum => shnum as usize
+    }
+}
+
+
+/* e_shstrndx is SHN_XINDEX when the index does not fit in 16 bits, the real index is then section_headers[0].sh_link */
+fn get_true_shstrndx(elf: &Elf) -> usize
+{
+    match elf.header.e_shstrndx as u32
+    {
+        SHN_XINDEX => elf.section_headers.first().map_or(0, |shdr| shdr.sh_link as usize),
+        shstrndx => shstrndx as usize
+    }
+}
+
+
+/* e_shstrndx (after SHN_XINDEX) must name an in-bounds SHT_STRTAB section that fits inside the file */
+fn validate_shstrtab(elf: &Elf, file_size: usize) -> Result<(), String>
+{
+    if elf.section_headers.is_empty()
+    {
+        return Ok(());
+    }
+
+    let shstrndx: usize = get_true_shstrndx(elf);
+
+    let shdr = match (shstrndx, elf.section_headers.get(shstrndx))
+    {
+        (0, _) => return Err(String::from("e_shstrndx is SHN_UNDEF")),
+        (_, Some(shdr)) => shdr,
+        (_, None) => return Err(format!("e_shstrndx {} >= e_shnum {}", shstrndx, elf.section_headers.len()))
+    };
+
+    if shdr.sh_type != SHT_STRTAB
+    {
+        return Err(format!("section {} is {}, not SHT_STRTAB", shstrndx, return_elf_section_type(shdr.sh_type).get_type()));
+    }
+
+    if shdr.sh_size == 0 || shdr.sh_offset.saturating_add(shdr.sh_size) > file_size as u64
+    {
+        return Err(format!("section {} spans {:#x}..{:#x} of a {} byte file", shstrndx, shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size), file_size));
+    }
+
+    Ok(())
+}
+
+
+/* "27 (.shstrtab)", following SHN_XINDEX to section_headers[0].sh_link */
+fn return_shstrndx_label(elf: &Elf) -> String
+{
+    let shstrndx: usize = get_true_shstrndx(elf);
+
+    let section_name: &str = elf.section_headers.get(shstrndx)
+        .and_then(|shdr| elf.shdr_strtab.get_at(shdr.sh_name))
+        .unwrap_or("Not defined");
+
+    match elf.header.e_shstrndx as u32
+    {
+        SHN_UNDEF => String::from("0 (SHN_UNDEF, section names are not available)"),
+        SHN_XINDEX => format!("{shstrndx} ({section_name}) via SHN_XINDEX"),
+
+        _ => format!("{shstrndx} ({section_name})")
+    }
+}
+
+
+/* BIND_NOW may be requested through DT_BIND_NOW, DF_BIND_NOW in DT_FLAGS or DF_1_NOW in DT_FLAGS_1 */
+fn has_bind_now(elf: &Elf) -> bool
+{
+    match &elf.dynamic
+    {
+        Some(dynamic) => dynamic.dyns.iter().any(|d| match d.d_tag
+        {
+            DT_BIND_NOW => true,
+            DT_FLAGS => d.d_val & DF_BIND_NOW != 0,
+            DT_FLAGS_1 => d.d_val & DF_1_NOW != 0,
+
+            _ => false
+        }),
+
+        None => false
+    }
+}
+
+
+fn return_dwarf_line_rows(elf: &Elf, data: &[u8]) -> Vec<(u64, String, u64)>
+{
+    let endian: RunTimeEndian = match elf.little_endian
+    {
+        true => RunTimeEndian::Little,
+        false => RunTimeEndian::Big
+    };
+
+    let load_section = |id: SectionId| -> Result<EndianSlice<RunTimeEndian>, gimli::Error> {
+        Ok(EndianSlice::new(return_section_data(elf, data, id.name()).unwrap_or(&[]), endian))
+    };
+
+    let mut line_rows: Vec<(u64, String, u64)> = Vec::new();
+
+    let dwarf = match Dwarf::load(load_section)
+    {
+        Ok(dwarf) => dwarf,
+        Err(_) => return line_rows
+    };
+
+    let mut units = dwarf.units();
+
+    while let Ok(Some(unit_header)) = units.next()
+    {
+        let unit = match dwarf.unit(unit_header)
+        {
+            Ok(unit) => unit,
+            Err(_) => continue
+        };
+
+        let program = match unit.line_program.clone()
+        {
+            Some(program) => program,
+            None => continue
+        };
+
+        let mut rows = program.rows();
+
+        while let Ok(Some((line_header, row))) = rows.next_row()
+        {
+            if row.end_sequence()
+            {
+                continue;
+            }
+
+            let file_name: String = match row.file(line_header)
+            {
+                Some(file) => match dwarf.attr_string(&unit, file.path_name())
+                {
+                    Ok(name) => name.to_string_lossy().to_string(),
+                    Err(_) => continue
+                },
+
+                None => continue
+            };
+
+            if let Some(line) = row.line()
+            {
+                line_rows.push((row.address(), file_name, line.get()));
+            }
+        }
+    }
+
+    line_rows.sort_by_key(|row| row.0);
+    line_rows
+}
+
+fn return_elf_ctx(elf: &Elf) -> Ctx
+{
+    let container: Container = match elf.is_64 { true => Container::Big, false => Container::Little };
+    let endian: Endian = match elf.little_endian { true => Endian::Little, false => Endian::Big };
+
+    Ctx::new(container, endian)
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /* Sections past SHN_LORESERVE: e_shnum = 0 and e_shstrndx = SHN_XINDEX, the real values live in section_headers[0] */
+    #[test]
+    fn extended_section_numbering()
+    {
+        const SECTION_COUNT: usize = 0x10004;
+        const SHDR_SIZE: usize = 64;
+
+        let names: &[u8] = b"\0.filler\0.shstrtab\0.last\0";
+        let names_offset: usize = 64;
+        let shoff: usize = (names_offset + names.len() + 7) & !7;
+        let shstrndx: usize = SECTION_COUNT - 1;
+
+        let mut data: Vec<u8> = vec![0; shoff + SECTION_COUNT * SHDR_SIZE];
+
+        /* ELF64 little endian ET_REL header */
+        data[..7].copy_from_slice(&[0x7f, b'E', b'L', b'F', ELFCLASS64, ELFDATA2LSB, EV_CURRENT]);
+        data[16..18].copy_from_slice(&ET_REL.to_le_bytes());
+        data[18..20].copy_from_slice(&EM_X86_64.to_le_bytes());
+        data[20..24].copy_from_slice(&(EV_CURRENT as u32).to_le_bytes());
+        data[40..48].copy_from_slice(&(shoff as u64).to_le_bytes());
+        data[52..54].copy_from_slice(&64u16.to_le_bytes());
+        data[58..60].copy_from_slice(&(SHDR_SIZE as u16).to_le_bytes());
+        data[60..62].copy_from_slice(&0u16.to_le_bytes());
+        data[62..64].copy_from_slice(&(SHN_XINDEX as u16).to_le_bytes());
+
+        data[names_offset..names_offset + names.len()].copy_from_slice(names);
+
+        /* sh_name, sh_type, sh_offset, sh_size and sh_link are the only fields the lookups read */
+        let mut write_shdr = |index: usize, name: u32, sh_type: u32, offset: u64, size: u64, link: u32| {
+            let shdr: usize = shoff + index * SHDR_SIZE;
+
+            data[shdr..shdr + 4].copy_from_slice(&name.to_le_bytes());
+            data[shdr + 4..shdr + 8].copy_from_slice(&sh_type.to_le_bytes());
+            data[shdr + 24..shdr + 32].copy_from_slice(&offset.to_le_bytes());
+            data[shdr + 32..shdr + 40].copy_from_slice(&size.to_le_bytes());
+            data[shdr + 40..shdr + 44].copy_from_slice(&link.to_le_bytes());
+        };
+
+        write_shdr(0, 0, SHT_NULL, 0, SECTION_COUNT as u64, shstrndx as u32);
+
+        for index in 1..SECTION_COUNT - 2
+        {
+            write_shdr(index, 1, SHT_PROGBITS, 0, 0, 0);
+        }
+
+        write_shdr(SECTION_COUNT - 2, 19, SHT_PROGBITS, 0, 0, 0);
+        write_shdr(shstrndx, 9, SHT_STRTAB, names_offset as u64, names.len() as u64, 0);
+
+        let elf_obj: Elf = Elf::parse(&data).expect("hand built ELF must parse");
+
+        assert_eq!(elf_obj.header.e_shnum, 0);
+        assert_eq!(elf_obj.section_headers.len(), SECTION_COUNT);
+        assert_eq!(get_true_shnum(&elf_obj), SECTION_COUNT);
+        assert_eq!(get_true_shstrndx(&elf_obj), shstrndx);
+        assert_eq!(validate_shstrtab(&elf_obj, data.len()), Ok(()));
+
+        let section_name = |index: usize| -> Option<&str> { elf_obj.shdr_strtab.get_at(elf_obj.section_headers[index].sh_name) };
+
+        assert_eq!(section_name(1), Some(".filler"));
+        assert_eq!(section_name(SECTION_COUNT - 2), Some(".last"));
+        assert_eq!(section_name(shstrndx), Some(".shstrtab"));
+        assert_eq!(return_shstrndx_label(&elf_obj), format!("{shstrndx} (.shstrtab) via SHN_XINDEX"));
+    }
+}
@@ -0,0 +1,2178 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Section header listings, section contents and section layout reports */
+
+use super::*;
+
+impl Arguments
+{
+    pub(crate) fn parse_header_sections(&self, elf_obj: &Elf, data: &[u8])
+    {
+        /* The library analysis owns the section listing, the CLI only lays it out */
+        let analysis: ElfAnalysis = match analyze_elf(data)
+        {
+            Ok(analysis) => analysis,
+
+            Err(error) => {
+                eprintln!("Error - failed to analyze the ELF binary: {error}");
+                return;
+            }
+        };
+
+        /* Every name below would silently read "Not defined", say why up front */
+        if let Err(reason) = validate_shstrtab(elf_obj, data.len())
+        {
+            let warning: String = format!("WARNING: Section name string table is missing or corrupt \u{2014} section names unavailable ({reason})");
+
+            match self.is_structured_output()
+            {
+                true => eprintln!("{warning}"),
+                false => println!("\n{ANSI_RED}{warning}{ANSI_RESET}")
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            let records: Vec<SectionRecord> = analysis.sections.iter().map(|section| SectionRecord {
+                name: section.name.clone(),
+                flags: return_section_flags(section.flags).to_string(),
+                section_type: return_elf_section_type(section.raw_type).get_type(),
+                address: section.address,
+                offset: section.offset,
+                size: section.size,
+                entsize: section.entry_size,
+                compression: return_section_compression(elf_obj, section.index, data)
+            }).collect();
+
+            self.insert_document("sections", &records);
+            return;
+        }
+
+        println!("\nSection Headers =>");
+
+        let mut section_hdr_table: Table = self.new_table();
+
+        /* Only grow the table by a column when there is something compressed to report */
+        let compressions: Vec<Option<CompressionRecord>> = analysis.sections.iter()
+            .map(|section| return_section_compression(elf_obj, section.index, data))
+            .collect();
+
+        let has_compressed: bool = compressions.iter().any(Option::is_some);
+
+        let mut header: Vec<Cell> = vec![
+                /* Formatting options supplemented */
+                Cell::new("Symbol Name \u{00a7}").fg(Color::Green).add_attribute(Attribute::Bold), 
+                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Header Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new(format!("Offset {OMEGA}")).fg(Color::Green).add_attribute(Attribute::Bold),
+
+                /* No formatting options */
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold), 
+                Cell::new("Ent size").fg(Color::Green).add_attribute(Attribute::Bold), 
+                Cell::new("Has Table?").fg(Color::Green).add_attribute(Attribute::Bold)
+            ];
+
+        if has_compressed
+        {
+            header.push(Cell::new("Compression").fg(Color::Green).add_attribute(Attribute::Bold));
+        }
+
+        section_hdr_table.set_header(header);
+        
+        for (section, compression) in analysis.sections.iter().zip(&compressions)
+        {
+            let section_name: &str = section.name.as_deref().unwrap_or("Not defined");
+            let section_offset: String = self.number_format.format(section.offset);
+
+            /* ELF section header type */
+            let elf_sh_type: ElfSectionType = return_elf_section_type(section.raw_type);
+
+            let section_hdr_sz: String = (match (&self.number_format, (section.size >= 1024_u64)) {
+                (NumberFormat::Hex, _) => format!("{} bytes", self.number_format.format(section.size)),
+                (NumberFormat::Decimal, true) => format!("{} Kb ({:.2} bytes)", ((&section.size / 1024) as f64), section.size),
+                (NumberFormat::Decimal, false) => format!("{} bytes", &section.size)
+            }).to_string();
+
+            let section_ent_sz: String = format!("{} bytes", self.number_format.format(section.entry_size)); 
+
+            let _attributes: Vec<Attribute> = vec![
+                // Attribute::Bold,
+                // Attribute::Italic
+            ];
+
+            let mut row: Vec<Cell> = vec![
+                Cell::new(section_name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),  /* SECTION NAME */
+                Cell::new(return_section_flags(section.flags)).fg(Color::Yellow),
+
+                Cell::new(elf_sh_type.get_type()).fg(Color::DarkGreen).add_attribute(Attribute::Italic), 
+                Cell::new(&section_offset),                                                  /* OFFSET  */
+                Cell::new(&section_hdr_sz),                                                  /* HDR_SIZE */
+                
+                match section.entry_size > 0                               /* ENT_SIZE */
+                {
+                    true => Cell::new(&section_ent_sz),
+                    false => Cell::new("")
+                },                                  
+
+                match section.entry_size > 0                               /* Has Table? */
+                {
+                    true => Cell::new(CHECK.to_string()).fg(Color::Green).add_attribute(Attribute::Bold),
+                    false => Cell::new(CROSS.to_string()).fg(Color::Red).add_attribute(Attribute::Dim)
+                } 
+            ];
+
+            if has_compressed
+            {
+                /* e.g. "zlib 1834 -> 4021 bytes (2.19x)", compressed size is sh_size, decompressed is ch_size */
+                row.push(match compression
+                {
+                    Some(record) => Cell::new(format!("{} {} -> {} bytes ({:.2}x)",
+                        record.algorithm,
+                        self.number_format.format(record.compressed_size),
+                        self.number_format.format(record.decompressed_size),
+                        record.ratio
+                    )).fg(Color::Yellow),
+
+                    None => Cell::new("")
+                });
+            }
+
+            section_hdr_table.add_row(row);
+        }
+
+        println!("\n{section_hdr_table}");
+        println!("\n{} section headers detected.", get_true_shnum(elf_obj).saturating_sub(1));
+    }
+
+
+    pub(super) fn parse_size_breakdown(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let mut size: SizeRecord = SizeRecord { text: 0, rodata: 0, data: 0, bss: 0, debug: 0, other: 0, file_size: data.len() as u64 };
+
+        /* Bytes actually backed by section contents in the file (BSS occupies none) */
+        let mut file_backed: u64 = 0;
+
+        for elf_section_hdr in &elf_obj.section_headers
+        {
+            let section_name: &str = elf_obj.shdr_strtab.get_at(elf_section_hdr.sh_name).unwrap_or("");
+            let flags: u32 = elf_section_hdr.sh_flags as u32;
+
+            if elf_section_hdr.sh_type != SHT_NOBITS
+            {
+                file_backed += elf_section_hdr.sh_size;
+            }
+
+            let bucket: &mut u64 = match (section_name.starts_with(".debug_"), elf_section_hdr.sh_type, flags & SHF_ALLOC != 0)
+            {
+                (true, _, _) => &mut size.debug,
+                (false, SHT_NOBITS, true) => &mut size.bss,
+                (false, _, true) if flags & SHF_EXECINSTR != 0 => &mut size.text,
+                (false, _, true) if flags & SHF_WRITE != 0 => &mut size.data,
+                (false, _, true) => &mut size.rodata,
+
+                _ => &mut size.other
+            };
+
+            *bucket += elf_section_hdr.sh_size;
+        }
+
+        /* ELF header, program header table, section header table and alignment padding */
+        size.other += size.file_size.saturating_sub(file_backed);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("size", &size);
+
+            return;
+        }
+
+        println!("\nSize Breakdown =>");
+
+        let total: u64 = size.text + size.rodata + size.data + size.bss + size.debug + size.other;
+        let mut size_table: Table = self.new_table();
+
+        size_table.set_header(vec![
+                Cell::new("Category").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Percent").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (category, bytes) in [
+            ("text", size.text),
+            ("rodata", size.rodata),
+            ("data", size.data),
+            ("bss", size.bss),
+            ("debug", size.debug),
+            ("other", size.other)
+        ]
+        {
+            size_table.add_row(vec![
+                Cell::new(category).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{bytes} bytes")),
+                Cell::new(format!("{:.2}%", match total { 0 => 0.0, _ => bytes as f64 * 100.0 / total as f64 })).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{size_table}");
+        println!("\nFile size: {} bytes (bss is not stored in the file, other includes header/padding overhead)", size.file_size);
+    }
+
+
+    pub(crate) fn compare_sections(&self, old_path: &str, new_path: &str) -> bool
+    {
+        let old_data: Vec<u8> = fs::read(old_path).expect("Failed to read old binary file data!");
+        let new_data: Vec<u8> = fs::read(new_path).expect("Failed to read new binary file data!");
+
+        let old_elf: Elf = Elf::parse(&old_data).expect("Failed to parse old binary as ELF!");
+        let new_elf: Elf = Elf::parse(&new_data).expect("Failed to parse new binary as ELF!");
+
+        let old_sections: Vec<(String, u64, u64)> = return_named_sections(&old_elf);
+        let new_sections: Vec<(String, u64, u64)> = return_named_sections(&new_elf);
+
+        let find = |sections: &'_ [(String, u64, u64)], name: &str| -> Option<(u64, u64)> {
+            sections.iter().find(|(section_name, _, _)| section_name == name).map(|(_, size, flags)| (*size, *flags))
+        };
+
+        let mut diff: Vec<SectionDiffRecord> = Vec::new();
+
+        /* Deletions first, then modifications, then additions */
+        for (name, size, flags) in old_sections.iter().filter(|(name, _, _)| find(&new_sections, name).is_none())
+        {
+            diff.push(SectionDiffRecord {
+                name: name.clone(),
+                change: String::from("deleted"),
+                old_size: Some(*size),
+                new_size: None,
+                delta: -(*size as i64),
+                old_flags: Some(return_section_flag_letters(*flags)),
+                new_flags: None
+            });
+        }
+
+        for (name, old_size, old_flags) in &old_sections
+        {
+            if let Some((new_size, new_flags)) = find(&new_sections, name).filter(|(new_size, new_flags)| new_size != old_size || new_flags != old_flags)
+            {
+                diff.push(SectionDiffRecord {
+                    name: name.clone(),
+                    change: String::from("modified"),
+                    old_size: Some(*old_size),
+                    new_size: Some(new_size),
+                    delta: new_size as i64 - *old_size as i64,
+                    old_flags: Some(return_section_flag_letters(*old_flags)),
+                    new_flags: Some(return_section_flag_letters(new_flags))
+                });
+            }
+        }
+
+        for (name, size, flags) in new_sections.iter().filter(|(name, _, _)| find(&old_sections, name).is_none())
+        {
+            diff.push(SectionDiffRecord {
+                name: name.clone(),
+                change: String::from("added"),
+                old_size: None,
+                new_size: Some(*size),
+                delta: *size as i64,
+                old_flags: None,
+                new_flags: Some(return_section_flag_letters(*flags))
+            });
+        }
+
+        let comparison: SectionComparisonRecord = SectionComparisonRecord {
+            old_file_size: old_data.len() as u64,
+            new_file_size: new_data.len() as u64,
+            net_change: new_data.len() as i64 - old_data.len() as i64,
+            sections: diff
+        };
+
+        let has_changes: bool = !comparison.sections.is_empty();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("section_comparison", &comparison);
+            return has_changes;
+        }
+
+        println!("\nSection Comparison => {old_path} -> {new_path}");
+
+        let mut diff_table: Table = self.new_table();
+
+        diff_table.set_header(vec![
+                Cell::new("Change").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &comparison.sections
+        {
+            let (change, color): (&str, Color) = match record.change.as_str()
+            {
+                "deleted" => ("DELETED", Color::Red),
+                "modified" => ("MODIFIED", Color::Yellow),
+                _ => ("ADDED", Color::Green)
+            };
+
+            let size: String = match (record.old_size, record.new_size)
+            {
+                (Some(old_size), Some(new_size)) if old_size != new_size => format!("{old_size} \u{2192} {new_size} ({:+} bytes)", record.delta),
+                (Some(size), _) | (None, Some(size)) => format!("{size} bytes"),
+                (None, None) => String::new()
+            };
+
+            let flags: String = match (&record.old_flags, &record.new_flags)
+            {
+                (Some(old_flags), Some(new_flags)) if old_flags != new_flags => format!("{old_flags} \u{2192} {new_flags}"),
+                (Some(flags), _) | (None, Some(flags)) => flags.clone(),
+                (None, None) => String::new()
+            };
+
+            diff_table.add_row(vec![
+                Cell::new(change).fg(color).add_attribute(Attribute::Bold),
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(size).fg(color),
+                Cell::new(flags).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        match has_changes
+        {
+            true => println!("\n{diff_table}"),
+            false => println!("\n* No section header changes detected.")
+        }
+
+        let count = |change: &str| -> usize { comparison.sections.iter().filter(|record| record.change == change).count() };
+
+        println!("\n{} deleted, {} modified, {} added.", count("deleted"), count("modified"), count("added"));
+        println!("Net binary size change: {} \u{2192} {} bytes ({:+} bytes)", comparison.old_file_size, comparison.new_file_size, comparison.net_change);
+
+        has_changes
+    }
+
+
+    pub(super) fn parse_section_content(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let section_name: &str = match self.get_flag_values("--section-content").first()
+        {
+            Some(name) => name,
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --section-content <name>");
+                std::process::exit(-1);
+            }
+        };
+
+        let shdr = match elf_obj.section_headers.iter().find(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name) == Some(section_name))
+        {
+            Some(shdr) => shdr,
+
+            None => {
+                eprintln!("Error - section \"{section_name}\" not found in binary!");
+                std::process::exit(-1);
+            }
+        };
+
+        /* SHF_COMPRESSED sections are interpreted (and hex dumped) from their decompressed contents */
+        let section_contents: Cow<[u8]> = return_section_contents(elf_obj, shdr, data);
+        let section_bytes: &[u8] = &section_contents;
+        let section_type: String = return_elf_section_type(shdr.sh_type).get_type();
+
+        let interpretation: &str = match shdr.sh_type
+        {
+            SHT_STRTAB => "strings",
+            SHT_SYMTAB | SHT_DYNSYM => "symbols",
+            SHT_RELA | SHT_REL => "relocations",
+            SHT_DYNAMIC => "dynamic entries",
+            SHT_NOTE => "notes",
+            SHT_NOBITS => "none (SHT_NOBITS occupies no file space)",
+
+            /* No disassembler is wired in yet, executable code is shown as raw bytes */
+            SHT_PROGBITS if shdr.sh_flags as u32 & SHF_EXECINSTR != 0 => "hex dump (executable code)",
+            _ => "hex dump"
+        };
+
+        let padding: Option<u64> = match self.flags.iter().any(|flag| flag == "--show-padding") && shdr.sh_type != SHT_NOBITS
+        {
+            true => Some(return_section_padding(elf_obj, shdr)),
+            false => None
+        };
+
+        let strings: Vec<String> = section_bytes.split(|&b| b == 0)
+            .filter(|s| !s.is_empty())
+            .map(|s| String::from_utf8_lossy(s).to_string())
+            .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("section_content", &SectionContentRecord {
+                name: section_name.to_string(),
+                section_type,
+                interpretation: interpretation.to_string(),
+                offset: shdr.sh_offset,
+                size: shdr.sh_size,
+                strings: match shdr.sh_type { SHT_STRTAB => Some(strings), _ => None },
+                hex: Some(section_bytes.iter().map(|b| format!("{:02x}", b)).collect()),
+                padding
+            });
+
+            return;
+        }
+
+        println!("\nSection Content => {section_name}");
+        println!("\n* Type: {section_type} | interpreted as: {interpretation} | offset {:#x} | {} bytes", shdr.sh_offset, shdr.sh_size);
+
+        /* Offsets in the dump below are relative to the decompressed contents, not the file */
+        let is_decompressed: bool = matches!(section_contents, Cow::Owned(_));
+
+        if is_decompressed
+        {
+            println!("* SHF_COMPRESSED: showing {} decompressed bytes", section_bytes.len());
+        }
+
+        match shdr.sh_type
+        {
+            SHT_STRTAB => {
+                println!();
+
+                for (i, string) in strings.iter().enumerate()
+                {
+                    println!("\t {}. {string}", i + 1);
+                }
+            },
+
+            SHT_SYMTAB | SHT_DYNSYM => {
+                let ctx: Ctx = return_elf_ctx(elf_obj);
+                let count: usize = match shdr.sh_entsize { 0 => 0, entsize => (shdr.sh_size / entsize) as usize };
+
+                let symtab: Symtab = Symtab::parse(data, shdr.sh_offset as usize, count, ctx).unwrap_or_default();
+                let strtab: Strtab = return_linked_strtab(elf_obj, data, shdr.sh_link as usize);
+
+                let mut symbol_table: Table = self.new_table();
+
+                symbol_table.set_header(vec![
+                        Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Bind").fg(Color::Green).add_attribute(Attribute::Bold)
+                    ]);
+
+                for symbol in symtab.iter()
+                {
+                    symbol_table.add_row(vec![
+                        Cell::new(strtab.get_at(symbol.st_name).unwrap_or("Not defined")).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                        Cell::new(format!("{:#x}", symbol.st_value)),
+                        Cell::new(format!("{} bytes", symbol.st_size)),
+                        Cell::new(sym::type_to_str(symbol.st_type())).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                        Cell::new(sym::bind_to_str(symbol.st_bind())).fg(Color::Yellow)
+                    ]);
+                }
+
+                println!("\n{symbol_table}");
+            },
+
+            SHT_RELA | SHT_REL => {
+                let ctx: Ctx = return_elf_ctx(elf_obj);
+                let relocs: RelocSection = RelocSection::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx).unwrap_or_default();
+
+                /* sh_link of a relocation section names its symbol table */
+                let symtab_hdr = elf_obj.section_headers.get(shdr.sh_link as usize);
+
+                let symtab: Symtab = symtab_hdr.map_or(Symtab::default(), |symtab_hdr| {
+                    let count: usize = match symtab_hdr.sh_entsize { 0 => 0, entsize => (symtab_hdr.sh_size / entsize) as usize };
+
+                    Symtab::parse(data, symtab_hdr.sh_offset as usize, count, ctx).unwrap_or_default()
+                });
+
+                let strtab: Strtab = return_linked_strtab(elf_obj, data, symtab_hdr.map_or(0, |symtab_hdr| symtab_hdr.sh_link as usize));
+
+                let mut reloc_table: Table = self.new_table();
+
+                reloc_table.set_header(vec![
+                        Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Symbol").fg(Color::Green).add_attribute(Attribute::Bold),
+                        Cell::new("Addend").fg(Color::Green).add_attribute(Attribute::Bold)
+                    ]);
+
+                for relocation in relocs.iter()
+                {
+                    let symbol_name: &str = symtab.get(relocation.r_sym).and_then(|symbol| strtab.get_at(symbol.st_name)).unwrap_or("");
+
+                    let relocation_type: &str = reloc::r_to_str(relocation.r_type, elf_obj.header.e_machine);
+
+                    reloc_table.add_row(vec![
+                        Cell::new(format!("{:#x}", relocation.r_offset)),
+                        match is_irelative(relocation_type)
+                        {
+                            true => Cell::new(relocation_type).fg(Color::Magenta).add_attribute(Attribute::Bold),
+                            false => Cell::new(relocation_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                        },
+                        Cell::new(symbol_name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                        Cell::new(relocation.r_addend.map_or(String::new(), |addend| format!("{:#x}", addend)))
+                    ]);
+                }
+
+                println!("\n{reloc_table}");
+            },
+
+            SHT_DYNAMIC => self.parse_dynamic_section(elf_obj, data),
+
+            SHT_NOTE => {
+                if let Some(notes) = elf_obj.iter_note_sections(data, Some(section_name))
+                {
+                    for note in notes.flatten()
+                    {
+                        println!("\n\tOwner >> {} | type {:#x} | {} bytes", note.name, note.n_type, note.desc.len());
+                        println!("\tDesc  >> {}", note.desc.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join(" "));
+                    }
+                }
+            },
+
+            SHT_NOBITS => (),
+
+            _ => print_hex_dump(section_bytes, match is_decompressed { true => 0, false => shdr.sh_offset })
+        }
+
+        /* The bytes between the end of the section and the next region are alignment filler, not section data */
+        if let Some(padding) = padding.filter(|&padding| padding != 0)
+        {
+            let start: u64 = shdr.sh_offset.saturating_add(shdr.sh_size);
+            let padding_bytes: &[u8] = data.get(start as usize..start.saturating_add(padding) as usize).unwrap_or(&[]);
+
+            println!("\n<padding: {padding} bytes>");
+
+            match self.no_color
+            {
+                true => print_hex_dump(padding_bytes, start),
+
+                false => {
+                    print!("{ANSI_GREY}");
+                    print_hex_dump(padding_bytes, start);
+                    print!("{ANSI_RESET}");
+                }
+            }
+        }
+    }
+
+
+    pub(super) fn parse_exec_sections(&self, elf_obj: &Elf)
+    {
+        let sections: Vec<ExecSectionRecord> = elf_obj.section_headers.iter()
+            .filter(|shdr| shdr.sh_flags & SHF_EXECINSTR as u64 != 0)
+            .map(|shdr| ExecSectionRecord {
+                name: elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("").to_string(),
+                start: shdr.sh_addr,
+                end: shdr.sh_addr.saturating_add(shdr.sh_size),
+                size: shdr.sh_size,
+                offset: shdr.sh_offset,
+                writable: shdr.sh_flags & SHF_WRITE as u64 != 0
+            })
+            .collect();
+
+        let report: ExecSectionReportRecord = ExecSectionReportRecord {
+            total_size: sections.iter().fold(0, |total, section| total.saturating_add(section.size)),
+            sections
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("exec_sections", &report);
+            return;
+        }
+
+        println!("\nExecutable Sections =>");
+
+        if report.sections.is_empty()
+        {
+            println!("\n* No section has SHF_EXECINSTR set.");
+            return;
+        }
+
+        let mut exec_table: Table = self.new_table();
+
+        exec_table.set_header(vec![
+                Cell::new("Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address Range").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("W^X").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for section in &report.sections
+        {
+            exec_table.add_row(vec![
+                Cell::new(&section.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}-{:#x}", section.start, section.end)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", section.size)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", section.offset)).fg(Color::Yellow),
+                match section.writable
+                {
+                    true => Cell::new("Violated (WX)").fg(Color::Red).add_attribute(Attribute::Bold),
+                    false => Cell::new("OK").fg(Color::Green)
+                }
+            ]);
+        }
+
+        println!("\n{exec_table}");
+
+        println!("\nTotal executable size: {} bytes ({:#x}) in {} sections", report.total_size, report.total_size, report.sections.len());
+
+        /* Regular toolchain output has .init, .plt(.sec/.got) and .text, JIT stubs and packers scatter many more */
+        if report.sections.len() > 5
+        {
+            println!("Warning: {} executable sections (unusual: expected 1–3)", report.sections.len());
+        }
+    }
+
+
+    pub(super) fn parse_writable_sections(&self, elf_obj: &Elf)
+    {
+        let sections: Vec<WritableSectionRecord> = elf_obj.section_headers.iter()
+            .filter(|shdr| shdr.sh_flags & SHF_WRITE as u64 != 0)
+            .map(|shdr| WritableSectionRecord {
+                name: elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("").to_string(),
+                start: shdr.sh_addr,
+                end: shdr.sh_addr.saturating_add(shdr.sh_size),
+                size: shdr.sh_size,
+                executable: shdr.sh_flags & SHF_EXECINSTR as u64 != 0,
+                nobits: shdr.sh_type == SHT_NOBITS,
+                loaded: shdr.sh_flags & SHF_ALLOC as u64 != 0
+            })
+            .collect();
+
+        let loaded = || sections.iter().filter(|section| section.loaded);
+
+        let report: WritableSectionReportRecord = WritableSectionReportRecord {
+            total_size: loaded().fold(0, |total: u64, section| total.saturating_add(section.size)),
+            nobits_size: loaded().filter(|section| section.nobits).fold(0, |total: u64, section| total.saturating_add(section.size)),
+            sections
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("writable_sections", &report);
+            return;
+        }
+
+        println!("\nWritable Sections =>");
+
+        if report.sections.is_empty()
+        {
+            println!("\n* No section has SHF_WRITE set.");
+            return;
+        }
+
+        for (title, nobits) in [("Initialized", false), ("Zero-initialized (SHT_NOBITS)", true)]
+        {
+            let rows: Vec<&WritableSectionRecord> = report.sections.iter().filter(|section| section.nobits == nobits).collect();
+
+            if rows.is_empty()
+            {
+                continue;
+            }
+
+            let mut writable_table: Table = self.new_table();
+
+            writable_table.set_header(vec![
+                    Cell::new(title).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Address Range").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("W^X").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for section in rows
+            {
+                writable_table.add_row(vec![
+                    match section.loaded
+                    {
+                        true => Cell::new(&section.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                        false => Cell::new(format!("{} (not loaded into memory)", section.name)).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+                    },
+                    Cell::new(format!("{:#x}-{:#x}", section.start, section.end)).fg(Color::Yellow),
+                    Cell::new(format!("{:#x}", section.size)).fg(Color::Yellow),
+                    match section.executable
+                    {
+                        true => Cell::new("Violated (WX)").fg(Color::Red).add_attribute(Attribute::Bold),
+                        false => Cell::new("OK").fg(Color::Green)
+                    }
+                ]);
+            }
+
+            println!("\n{writable_table}");
+        }
+
+        println!("\nTotal writable memory: {} bytes ({:#x}), {} bytes of it zero-initialized", report.total_size, report.total_size, report.nobits_size);
+    }
+
+
+    pub(super) fn parse_absent_flags(&self, elf_obj: &Elf)
+    {
+        let records: Vec<AbsentFlagRecord> = return_absent_flags(elf_obj);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("absent_flags", &records);
+            return;
+        }
+
+        println!("\nAbsent Hardening Flags =>");
+
+        if records.is_empty()
+        {
+            println!("\n* No section or segment is missing a hardening attribute (W^X holds).");
+            return;
+        }
+
+        let mut absent_table: Table = self.new_table();
+
+        absent_table.set_header(vec![
+                Cell::new("Kind").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Concern").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            absent_table.add_row(vec![
+                Cell::new(&record.kind),
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match record.wx_violation
+                {
+                    true => Cell::new(&record.flags).fg(Color::Red).add_attribute(Attribute::Bold),
+                    false => Cell::new(&record.flags).fg(Color::Yellow)
+                },
+                Cell::new(&record.concern).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{absent_table}");
+
+        println!("\n{} issues, {} W^X violations (these fail --check-nx).", records.len(), records.iter().filter(|record| record.wx_violation).count());
+    }
+
+    pub(super) fn check_segment_overlaps(&self, elf_obj: &Elf)
+    {
+        let overlaps: Vec<OverlapReport> = check_segment_vaddr_overlaps(elf_obj);
+
+        if overlaps.is_empty()
+        {
+            println!("No overlapping PT_LOAD segments");
+            std::process::exit(0);
+        }
+
+        for overlap in &overlaps
+        {
+            println!("ERROR: PT_LOAD segments [{}] and [{}] overlap at {:#x}-{:#x} ({} bytes)",
+                overlap.seg1_idx,
+                overlap.seg2_idx,
+                overlap.overlap_start,
+                overlap.overlap_end,
+                overlap.overlap_end - overlap.overlap_start
+            );
+        }
+
+        println!("Warning: overlapping load segments map the same pages twice, a sign of a malformed or deliberately crafted ELF.");
+        std::process::exit(1);
+    }
+
+
+    /* Writes the untouched section bytes, to the --out file when given or straight to stdout for piping */
+    pub(super) fn dump_section_raw(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let section_name: &str = match self.get_flag_values("--dump-section-raw").first()
+        {
+            Some(name) => name,
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --dump-section-raw <name> [--out <file>]");
+                std::process::exit(-1);
+            }
+        };
+
+        if !elf_obj.section_headers.iter().any(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name) == Some(section_name))
+        {
+            eprintln!("Error - section \"{section_name}\" not found in binary!");
+            std::process::exit(-1);
+        }
+
+        /* SHT_NOBITS sections (.bss) occupy no file space and dump as zero bytes */
+        let section_bytes: &[u8] = return_section_data(elf_obj, data, section_name).unwrap_or(&[]);
+
+        match self.get_flag_values("--out").first()
+        {
+            Some(out_file) => {
+                if let Err(err) = fs::write(out_file, section_bytes)
+                {
+                    eprintln!("Error - unable to write \"{out_file}\": {err}");
+                    std::process::exit(-1);
+                }
+
+                println!("* Wrote {} bytes of {} to {}", section_bytes.len(), section_name, out_file);
+            }
+
+            None => {
+                let mut stdout = std::io::stdout().lock();
+
+                if stdout.write_all(section_bytes).and_then(|_| stdout.flush()).is_err()
+                {
+                    std::process::exit(-1);
+                }
+            }
+        }
+    }
+
+
+    /* Display only, every address is shifted by (new base - current base) and the file is left untouched */
+    pub(super) fn parse_rebased_addresses(&self, elf_obj: &Elf)
+    {
+        let new_base: u64 = match self.get_flag_values("--rebase-sections").first()
+            .and_then(|base| u64::from_str_radix(base.trim_start_matches("0x").trim_start_matches("0X"), 16).ok())
+        {
+            Some(base) => base,
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --rebase-sections <base-hex>");
+                std::process::exit(-1);
+            }
+        };
+
+        /* A PIE/shared object legitimately links at 0, so the lowest PT_LOAD is used even when it is zero */
+        let current_base: u64 = match return_load_base(&elf_obj.program_headers)
+        {
+            Some(base) => base,
+
+            None => {
+                eprintln!("Error - binary has no PT_LOAD segments, there is no load base to rebase from!");
+                std::process::exit(-1);
+            }
+        };
+
+        let rebase = |addr: u64| -> u64 { addr.wrapping_sub(current_base).wrapping_add(new_base) };
+
+        /* Non allocated sections (sh_addr == 0) are never mapped and keep their zero address */
+        let sections: Vec<RebasedAddressRecord> = elf_obj.section_headers.iter()
+            .filter(|shdr| shdr.sh_flags as u32 & SHF_ALLOC != 0)
+            .map(|shdr| RebasedAddressRecord {
+                name: elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string(),
+                original: shdr.sh_addr,
+                rebased: rebase(shdr.sh_addr)
+            })
+            .collect();
+
+        let segments: Vec<RebasedAddressRecord> = elf_obj.program_headers.iter()
+            .filter(|phdr| phdr.p_memsz != 0)
+            .flat_map(|phdr| {
+                let segment_type: &str = program_header::pt_to_str(phdr.p_type);
+
+                [
+                    RebasedAddressRecord { name: format!("{segment_type} (p_vaddr)"), original: phdr.p_vaddr, rebased: rebase(phdr.p_vaddr) },
+                    RebasedAddressRecord { name: format!("{segment_type} (p_paddr)"), original: phdr.p_paddr, rebased: rebase(phdr.p_paddr) }
+                ]
+            })
+            .collect();
+
+        /* Undefined and SHN_ABS symbols do not live at a load relative address */
+        let symbols: Vec<RebasedAddressRecord> = elf_obj.syms.iter().map(|symbol| (symbol, &elf_obj.strtab))
+            .chain(elf_obj.dynsyms.iter().map(|symbol| (symbol, &elf_obj.dynstrtab)))
+            .filter(|(symbol, _)| symbol.st_shndx != SHN_UNDEF as usize && symbol.st_shndx != SHN_ABS as usize && symbol.st_value != 0)
+            .filter_map(|(symbol, strtab)| {
+                let name: &str = strtab.get_at(symbol.st_name).filter(|name| !name.is_empty())?;
+
+                Some(RebasedAddressRecord { name: name.to_string(), original: symbol.st_value, rebased: rebase(symbol.st_value) })
+            })
+            .collect();
+
+        let rebase_info: RebaseRecord = RebaseRecord {
+            current_base,
+            new_base,
+            delta: new_base as i128 - current_base as i128,
+            sections,
+            segments,
+            symbols
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("rebase", &rebase_info);
+            return;
+        }
+
+        println!("\nRebased Addresses =>");
+        println!("\nCurrent base: {:#x} | New base: {:#x} | Delta: {}{:#x}",
+            rebase_info.current_base,
+            rebase_info.new_base,
+            match rebase_info.delta < 0 { true => "-", false => "+" },
+            rebase_info.delta.unsigned_abs()
+        );
+
+        for (title, records) in [
+            ("Section", &rebase_info.sections),
+            ("Segment", &rebase_info.segments),
+            ("Symbol", &rebase_info.symbols)
+        ]
+        {
+            let mut rebase_table: Table = self.new_table();
+
+            rebase_table.set_header(vec![
+                    Cell::new(title).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Original Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Rebased Address").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in records
+            {
+                rebase_table.add_row(vec![
+                    Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(format!("{:#x}", record.original)),
+                    Cell::new(format!("{:#x}", record.rebased)).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("\n{rebase_table}");
+        }
+    }
+
+
+    /* File regions sorted by offset, anything not covered by a header, table or section is reported as a gap */
+    pub(super) fn parse_section_gaps(&self, elf_obj: &Elf)
+    {
+        let header: &Header = &elf_obj.header;
+
+        let mut regions: Vec<(u64, u64, String)> = vec![
+            (0, header.e_ehsize as u64, String::from("ELF header"))
+        ];
+
+        if header.e_phnum != 0
+        {
+            regions.push((header.e_phoff, header.e_phoff.saturating_add(header.e_phnum as u64 * header.e_phentsize as u64), String::from("Program header table")));
+        }
+
+        if header.e_shoff != 0
+        {
+            regions.push((header.e_shoff, header.e_shoff.saturating_add(get_true_shnum(elf_obj) as u64 * header.e_shentsize as u64), String::from("Section header table")));
+        }
+
+        /* SHT_NOBITS sections (.bss) take no file space and SHT_NULL has no contents */
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_type != SHT_NULL && shdr.sh_size != 0)
+        {
+            regions.push((shdr.sh_offset, shdr.sh_offset.saturating_add(shdr.sh_size), elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string()));
+        }
+
+        regions.sort_by_key(|region| region.0);
+
+        let mut gaps: Vec<SectionGapRecord> = Vec::new();
+
+        /* Furthest end seen so far, so overlapping/nested regions never produce a bogus gap */
+        let mut covered_end: u64 = 0;
+        let mut preceding: &str = "";
+
+        for (start, end, name) in &regions
+        {
+            if *start > covered_end
+            {
+                gaps.push(SectionGapRecord {
+                    start: covered_end,
+                    end: *start,
+                    size: start - covered_end,
+                    preceding: preceding.to_string(),
+                    following: name.clone()
+                });
+            }
+
+            if *end >= covered_end
+            {
+                covered_end = *end;
+                preceding = name;
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("section_gaps", &gaps);
+            return;
+        }
+
+        println!("\nSection Gaps =>");
+
+        if gaps.is_empty()
+        {
+            println!("\n* No gaps found, every byte up to the last region is accounted for.");
+            return;
+        }
+
+        let mut gap_table: Table = self.new_table();
+
+        gap_table.set_header(vec![
+                Cell::new("Gap Start").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Gap End").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Preceding").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Following").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for gap in &gaps
+        {
+            /* Alignment padding is small, large holes may hide injected code or data */
+            let size_color: Color = match gap.size
+            {
+                0..=16 => Color::Reset,
+                17..=4096 => Color::Yellow,
+
+                _ => Color::Red
+            };
+
+            gap_table.add_row(vec![
+                Cell::new(format!("{:#x}", gap.start)),
+                Cell::new(format!("{:#x}", gap.end)),
+                Cell::new(format!("{} bytes", gap.size)).fg(size_color),
+                Cell::new(&gap.preceding).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&gap.following).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+            ]);
+        }
+
+        println!("\n{gap_table}");
+        println!("\n{} gaps, {} bytes unaccounted for.", gaps.len(), gaps.iter().map(|gap| gap.size).sum::<u64>());
+    }
+
+
+    pub(super) fn parse_section_type_extensions(&self, elf_obj: &Elf)
+    {
+        let custom_types: &HashMap<u32, String> = CUSTOM_SECTION_TYPES.get_or_init(load_custom_section_types);
+
+        let mut section_types: Vec<SectionTypeExtensionRecord> = custom_types.iter()
+            .map(|(sh_type, name)| SectionTypeExtensionRecord {
+                sh_type: *sh_type,
+                name: name.clone(),
+                sections: elf_obj.section_headers.iter()
+                    .filter(|shdr| shdr.sh_type == *sh_type)
+                    .map(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string())
+                    .collect()
+            })
+            .collect();
+
+        section_types.sort_by_key(|record| record.sh_type);
+
+        let extensions: SectionTypeExtensionsRecord = SectionTypeExtensionsRecord {
+            config: return_section_types_config_path().map(|path| path.display().to_string()),
+            section_types
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("section_type_extensions", &extensions);
+            return;
+        }
+
+        println!("\nSection Type Extensions =>");
+        println!("\nConfig: {}", extensions.config.as_deref().unwrap_or("none ($HOME is not set)"));
+
+        if extensions.section_types.is_empty()
+        {
+            println!("\n* No custom section types registered, add a [section_types] table with entries like 0x6ffffff0 = \"SHT_CUSTOM_METADATA\".");
+            return;
+        }
+
+        let mut extension_table: Table = self.new_table();
+
+        extension_table.set_header(vec![
+                Cell::new("sh_type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Sections").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &extensions.section_types
+        {
+            /* A built-in decoder always wins, the config entry is never consulted for it */
+            let sections: String = match return_elf_section_type(record.sh_type)
+            {
+                ElfSectionType::ShtCustom(_) => record.sections.join(", "),
+                builtin => format!("(shadowed by built-in {})", builtin.get_type())
+            };
+
+            extension_table.add_row(vec![
+                Cell::new(format!("{:#x}", record.sh_type)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.name).fg(Color::Yellow),
+                Cell::new(sections).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{extension_table}");
+    }
+
+
+    pub(super) fn parse_compressed_sections(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let records: Vec<CompressedSectionRecord> = elf_obj.section_headers.iter()
+            .filter(|shdr| shdr.sh_flags & goblin::elf::section_header::SHF_COMPRESSED as u64 != 0)
+            .map(|shdr| CompressedSectionRecord {
+                name: elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string(),
+                compression: return_compression_record(elf_obj, shdr, data)
+            })
+            .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("compressed_sections", &records);
+            return;
+        }
+
+        println!("\nCompressed Sections =>");
+
+        if records.is_empty()
+        {
+            println!("\n* No SHF_COMPRESSED sections present.");
+            return;
+        }
+
+        let mut compressed_table: Table = self.new_table();
+
+        compressed_table.set_header(vec![
+                Cell::new("Section Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Algorithm").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Compressed").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Decompressed").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Ratio").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Valid?").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            compressed_table.add_row(vec![
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.compression.algorithm).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(format!("{} bytes", record.compression.compressed_size)),
+                Cell::new(format!("{} bytes", record.compression.decompressed_size)),
+                Cell::new(format!("{:.2}x", record.compression.ratio)).fg(Color::Yellow),
+                match record.compression.decompresses
+                {
+                    true => Cell::new("Yes").fg(Color::Green),
+                    false => Cell::new("No").fg(Color::Red)
+                }
+            ]);
+        }
+
+        println!("\n{compressed_table}");
+    }
+
+    /* File layout map for external visualisers: headers, sections and the gaps between them, in file order */
+    pub(super) fn parse_timeline_json(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let header: &Header = &elf_obj.header;
+
+        /* (start, end exclusive, name, type, permissions) */
+        let mut regions: Vec<(u64, u64, String, &str, Option<String>)> = Vec::new();
+
+        /* Empty regions and sizes that overflow the file offset space are left out rather than drawn */
+        let mut push_region = |start: u64, size: u64, name: String, region_type: &'static str, permissions: Option<String>| {
+            if let Some(end) = start.checked_add(size).filter(|_| size != 0)
+            {
+                regions.push((start, end, name, region_type, permissions));
+            }
+        };
+
+        push_region(0, header.e_ehsize as u64, String::from("<ELF header>"), "elf_header", None);
+
+        if header.e_phnum != 0
+        {
+            push_region(header.e_phoff, header.e_phnum as u64 * header.e_phentsize as u64, String::from("<program headers>"), "program_header_table", None);
+        }
+
+        if header.e_shoff != 0
+        {
+            push_region(header.e_shoff, get_true_shnum(elf_obj) as u64 * header.e_shentsize as u64, String::from("<section headers>"), "section_header_table", None);
+        }
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_type != SHT_NULL && shdr.sh_size != 0)
+        {
+            let permissions: String = format!("{}{}{}",
+                match shdr.sh_flags & SHF_ALLOC as u64 != 0 { true => 'r', false => '-' },
+                match shdr.sh_flags & SHF_WRITE as u64 != 0 { true => 'w', false => '-' },
+                match shdr.sh_flags & SHF_EXECINSTR as u64 != 0 { true => 'x', false => '-' }
+            );
+
+            push_region(shdr.sh_offset, shdr.sh_size, elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string(), "section", Some(permissions));
+        }
+
+        regions.sort_by_key(|region| region.0);
+
+        let mut timeline: Vec<TimelineRecord> = Vec::new();
+        let mut covered_end: u64 = 0;
+
+        let gap = |start: u64, end: u64| -> TimelineRecord {
+            TimelineRecord { name: String::from("<gap>"), start: format!("{:#x}", start), end: format!("{:#x}", end - 1), r#type: String::from("gap"), permissions: None }
+        };
+
+        for (start, end, name, region_type, permissions) in regions
+        {
+            if start > covered_end
+            {
+                timeline.push(gap(covered_end, start));
+            }
+
+            covered_end = covered_end.max(end);
+
+            timeline.push(TimelineRecord {
+                name,
+                start: format!("{:#x}", start),
+                end: format!("{:#x}", end - 1),
+                r#type: region_type.to_string(),
+                permissions
+            });
+        }
+
+        /* Trailing bytes past the last region (appended data, signatures...) */
+        if (data.len() as u64) > covered_end
+        {
+            timeline.push(gap(covered_end, data.len() as u64));
+        }
+
+        match self.is_structured_output()
+        {
+            true => self.insert_document("timeline", &timeline),
+            false => println!("{}", serde_json::to_string_pretty(&timeline).expect("Failed to serialize JSON timeline!"))
+        }
+    }
+
+    pub(super) fn list_section_types(&self, elf_obj: &Elf)
+    {
+        let mut section_types: Vec<SectionTypeRecord> = Vec::new();
+
+        for shdr in &elf_obj.section_headers
+        {
+            let section_type: String = return_elf_section_type(shdr.sh_type).get_type();
+            let section_name: String = elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string();
+
+            match section_types.iter_mut().find(|record| record.section_type == section_type)
+            {
+                Some(record) => {
+                    record.count += 1;
+                    record.sections.push(section_name);
+                },
+
+                None => section_types.push(SectionTypeRecord { section_type, count: 1, sections: vec![section_name] })
+            }
+        }
+
+        /* Stable sort, types with equal counts keep their section header order */
+        section_types.sort_by_key(|record| std::cmp::Reverse(record.count));
+
+        if self.is_structured_output()
+        {
+            self.insert_document("section_types", &section_types);
+            return;
+        }
+
+        println!("\nSection Types =>\n");
+
+        for record in &section_types
+        {
+            println!("{} ({} {}): {}",
+                record.section_type,
+                record.count,
+                match record.count { 1 => "section", _ => "sections" },
+                record.sections.iter().map(|name| match name.is_empty() { true => "<unnamed>", false => name.as_str() }).collect::<Vec<&str>>().join(", ")
+            );
+        }
+    }
+
+    pub(super) fn parse_segment_section_mapping(&self, elf_obj: &Elf)
+    {
+        let segments: Vec<SegmentSectionsRecord> = elf_obj.program_headers.iter()
+            .filter(|phdr| phdr.p_type != program_header::PT_NULL)
+            .map(|phdr| {
+                let sections: Vec<String> = elf_obj.section_headers.iter()
+                    .filter(|shdr| match shdr.sh_type
+                    {
+                        SHT_NULL => false,
+
+                        /* .bss has no file bytes, it belongs to the segment whose memory image covers it */
+                        SHT_NOBITS => shdr.sh_flags & SHF_ALLOC as u64 != 0
+                            && shdr.sh_addr >= phdr.p_vaddr && shdr.sh_addr.saturating_add(shdr.sh_size) <= phdr.p_vaddr.saturating_add(phdr.p_memsz),
+
+                        _ => shdr.sh_offset >= phdr.p_offset && shdr.sh_offset.saturating_add(shdr.sh_size) <= phdr.p_offset.saturating_add(phdr.p_filesz)
+                    })
+                    .map(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name).unwrap_or("Not defined").to_string())
+                    .collect();
+
+                SegmentSectionsRecord {
+                    segment_type: program_header::pt_to_str(phdr.p_type).to_string(),
+                    flags: format!("{}{}{}",
+                        match phdr.p_flags & program_header::PF_R != 0 { true => 'R', false => '-' },
+                        match phdr.p_flags & program_header::PF_W != 0 { true => 'W', false => '-' },
+                        match phdr.p_flags & program_header::PF_X != 0 { true => 'X', false => '-' }
+                    ),
+                    offset: phdr.p_offset,
+                    virtual_address: phdr.p_vaddr,
+                    file_size: phdr.p_filesz,
+                    memory_size: phdr.p_memsz,
+                    sections
+                }
+            })
+            .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("segment_sections", &segments);
+            return;
+        }
+
+        println!("\nSegment to Section Mapping =>");
+
+        let mut segment_table: Table = self.new_table();
+
+        segment_table.set_header(vec![
+                Cell::new("Segment").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Virtual Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("FileSz / MemSz").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Sections").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for segment in &segments
+        {
+            segment_table.add_row(vec![
+                Cell::new(&segment.segment_type).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&segment.flags).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", segment.offset)),
+                Cell::new(format!("{:#x}", segment.virtual_address)),
+                Cell::new(format!("{:#x} / {:#x}", segment.file_size, segment.memory_size)),
+                Cell::new(segment.sections.iter().map(|name| format!("\u{2514}\u{2500} {name}")).collect::<Vec<String>>().join("\n"))
+                    .fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{segment_table}");
+    }
+
+    pub(super) fn parse_cross_section_refs(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let ctx: Ctx = return_elf_ctx(elf_obj);
+        let is_relocatable: bool = elf_obj.header.e_type == ET_REL;
+
+        let section_name = |index: usize| -> String {
+            elf_obj.section_headers.get(index)
+                .and_then(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name))
+                .unwrap_or("")
+                .to_string()
+        };
+
+        /* Linked images: the allocated section whose address range covers the pointer */
+        let section_at = |address: u64| -> Option<usize> {
+            elf_obj.section_headers.iter().position(|shdr| {
+                shdr.sh_flags & SHF_ALLOC as u64 != 0 && shdr.sh_size != 0 && address >= shdr.sh_addr && address - shdr.sh_addr < shdr.sh_size
+            })
+        };
+
+        let mut records: Vec<CrossSectionRefRecord> = Vec::new();
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA)
+        {
+            let relocs: RelocSection = RelocSection::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx).unwrap_or_default();
+
+            /* sh_link of a relocation section names its symbol table */
+            let symtab: Symtab = elf_obj.section_headers.get(shdr.sh_link as usize).map_or(Symtab::default(), |symtab_hdr| {
+                let count: usize = match symtab_hdr.sh_entsize { 0 => 0, entsize => (symtab_hdr.sh_size / entsize) as usize };
+
+                Symtab::parse(data, symtab_hdr.sh_offset as usize, count, ctx).unwrap_or_default()
+            });
+
+            for relocation in relocs.iter()
+            {
+                /* Object files patch sh_info at a section relative offset, linked images patch a virtual address */
+                let source: Option<usize> = match is_relocatable
+                {
+                    true => Some(shdr.sh_info as usize),
+                    false => section_at(relocation.r_offset)
+                };
+
+                let symbol: Option<Sym> = match relocation.r_sym
+                {
+                    0 => None,
+                    index => symtab.get(index)
+                };
+
+                /* Imports resolve outside the image, they are not cross section references */
+                if symbol.as_ref().is_some_and(|symbol| symbol.st_shndx == SHN_UNDEF as usize)
+                {
+                    continue;
+                }
+
+                let address: u64 = symbol.as_ref().map_or(0, |symbol| symbol.st_value).wrapping_add(relocation.r_addend.unwrap_or(0) as u64);
+
+                let target: Option<usize> = match (is_relocatable, &symbol)
+                {
+                    (true, Some(symbol)) if symbol.st_shndx < SHN_LORESERVE as usize => Some(symbol.st_shndx),
+                    (true, _) => continue,
+
+                    /* REL entries keep the addend in place, and a null pointer is no reference at all */
+                    (false, _) if address == 0 => continue,
+                    (false, _) => section_at(address)
+                };
+
+                if source.is_some() && source == target
+                {
+                    continue;
+                }
+
+                let source_name: String = source.map_or(String::from("<outside sections>"), section_name);
+                let target_name: String = target.map_or(String::from("<outside sections>"), section_name);
+
+                match records.iter_mut().find(|record| record.source == source_name && record.target == target_name)
+                {
+                    Some(record) => record.count += 1,
+
+                    None => records.push(CrossSectionRefRecord {
+                        suspicious: return_cross_section_suspicion(&source_name, target.map(|_| target_name.as_str())),
+                        source: source_name,
+                        target: target_name,
+                        count: 1
+                    })
+                }
+            }
+        }
+
+        records.sort_by(|a, b| b.suspicious.is_some().cmp(&a.suspicious.is_some()).then(b.count.cmp(&a.count)));
+
+        if self.is_structured_output()
+        {
+            self.insert_document("cross_section_refs", &records);
+            return;
+        }
+
+        println!("\nCross Section References =>");
+
+        if records.is_empty()
+        {
+            println!("\n* No relocated pointers cross a section boundary.");
+            return;
+        }
+
+        let mut refs_table: Table = self.new_table();
+
+        refs_table.set_header(vec![
+                Cell::new("Source Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Target Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Status").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            refs_table.add_row(vec![
+                Cell::new(&record.source).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.target).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(record.count).fg(Color::Yellow),
+
+                match &record.suspicious
+                {
+                    Some(reason) => Cell::new(reason).fg(Color::Red).add_attribute(Attribute::Bold),
+                    None => Cell::new("normal").fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                }
+            ]);
+        }
+
+        println!("\n{refs_table}");
+
+        let suspicious: usize = records.iter().filter(|record| record.suspicious.is_some()).map(|record| record.count).sum();
+
+        println!("\n{} cross section references, {} flagged as unusual.", records.iter().map(|record| record.count).sum::<usize>(), suspicious);
+    }
+
+    pub(super) fn parse_count_per_type(&self, elf_obj: &Elf)
+    {
+        let mut sections: Vec<TypeCountRecord> = Vec::new();
+        let mut symbols: Vec<TypeCountRecord> = Vec::new();
+
+        for shdr in &elf_obj.section_headers
+        {
+            let section_type: String = return_elf_section_type(shdr.sh_type).get_type();
+
+            match sections.iter_mut().find(|record| record.type_name == section_type)
+            {
+                Some(record) => record.count += 1,
+                None => sections.push(TypeCountRecord { type_name: section_type, count: 1 })
+            }
+        }
+
+        /* Stripped binaries only keep .dynsym around */
+        let (symbol_table, symtab): (&str, &Symtab) = match elf_obj.syms.is_empty()
+        {
+            true => (".dynsym", &elf_obj.dynsyms),
+            false => (".symtab", &elf_obj.syms)
+        };
+
+        /* Index 0 is the reserved null symbol, it is not a symbol of any type */
+        for symbol in symtab.iter().skip(1)
+        {
+            let symbol_type: String = sym::type_to_str(symbol.st_type()).to_string();
+
+            match symbols.iter_mut().find(|record| record.type_name == symbol_type)
+            {
+                Some(record) => record.count += 1,
+                None => symbols.push(TypeCountRecord { type_name: symbol_type, count: 1 })
+            }
+        }
+
+        sections.sort_by_key(|record| std::cmp::Reverse(record.count));
+        symbols.sort_by_key(|record| std::cmp::Reverse(record.count));
+
+        if self.is_structured_output()
+        {
+            self.insert_document("count_per_type", &CountPerTypeRecord { sections, symbol_table: symbol_table.to_string(), symbols });
+            return;
+        }
+
+        for (title, column, records) in [("Sections Per Type", "Section Type", &sections), ("Symbols Per Type", "Symbol Type", &symbols)]
+        {
+            println!("\n{} =>", title);
+
+            let mut t: Table = self.new_table();
+
+            t.set_header(vec![
+                    Cell::new(column).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in records.iter()
+            {
+                t.add_row(vec![
+                    Cell::new(&record.type_name).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                    Cell::new(record.count).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("{t}");
+        }
+
+        println!("\n{} sections: {}", elf_obj.section_headers.len(),
+            sections.iter().map(|record| format!("{} {}", record.count, record.type_name)).collect::<Vec<String>>().join(", "));
+
+        match symbols.is_empty()
+        {
+            true => println!("No symbols in .symtab or .dynsym"),
+
+            false => println!("{} symbols ({}): {}", symbols.iter().map(|record| record.count).sum::<usize>(), symbol_table,
+                symbols.iter().map(|record| format!("{} {}", record.count, record.type_name)).collect::<Vec<String>>().join(", "))
+        }
+    }
+}
+
+
+/* Custom section header flags */
+const SHF_WRITE_ALLOC: u32 = SHF_WRITE | SHF_ALLOC;
+const SHF_ASM_INST_ALLOC: u32 = SHF_ALLOC | SHF_EXECINSTR;
+
+const ANSI_GREY: &str = "\x1b[90m";
+
+/* zstd ch_type, goblin 0.7 only exports ELFCOMPRESS_ZLIB */
+const ELFCOMPRESS_ZSTD: u32 = 2;
+
+/* ch_size is file data: never inflate past it or this ceiling, and only preallocate a small multiple of the payload */
+const MAX_DECOMPRESSED_SECTION: u64 = 256 << 20;
+const DECOMPRESS_PREALLOC_FACTOR: usize = 8;
+
+/* Section name prefixes emitted by toolchains/linkers, anything else is treated as non-standard */
+const STANDARD_SECTION_PREFIXES: [&str; 24] = [
+    ".text", ".data", ".rodata", ".bss", ".got", ".plt", ".init", ".fini", ".preinit_array",
+    ".tdata", ".tbss", ".dynamic", ".eh_frame", ".gcc_except_table", ".ctors", ".dtors", ".jcr",
+    ".debug", ".note", ".rel", ".gnu", ".modinfo", ".altinstr", "__"
+];
+
+const SHF_UNDEFINED: u32 = 0; const SHF_UNDEFINED_STR: &str = "SHF_UNDEFINED";
+
+/* Serializable analysis records (used by structured output formats) */
+#[derive(Serialize)]
+struct SectionRecord
+{
+    name: Option<String>,
+    flags: String,
+    section_type: String,
+    address: u64,
+    offset: u64,
+    size: u64,
+    entsize: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compression: Option<CompressionRecord>
+}
+
+#[derive(Serialize)]
+struct CompressedSectionRecord
+{
+    name: String,
+    #[serde(flatten)]
+    compression: CompressionRecord
+}
+
+/* Elf32_Chdr/Elf64_Chdr of an SHF_COMPRESSED section, shared by --compressed-sections and the sections table */
+#[derive(Serialize)]
+struct CompressionRecord
+{
+    algorithm: String,
+    compressed_size: u64,
+    decompressed_size: u64,
+    ratio: f64,
+    decompresses: bool
+}
+
+#[derive(Serialize)]
+struct SectionTypeExtensionRecord
+{
+    sh_type: u32,
+    name: String,
+    /* Sections of the target with this sh_type */
+    sections: Vec<String>
+}
+
+#[derive(Serialize)]
+struct SectionTypeExtensionsRecord
+{
+    config: Option<String>,
+    section_types: Vec<SectionTypeExtensionRecord>
+}
+
+/* Two PT_LOAD segments (program header indexes) sharing [overlap_start, overlap_end) */
+struct OverlapReport
+{
+    seg1_idx: usize,
+    seg2_idx: usize,
+    overlap_start: u64,
+    overlap_end: u64
+}
+
+#[derive(Serialize)]
+struct TimelineRecord
+{
+    name: String,
+    start: String,
+    /* Inclusive, the last byte of the region */
+    end: String,
+    r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<String>
+}
+
+#[derive(Serialize)]
+struct SectionTypeRecord
+{
+    section_type: String,
+    count: usize,
+    sections: Vec<String>
+}
+
+#[derive(Serialize)]
+struct CountPerTypeRecord
+{
+    sections: Vec<TypeCountRecord>,
+    symbol_table: String,
+    symbols: Vec<TypeCountRecord>
+}
+
+#[derive(Serialize)]
+struct SegmentSectionsRecord
+{
+    segment_type: String,
+    flags: String,
+    offset: u64,
+    virtual_address: u64,
+    file_size: u64,
+    memory_size: u64,
+    sections: Vec<String>
+}
+
+#[derive(Serialize)]
+struct SectionGapRecord
+{
+    start: u64,
+    end: u64,
+    size: u64,
+    preceding: String,
+    following: String
+}
+
+#[derive(Serialize)]
+struct RebasedAddressRecord
+{
+    name: String,
+    original: u64,
+    rebased: u64
+}
+
+#[derive(Serialize)]
+struct RebaseRecord
+{
+    current_base: u64,
+    new_base: u64,
+    delta: i128,
+    sections: Vec<RebasedAddressRecord>,
+    segments: Vec<RebasedAddressRecord>,
+    symbols: Vec<RebasedAddressRecord>
+}
+
+#[derive(Serialize)]
+struct SizeRecord
+{
+    text: u64,
+    rodata: u64,
+    data: u64,
+    bss: u64,
+    debug: u64,
+    other: u64,
+    file_size: u64
+}
+
+#[derive(Serialize)]
+struct SectionDiffRecord
+{
+    name: String,
+    change: String,
+    old_size: Option<u64>,
+    new_size: Option<u64>,
+    delta: i64,
+    old_flags: Option<String>,
+    new_flags: Option<String>
+}
+
+#[derive(Serialize)]
+struct SectionComparisonRecord
+{
+    sections: Vec<SectionDiffRecord>,
+    old_file_size: u64,
+    new_file_size: u64,
+    net_change: i64
+}
+
+#[derive(Serialize)]
+struct ExecSectionRecord
+{
+    name: String,
+    start: u64,
+    end: u64,
+    size: u64,
+    offset: u64,
+    /* SHF_WRITE as well, a W^X violation */
+    writable: bool
+}
+
+#[derive(Serialize)]
+struct ExecSectionReportRecord
+{
+    sections: Vec<ExecSectionRecord>,
+    total_size: u64
+}
+
+#[derive(Serialize)]
+struct WritableSectionRecord
+{
+    name: String,
+    start: u64,
+    end: u64,
+    size: u64,
+    /* SHF_EXECINSTR as well, a W^X violation */
+    executable: bool,
+    /* SHT_NOBITS, zero-filled at load time */
+    nobits: bool,
+    /* SHF_ALLOC, sections without it are never mapped */
+    loaded: bool
+}
+
+#[derive(Serialize)]
+struct WritableSectionReportRecord
+{
+    sections: Vec<WritableSectionRecord>,
+    /* Writable + allocated bytes, what the image costs in RAM */
+    total_size: u64,
+    nobits_size: u64
+}
+
+#[derive(Serialize)]
+struct SectionContentRecord
+{
+    name: String,
+    section_type: String,
+    interpretation: String,
+    offset: u64,
+    size: u64,
+    strings: Option<Vec<String>>,
+    hex: Option<String>,
+    /* Alignment padding up to the next file region, only reported with --show-padding */
+    #[serde(skip_serializing_if = "Option::is_none")]
+    padding: Option<u64>
+}
+
+#[derive(Serialize)]
+struct CrossSectionRefRecord
+{
+    source: String,
+    target: String,
+    count: usize,
+    /* Why the reference looks unusual, None for ordinary code/data cross references */
+    suspicious: Option<String>
+}
+
+
+fn return_section_flags(sh_flags: u64) -> &'static str
+{
+    match sh_flags as u32
+    {
+        SHF_UNDEFINED => SHF_UNDEFINED_STR,
+
+        /* handle write/allocation primary flags */
+        SHF_WRITE => "SHF_WRITE",
+        SHF_ALLOC => "SHF_ALLOC",
+        SHF_WRITE_ALLOC => "SHF_WRITE & SHF_ALLOC",
+
+        /* assembly instructions (intel/at&t)? */
+        SHF_ASM_INST_ALLOC => "SHF_ASM_OPCODE",
+
+        SHF_EXECINSTR => "SHF_EXECINSTR",
+        SHF_MERGE => "SHF_MERGE",
+        SHF_STRINGS => "SHF_STRINGS",
+        SHF_INFO_LINK => "SHF_INFO_LINK",
+        SHF_LINK_ORDER => "SHF_LINK_ORDER",
+        SHF_OS_NONCONFORMING => "SHF_OS_NON_CONFORMING",
+        SHF_GROUP => "SHF_GROUP",
+        SHF_TLS => "SHF_TLS",
+        SHF_MASKOS => "SHF_MASKOS",
+        SHF_MASKPROC => "SHF_MASKPROC",
+        
+        /* lazy method to handle bogus data */
+        _ => SHF_UNDEFINED_STR
+    }
+}
+
+
+/* (name, sh_size, sh_flags) of every named section, the null section at index 0 has no name to match on */
+fn return_named_sections(elf: &Elf) -> Vec<(String, u64, u64)>
+{
+    elf.section_headers.iter()
+        .filter_map(|shdr| match elf.shdr_strtab.get_at(shdr.sh_name)
+        {
+            Some(name) if !name.is_empty() => Some((name.to_string(), shdr.sh_size, shdr.sh_flags)),
+            _ => None
+        })
+        .collect()
+}
+
+
+/*
+    PT_LOAD segments sorted by p_vaddr, each compared with the following ones until one starts past its end,
+    so a segment nested inside a large one is caught even when it is not the direct neighbour
+*/
+fn check_segment_vaddr_overlaps(elf: &Elf) -> Vec<OverlapReport>
+{
+    let mut segments: Vec<(usize, u64, u64)> = elf.program_headers.iter().enumerate()
+        .filter(|(_, phdr)| phdr.p_type == PT_LOAD && phdr.p_memsz != 0)
+        .map(|(index, phdr)| (index, phdr.p_vaddr, phdr.p_vaddr.saturating_add(phdr.p_memsz)))
+        .collect();
+
+    segments.sort_by_key(|(_, start, _)| *start);
+
+    let mut overlaps: Vec<OverlapReport> = Vec::new();
+
+    for (position, (seg1_idx, _, end)) in segments.iter().enumerate()
+    {
+        for (seg2_idx, start2, end2) in segments[position + 1..].iter().take_while(|(_, start2, _)| start2 < end)
+        {
+            overlaps.push(OverlapReport { seg1_idx: *seg1_idx, seg2_idx: *seg2_idx, overlap_start: *start2, overlap_end: (*end).min(*end2) });
+        }
+    }
+
+    overlaps
+}
+
+/* None for the usual toolchain layout, otherwise the reason a pointer between the two sections stands out */
+fn return_cross_section_suspicion(source: &str, target: Option<&str>) -> Option<String>
+{
+    let is_standard = |name: &str| -> bool {
+        STANDARD_SECTION_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+    };
+
+    match target
+    {
+        None => Some(String::from("target outside every section")),
+        Some("") => Some(String::from("target section has no name")),
+        Some(target) if !is_standard(target) => Some(String::from("non-standard target section")),
+        Some(_) if source.is_empty() => Some(String::from("source section has no name")),
+        Some(_) if !is_standard(source) && !source.starts_with('<') => Some(String::from("non-standard source section")),
+
+        _ => None
+    }
+}
+
+
+/* Elf32_Chdr/Elf64_Chdr -> (ch_type, ch_size, header length) */
+fn return_compression_header(elf: &Elf, raw: &[u8]) -> Option<(u32, u64, usize)>
+{
+    let word = |offset: usize| -> Option<u32> {
+        let bytes: [u8; 4] = raw.get(offset..offset + 4)?.try_into().ok()?;
+
+        Some(match elf.little_endian { true => u32::from_le_bytes(bytes), false => u32::from_be_bytes(bytes) })
+    };
+
+    let xword = |offset: usize| -> Option<u64> {
+        let bytes: [u8; 8] = raw.get(offset..offset + 8)?.try_into().ok()?;
+
+        Some(match elf.little_endian { true => u64::from_le_bytes(bytes), false => u64::from_be_bytes(bytes) })
+    };
+
+    match elf.is_64
+    {
+        /* ch_type, ch_reserved, ch_size, ch_addralign */
+        true => Some((word(0)?, xword(8)?, 24)),
+
+        /* ch_type, ch_size, ch_addralign */
+        false => Some((word(0)?, word(4)? as u64, 12))
+    }
+}
+
+
+fn return_section_compression(elf: &Elf, index: usize, data: &[u8]) -> Option<CompressionRecord>
+{
+    elf.section_headers.get(index)
+        .filter(|shdr| shdr.sh_flags & goblin::elf::section_header::SHF_COMPRESSED as u64 != 0)
+        .map(|shdr| return_compression_record(elf, shdr, data))
+}
+
+
+fn return_compression_record(elf: &Elf, shdr: &goblin::elf::SectionHeader, data: &[u8]) -> CompressionRecord
+{
+    let raw: &[u8] = return_section_range(shdr, data).unwrap_or(&[]);
+    let (ch_type, ch_size, _): (u32, u64, usize) = return_compression_header(elf, raw).unwrap_or((0, 0, 0));
+
+    CompressionRecord {
+        algorithm: match ch_type
+        {
+            ELFCOMPRESS_ZLIB => String::from("zlib"),
+            ELFCOMPRESS_ZSTD => String::from("zstd"),
+
+            _ => format!("Unknown ({ch_type})")
+        },
+        compressed_size: shdr.sh_size,
+        decompressed_size: ch_size,
+        ratio: match shdr.sh_size { 0 => 0.0, _ => ch_size as f64 / shdr.sh_size as f64 },
+        decompresses: return_decompressed_section(elf, raw).is_some_and(|bytes| bytes.len() as u64 == ch_size)
+    }
+}
+
+
+fn return_decompressed_section(elf: &Elf, raw: &[u8]) -> Option<Vec<u8>>
+{
+    let (ch_type, ch_size, header_len): (u32, u64, usize) = return_compression_header(elf, raw)?;
+    let payload: &[u8] = raw.get(header_len..)?;
+
+    let limit: u64 = ch_size.min(MAX_DECOMPRESSED_SECTION);
+    let mut decompressed: Vec<u8> = Vec::with_capacity((limit as usize).min(payload.len().saturating_mul(DECOMPRESS_PREALLOC_FACTOR)));
+
+    match ch_type
+    {
+        ELFCOMPRESS_ZLIB => { flate2::read::ZlibDecoder::new(payload).take(limit).read_to_end(&mut decompressed).ok()?; },
+        ELFCOMPRESS_ZSTD => { zstd::stream::read::Decoder::new(payload).ok()?.take(limit).read_to_end(&mut decompressed).ok()?; },
+
+        _ => return None
+    }
+
+    Some(decompressed)
+}
+
+
+/* Bytes between the end of a section and the next file region (section, or the section header table) */
+fn return_section_padding(elf: &Elf, shdr: &goblin::elf::SectionHeader) -> u64
+{
+    let end: u64 = shdr.sh_offset.saturating_add(shdr.sh_size);
+
+    elf.section_headers.iter()
+        .filter(|other| other.sh_type != SHT_NOBITS && other.sh_type != SHT_NULL && other.sh_size != 0)
+        .map(|other| other.sh_offset)
+        .chain(std::iter::once(elf.header.e_shoff).filter(|&shoff| shoff != 0))
+        .filter(|&offset| offset >= end)
+        .min()
+        .map_or(0, |next| next - end)
+}
+
+/* Section contents as the program sees them, SHF_COMPRESSED data is inflated (falling back to the raw bytes) */
+fn return_section_contents<'a>(elf: &Elf, shdr: &goblin::elf::SectionHeader, data: &'a [u8]) -> Cow<'a, [u8]>
+{
+    if shdr.sh_type == SHT_NOBITS
+    {
+        return Cow::Borrowed(&[]);
+    }
+
+    let raw: &[u8] = return_section_range(shdr, data).unwrap_or(&[]);
+
+    match shdr.sh_flags & goblin::elf::section_header::SHF_COMPRESSED as u64 != 0
+    {
+        true => return_decompressed_section(elf, raw).map_or(Cow::Borrowed(raw), Cow::Owned),
+        false => Cow::Borrowed(raw)
+    }
+}
+
+
+/* String table referenced by a section's sh_link field */
+fn return_linked_strtab<'a>(elf: &Elf, data: &'a [u8], link: usize) -> Strtab<'a>
+{
+    match elf.section_headers.get(link)
+    {
+        Some(shdr) => Strtab::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, 0x0).unwrap_or_default(),
+        None => Strtab::default()
+    }
+}
+
+
+/* Classic 16 bytes per row hex dump: offset, hex pairs, printable ASCII */
+fn print_hex_dump(bytes: &[u8], base_offset: u64)
+{
+    println!();
+
+    for (i, row) in bytes.chunks(16).enumerate()
+    {
+        let hex: String = row.iter().map(|b| format!("{:02x} ", b)).collect();
+        let ascii: String = row.iter().map(|&b| match b { 0x20..=0x7e => b as char, _ => '.' }).collect();
+
+        println!("{:#010x}  {:<48} |{}|", base_offset + (i * 16) as u64, hex, ascii);
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /* Comments inside strings, literal strings, dotted keys and inline tables all reach [section_types] */
+    #[test]
+    fn section_types_config()
+    {
+        let table: &str = "title = \"a # b\"\n\
+            [section_types] # header comment\n\
+            0x6ffffff0 = \"SHT_#HASH\" # trailing comment\n\
+            0x6fff_fff1 = 'C:\\literal'\n\
+            1879048178 = \"DECIMAL\"\n\
+            \"0x6ffffff3\" = \"QUOTED\"\n\
+            not_a_type = \"SKIPPED\"\n\
+            0x6ffffff4 = \"\"\n";
+
+        let (section_types, skipped): (HashMap<u32, String>, Vec<String>) = return_custom_section_types(table).expect("valid TOML");
+
+        assert_eq!(section_types.get(&0x6ffffff0).map(|name| name.as_str()), Some("SHT_#HASH"));
+        assert_eq!(section_types.get(&0x6ffffff1).map(|name| name.as_str()), Some("C:\\literal"));
+        assert_eq!(section_types.get(&0x6ffffff2).map(|name| name.as_str()), Some("DECIMAL"));
+        assert_eq!(section_types.get(&0x6ffffff3).map(|name| name.as_str()), Some("QUOTED"));
+        assert_eq!(section_types.len(), 4);
+        assert_eq!(skipped, vec![String::from("0x6ffffff4"), String::from("not_a_type")]);
+
+        let dotted: &str = "section_types.0x6ffffff0 = \"DOTTED\"\n";
+        let inline: &str = "section_types = { 0x6ffffff0 = \"INLINE\" }\n";
+
+        assert_eq!(return_custom_section_types(dotted).expect("valid TOML").0.get(&0x6ffffff0).map(|name| name.as_str()), Some("DOTTED"));
+        assert_eq!(return_custom_section_types(inline).expect("valid TOML").0.get(&0x6ffffff0).map(|name| name.as_str()), Some("INLINE"));
+
+        /* No table at all is an empty config, a value that is not a string rejects the file */
+        assert!(return_custom_section_types("").expect("valid TOML").0.is_empty());
+        assert!(return_custom_section_types("[section_types]\n0x6ffffff0 = 5\n").is_err());
+    }
+
+    /* Only sh_type 0 is SHT_NULL, anything undecoded keeps its raw value */
+    #[test]
+    fn section_type_labels()
+    {
+        assert_eq!(return_elf_section_type(SHT_NULL).get_type(), "SHT_NULL");
+        assert_eq!(return_elf_section_type(SHT_PROGBITS).get_type(), "SHT_PROGBITS");
+        assert_eq!(return_elf_section_type(0x12345).get_type(), "SHT_UNKNOWN(0x12345)");
+    }
+
+    /* Bare ELF64 little endian header followed by (p_type, p_vaddr, p_memsz) program headers, nothing else */
+    fn return_phdr_fixture(phdrs: &[(u32, u64, u64)]) -> Vec<u8>
+    {
+        const PHDR_SIZE: usize = 56;
+
+        let mut data: Vec<u8> = vec![0; 64 + phdrs.len() * PHDR_SIZE];
+
+        data[..7].copy_from_slice(&[0x7f, b'E', b'L', b'F', ELFCLASS64, ELFDATA2LSB, EV_CURRENT]);
+        data[16..18].copy_from_slice(&ET_EXEC.to_le_bytes());
+        data[18..20].copy_from_slice(&EM_X86_64.to_le_bytes());
+        data[20..24].copy_from_slice(&(EV_CURRENT as u32).to_le_bytes());
+        data[32..40].copy_from_slice(&64u64.to_le_bytes());
+        data[52..54].copy_from_slice(&64u16.to_le_bytes());
+        data[54..56].copy_from_slice(&(PHDR_SIZE as u16).to_le_bytes());
+        data[56..58].copy_from_slice(&(phdrs.len() as u16).to_le_bytes());
+
+        for (index, (p_type, p_vaddr, p_memsz)) in phdrs.iter().enumerate()
+        {
+            let phdr: usize = 64 + index * PHDR_SIZE;
+
+            data[phdr..phdr + 4].copy_from_slice(&p_type.to_le_bytes());
+            data[phdr + 16..phdr + 24].copy_from_slice(&p_vaddr.to_le_bytes());
+            data[phdr + 40..phdr + 48].copy_from_slice(&p_memsz.to_le_bytes());
+        }
+
+        data
+    }
+
+    /* Elf64_Chdr is 24 bytes with a reserved word, Elf32_Chdr 12 bytes, both in the byte order of the file */
+    #[test]
+    fn compression_header()
+    {
+        let elf64_data: Vec<u8> = return_phdr_fixture(&[]);
+        let elf64: Elf = Elf::parse(&elf64_data).expect("hand built ELF must parse");
+
+        let mut chdr64: Vec<u8> = Vec::new();
+
+        chdr64.extend_from_slice(&ELFCOMPRESS_ZLIB.to_le_bytes());
+        chdr64.extend_from_slice(&0u32.to_le_bytes());
+        chdr64.extend_from_slice(&0x1234u64.to_le_bytes());
+        chdr64.extend_from_slice(&8u64.to_le_bytes());
+
+        assert_eq!(return_compression_header(&elf64, &chdr64), Some((ELFCOMPRESS_ZLIB, 0x1234, 24)));
+        assert_eq!(return_compression_header(&elf64, &chdr64[..15]), None);
+
+        /* ELF32 big endian header, e_ehsize is the only field past e_version that has to be set */
+        let mut elf32_data: Vec<u8> = vec![0; 52];
+
+        elf32_data[..7].copy_from_slice(&[0x7f, b'E', b'L', b'F', ELFCLASS32, ELFDATA2MSB, EV_CURRENT]);
+        elf32_data[16..18].copy_from_slice(&ET_REL.to_be_bytes());
+        elf32_data[18..20].copy_from_slice(&EM_PPC.to_be_bytes());
+        elf32_data[20..24].copy_from_slice(&(EV_CURRENT as u32).to_be_bytes());
+        elf32_data[40..42].copy_from_slice(&52u16.to_be_bytes());
+
+        let elf32: Elf = Elf::parse(&elf32_data).expect("hand built ELF must parse");
+
+        let mut chdr32: Vec<u8> = Vec::new();
+
+        chdr32.extend_from_slice(&ELFCOMPRESS_ZSTD.to_be_bytes());
+        chdr32.extend_from_slice(&0x40u32.to_be_bytes());
+        chdr32.extend_from_slice(&4u32.to_be_bytes());
+
+        assert_eq!(return_compression_header(&elf32, &chdr32), Some((ELFCOMPRESS_ZSTD, 0x40, 12)));
+        assert_eq!(return_compression_header(&elf32, &chdr32[..7]), None);
+    }
+
+    /* A nested and a partially overlapping PT_LOAD are reported, touching, empty and non-PT_LOAD segments are not */
+    #[test]
+    fn segment_vaddr_overlaps()
+    {
+        let data: Vec<u8> = return_phdr_fixture(&[
+            (PT_LOAD, 0x1000, 0x4000),
+            (PT_LOAD, 0x2000, 0x1000),
+            (PT_LOAD, 0x4800, 0x1800),
+            (PT_LOAD, 0x6000, 0x1000),
+            (program_header::PT_NOTE, 0x1000, 0x100),
+            (PT_LOAD, 0x1500, 0)
+        ]);
+
+        let elf_obj: Elf = Elf::parse(&data).expect("hand built ELF must parse");
+
+        let overlaps: Vec<(usize, usize, u64, u64)> = check_segment_vaddr_overlaps(&elf_obj).iter()
+            .map(|report| (report.seg1_idx, report.seg2_idx, report.overlap_start, report.overlap_end))
+            .collect();
+
+        assert_eq!(overlaps, vec![(0, 1, 0x2000, 0x3000), (0, 2, 0x4800, 0x5000)]);
+        assert!(check_segment_vaddr_overlaps(&Elf::parse(&return_phdr_fixture(&[(PT_LOAD, 0x1000, 0x1000)])).expect("hand built ELF must parse")).is_empty());
+    }
+}
@@ -0,0 +1,594 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Hardening report and the --check-* exit code checks */
+
+use super::*;
+
+impl Arguments
+{
+    pub(super) fn parse_security(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let has_relro: bool = has_gnu_relro(elf_obj);
+        let binding: ElfBindingMode = return_elf_binding_mode(elf_obj);
+
+        let relro: &str = match (has_relro, &binding)
+        {
+            (true, ElfBindingMode::FullRelro) => "Full",
+            (true, _) => "Partial",
+            (false, _) => "None"
+        };
+
+        let cet_features: u32 = return_x86_feature_1(elf_obj, data).unwrap_or(0);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("security", &SecurityRecord {
+                relro: relro.to_string(),
+                binding: binding.get_type(),
+                fortify: return_fortify_status(elf_obj).0.get_type(),
+                textrel: has_textrel(elf_obj),
+                safe_stack: has_safe_stack(elf_obj),
+                shadow_call_stack: has_shadow_call_stack(elf_obj, data),
+                cet_shadow_stack: cet_features & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0,
+                cet_ibt: cet_features & GNU_PROPERTY_X86_FEATURE_1_IBT != 0
+            });
+            return;
+        }
+
+        println!("\nSecurity Checks =>");
+
+        let mut security_table: Table = self.new_table();
+
+        security_table.set_header(vec![
+                Cell::new("Check").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Status").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        security_table.add_row(vec![
+            Cell::new("RELRO").fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+            match relro
+            {
+                "Full" => Cell::new(relro).fg(Color::Green),
+                "Partial" => Cell::new(relro).fg(Color::Yellow),
+                _ => Cell::new(relro).fg(Color::Red)
+            }
+        ]);
+
+        security_table.add_row(vec![
+            Cell::new("Binding").fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+            match binding
+            {
+                ElfBindingMode::Lazy => Cell::new(binding.get_type()).fg(Color::Red),
+                ElfBindingMode::Eager => Cell::new(binding.get_type()).fg(Color::Yellow),
+                ElfBindingMode::FullRelro => Cell::new(binding.get_type()).fg(Color::Green),
+                ElfBindingMode::NotApplicable => Cell::new(binding.get_type()).fg(Color::DarkGrey)
+            }
+        ]);
+
+        let (fortify, _, _): (FortifyLevel, Vec<String>, Vec<String>) = return_fortify_status(elf_obj);
+
+        security_table.add_row(vec![
+            Cell::new("Fortify").fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+            match fortify
+            {
+                FortifyLevel::Strong => Cell::new(fortify.get_type()).fg(Color::Green),
+                FortifyLevel::Partial => Cell::new(fortify.get_type()).fg(Color::Yellow),
+                FortifyLevel::Disabled => Cell::new(fortify.get_type()).fg(Color::Red),
+                FortifyLevel::NotApplicable => Cell::new(fortify.get_type()).fg(Color::DarkGrey)
+            }
+        ]);
+
+        security_table.add_row(vec![
+            Cell::new("TEXTREL").fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+            match has_textrel(elf_obj)
+            {
+                true => Cell::new("Present (text segment made writable)").fg(Color::Red),
+                false => Cell::new("None").fg(Color::Green)
+            }
+        ]);
+
+        for (check, enabled) in [("SafeStack", has_safe_stack(elf_obj)), ("ShadowCallStack", has_shadow_call_stack(elf_obj, data))]
+        {
+            security_table.add_row(vec![
+                Cell::new(check).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match enabled
+                {
+                    true => Cell::new("Enabled").fg(Color::Green),
+                    false => Cell::new("Disabled (Clang only)").fg(Color::DarkGrey)
+                }
+            ]);
+        }
+
+        let is_x86: bool = matches!(elf_obj.header.e_machine, EM_X86_64 | EM_386);
+
+        for (check, bit) in [("CET Shadow Stack", GNU_PROPERTY_X86_FEATURE_1_SHSTK), ("CET IBT", GNU_PROPERTY_X86_FEATURE_1_IBT)]
+        {
+            security_table.add_row(vec![
+                Cell::new(check).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match (cet_features & bit != 0, is_x86)
+                {
+                    (true, _) => Cell::new("Enabled").fg(Color::Green),
+                    (false, true) => Cell::new("Disabled").fg(Color::Red),
+                    (false, false) => Cell::new("Not applicable (x86 only)").fg(Color::DarkGrey)
+                }
+            ]);
+        }
+
+        println!("\n{security_table}");
+    }
+
+
+    /* ET_DYN alone is ambiguous, a SONAME (or no entry point) marks a shared library rather than a PIE executable */
+    pub(super) fn check_pie(&self, elf_obj: &Elf)
+    {
+        let (status, exit_code): (&str, i32) = match (elf_obj.header.e_type, elf_obj.soname.is_some(), elf_obj.entry != 0)
+        {
+            (ET_DYN, false, true) => ("PIE executable", 0),
+            (ET_DYN, true, _) => ("Shared library (ET_DYN with SONAME)", 2),
+            (ET_DYN, false, false) => ("Shared library (ET_DYN without entry point)", 2),
+            (ET_EXEC, _, _) => ("Non-PIE executable (ET_EXEC)", 1),
+
+            _ => ("Not an executable (neither ET_EXEC nor ET_DYN)", 1)
+        };
+
+        println!("{status}");
+
+        std::process::exit(exit_code);
+    }
+
+    /* PT_GNU_STACK decides whether the kernel maps the main thread stack executable */
+    pub(super) fn check_nx(&self, elf_obj: &Elf)
+    {
+        let gnu_stack: Option<&ProgramHeader> = elf_obj.program_headers.iter().find(|phdr| phdr.p_type == program_header::PT_GNU_STACK);
+
+        /* A non-executable stack does not help much when a writable mapping is executable anyway */
+        let wx_violations: Vec<AbsentFlagRecord> = return_absent_flags(elf_obj).into_iter().filter(|record| record.wx_violation).collect();
+
+        let (status, exit_code): (&str, i32) = match gnu_stack
+        {
+            Some(phdr) if phdr.p_flags & program_header::PF_X == 0 && !wx_violations.is_empty() => ("NX enabled (PT_GNU_STACK is not executable) but W^X is violated", 1),
+            Some(phdr) if phdr.p_flags & program_header::PF_X == 0 => ("NX enabled (PT_GNU_STACK is not executable)", 0),
+            Some(_) => ("NX disabled (PT_GNU_STACK is executable)", 1),
+
+            /* 32-bit x86 processes without PT_GNU_STACK run with READ_IMPLIES_EXEC, every readable mapping becomes executable */
+            None if !elf_obj.is_64 => ("PT_GNU_STACK missing (32-bit: the kernel treats the stack and readable mappings as executable)", 2),
+            None => ("PT_GNU_STACK missing (stack permissions depend on the kernel/architecture defaults)", 2)
+        };
+
+        println!("{status}");
+
+        for record in &wx_violations
+        {
+            println!("W^X violation: {} {} ({})", record.kind, record.name, record.flags);
+        }
+
+        std::process::exit(exit_code);
+    }
+
+
+    /* Partial RELRO leaves .got.plt writable for lazy binding, only BIND_NOW lets the loader seal it too */
+    pub(super) fn check_relro(&self, elf_obj: &Elf)
+    {
+        let (status, exit_code): (&str, i32) = match (has_gnu_relro(elf_obj), return_elf_binding_mode(elf_obj))
+        {
+            (true, ElfBindingMode::FullRelro) => ("Full RELRO (PT_GNU_RELRO + BIND_NOW, GOT read-only after startup)", 0),
+            (true, _) => ("Partial RELRO (PT_GNU_RELRO without BIND_NOW, GOT.PLT stays writable)", 1),
+            (false, _) => ("No RELRO (PT_GNU_RELRO missing)", 2)
+        };
+
+        println!("{status}");
+
+        if exit_code == 1
+        {
+            println!("{}", return_bind_now_status(elf_obj).0);
+        }
+
+        std::process::exit(exit_code);
+    }
+
+    /* Full RELRO needs this too, --check-relro reports the same line when it stops at Partial */
+    pub(super) fn check_bindnow(&self, elf_obj: &Elf)
+    {
+        let (status, exit_code): (&str, i32) = return_bind_now_status(elf_obj);
+
+        println!("{status}");
+
+        std::process::exit(exit_code);
+    }
+
+    pub(super) fn check_textrel(&self, elf_obj: &Elf)
+    {
+        match has_textrel(elf_obj)
+        {
+            true => {
+                println!("Text relocations present (DT_TEXTREL/DF_TEXTREL)");
+                println!("Warning: the dynamic linker makes the text segment writable to relocate it. Those pages are no longer shared between processes (more memory per process), code is writable while relocating, and SELinux policies denying execmod/text relocation refuse to load it.");
+
+                std::process::exit(1);
+            },
+
+            false => {
+                println!("No text relocations");
+                std::process::exit(0);
+            }
+        }
+    }
+
+    pub(super) fn check_safe_stack(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let safe_stack: bool = has_safe_stack(elf_obj);
+        let shadow_call_stack: bool = has_shadow_call_stack(elf_obj, data);
+
+        println!("SafeStack: {}", match safe_stack { true => "enabled (__safestack_unsafe_stack_ptr)", false => "disabled" });
+        println!("ShadowCallStack: {}", match (shadow_call_stack, elf_obj.header.e_machine)
+        {
+            (true, _) => "enabled (return addresses pushed to the x18 shadow stack)",
+            (false, EM_AARCH64) => "disabled",
+            (false, _) => "disabled (AArch64 only)"
+        });
+        println!("Note: both are Clang (-fsanitize=safe-stack / -fsanitize=shadow-call-stack) features, GCC builds never have them.");
+
+        std::process::exit(match safe_stack || shadow_call_stack { true => 0, false => 1 });
+    }
+
+    /* -fcf-protection: the linker only keeps a feature bit when every input object was built with it */
+    pub(super) fn check_cet_shadow_stack(&self, elf_obj: &Elf, data: &[u8])
+    {
+        if !matches!(elf_obj.header.e_machine, EM_X86_64 | EM_386)
+        {
+            println!("Shadow Stack: DISABLED (Intel CET is x86 only, e_machine = {})", return_elf_emachine(elf_obj.header.e_machine));
+            std::process::exit(1);
+        }
+
+        let features: Option<u32> = return_x86_feature_1(elf_obj, data);
+        let shadow_stack: bool = features.is_some_and(|features| features & GNU_PROPERTY_X86_FEATURE_1_SHSTK != 0);
+        let ibt: bool = features.is_some_and(|features| features & GNU_PROPERTY_X86_FEATURE_1_IBT != 0);
+        let (landing_pads, functions): (usize, usize) = return_endbr_functions(elf_obj, data);
+
+        println!("Shadow Stack: {}", match shadow_stack { true => "ENABLED (CET)", false => "DISABLED" });
+        println!("IBT: {}", match ibt { true => "ENABLED (CET)", false => "DISABLED" });
+        println!("{} of {} functions start with {}", landing_pads, functions, match elf_obj.is_64 { true => "endbr64", false => "endbr32" });
+
+        if features.is_none()
+        {
+            println!("Note: no GNU_PROPERTY_X86_FEATURE_1_AND property in .note.gnu.property (built without -fcf-protection).");
+        }
+
+        std::process::exit(match shadow_stack { true => 0, false => 1 });
+    }
+
+    /* -fstack-protector code calls __stack_chk_fail on a smashed canary, some targets also import the __stack_chk_guard value */
+    pub(super) fn check_canary(&self, elf_obj: &Elf)
+    {
+        let dynamic_strings: Vec<&str> = elf_obj.dynstrtab.to_vec().unwrap_or_default();
+
+        let has_chk_fail: bool = dynamic_strings.contains(&"__stack_chk_fail");
+        let has_chk_guard: bool = dynamic_strings.contains(&"__stack_chk_guard");
+
+        let (status, exit_code): (&str, i32) = match (has_chk_fail, has_chk_guard)
+        {
+            (true, true) => ("Stack canary: ENABLED (__stack_chk_fail + __stack_chk_guard)", 0),
+            (true, false) => ("Stack canary: ENABLED (__stack_chk_fail, guard value read from TLS)", 2),
+            (false, true) => ("Stack canary: DISABLED (__stack_chk_guard without __stack_chk_fail)", 2),
+            (false, false) => ("Stack canary: DISABLED", 1)
+        };
+
+        println!("{status}");
+
+        if !has_chk_fail
+        {
+            println!("Note: only dynamic symbols are checked, a statically linked binary carries __stack_chk_fail in its own code and reports DISABLED here.");
+        }
+
+        std::process::exit(exit_code);
+    }
+
+    pub(super) fn parse_fortify_level(&self, elf_obj: &Elf)
+    {
+        let (level, fortified, unfortified): (FortifyLevel, Vec<String>, Vec<String>) = return_fortify_status(elf_obj);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("fortify", &FortifyRecord { level: level.get_type(), fortified, unfortified });
+            return;
+        }
+
+        println!("\nFORTIFY_SOURCE =>");
+
+        let mut fortify_table: Table = self.new_table();
+
+        fortify_table.set_header(vec![
+                Cell::new("Imported Function").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Fortified").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for name in &fortified
+        {
+            fortify_table.add_row(vec![
+                Cell::new(name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(CHECK).fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+        }
+
+        for name in &unfortified
+        {
+            fortify_table.add_row(vec![
+                Cell::new(name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(CROSS).fg(Color::Red).add_attribute(Attribute::Bold)
+            ]);
+        }
+
+        println!("\n{fortify_table}");
+        println!("\n{} ({} fortified, {} unfortified)", level.get_type(), fortified.len(), unfortified.len());
+    }
+}
+
+
+/* libc functions glibc provides a __<name>_chk replacement for under _FORTIFY_SOURCE */
+const FORTIFIABLE_FUNCTIONS: [&str; 40] = [
+    "memcpy", "memmove", "mempcpy", "memset", "strcpy", "stpcpy", "strncpy", "stpncpy",
+    "strcat", "strncat", "sprintf", "snprintf", "vsprintf", "vsnprintf", "printf", "fprintf",
+    "vprintf", "vfprintf", "dprintf", "vdprintf", "gets", "fgets", "fgets_unlocked", "read",
+    "pread", "pread64", "recv", "recvfrom", "readlink", "readlinkat", "realpath", "getcwd",
+    "getwd", "confstr", "fread", "fread_unlocked", "wcscpy", "wcsncpy", "mbstowcs", "wcstombs"
+];
+
+/* AArch64 `str x30, [x18], #8`, the ShadowCallStack prologue pushing the return address */
+const SCS_PUSH_LR: u32 = 0xf800865e;
+
+/* GNU_PROPERTY_X86_FEATURE_1_AND and its IBT/SHSTK bits (<elf.h>, not exported by goblin 0.7) */
+const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 0x1;
+const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 0x2;
+const ENDBR64: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfa];
+const ENDBR32: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfb];
+
+enum ElfBindingMode
+{
+    Lazy,               /* no BIND_NOW, symbols resolved on first PLT call */
+    Eager,              /* BIND_NOW set but no PT_GNU_RELRO segment */
+    FullRelro,          /* BIND_NOW set and PT_GNU_RELRO present */
+    NotApplicable       /* no dynamic section (statically linked) */
+}
+
+impl ElfBindingMode
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            ElfBindingMode::Lazy => String::from("Lazy (BIND_NOW absent \u{2014} GOT writable at runtime)"),
+            ElfBindingMode::Eager => String::from("Eager (BIND_NOW set)"),
+            ElfBindingMode::FullRelro => String::from("Full RELRO (GOT read-only after init)"),
+            ElfBindingMode::NotApplicable => String::from("N/A (statically linked)")
+        }
+    }
+}
+
+enum FortifyLevel
+{
+    Strong,             /* every fortifiable import goes through its __*_chk variant */
+    Partial,            /* some imports are fortified, some plain variants remain */
+    Disabled,           /* fortifiable imports present, none replaced */
+    NotApplicable       /* no fortifiable libc function is imported */
+}
+
+impl FortifyLevel
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            FortifyLevel::Strong => String::from("FORTIFY_SOURCE=2 (strong)"),
+            FortifyLevel::Partial => String::from("FORTIFY_SOURCE=1 (partial)"),
+            FortifyLevel::Disabled => String::from("FORTIFY_SOURCE=0 (disabled)"),
+            FortifyLevel::NotApplicable => String::from("N/A (no fortifiable calls)")
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SecurityRecord
+{
+    relro: String,
+    binding: String,
+    fortify: String,
+    textrel: bool,
+    /* Clang only hardening, GCC has no equivalent */
+    safe_stack: bool,
+    shadow_call_stack: bool,
+    /* Intel CET, from .note.gnu.property */
+    cet_shadow_stack: bool,
+    cet_ibt: bool
+}
+
+#[derive(Serialize)]
+struct FortifyRecord
+{
+    level: String,
+    fortified: Vec<String>,
+    unfortified: Vec<String>
+}
+
+
+/* Does the binary carry a PT_GNU_RELRO segment (read-only after relocation)? */
+fn has_gnu_relro(elf: &Elf) -> bool
+{
+    elf.program_headers.iter().any(|phdr| phdr.p_type == PT_GNU_RELRO)
+}
+
+
+/* Non-PIC code in a shared object: DT_TEXTREL, or DF_TEXTREL in DT_FLAGS */
+fn has_textrel(elf: &Elf) -> bool
+{
+    match &elf.dynamic
+    {
+        Some(dynamic) => dynamic.dyns.iter().any(|d| match d.d_tag
+        {
+            DT_TEXTREL => true,
+            DT_FLAGS => d.d_val & DF_TEXTREL != 0,
+
+            _ => false
+        }),
+
+        None => false
+    }
+}
+
+
+/* -fsanitize=safe-stack keeps the unsafe stack pointer in a TLS variable the compiler references from every instrumented function */
+fn has_safe_stack(elf: &Elf) -> bool
+{
+    elf.syms.iter().map(|symbol| elf.strtab.get_at(symbol.st_name))
+        .chain(elf.dynsyms.iter().map(|symbol| elf.dynstrtab.get_at(symbol.st_name)))
+        .any(|name| name == Some("__safestack_unsafe_stack_ptr"))
+}
+
+
+/*
+    -fsanitize=shadow-call-stack leaves no dedicated symbol or section flag, the instrumented prologue is the marker:
+    `str x30, [x18], #8` pushes the return address onto the x18 shadow stack. An explicit __scs_guard symbol also counts.
+*/
+fn has_shadow_call_stack(elf: &Elf, data: &[u8]) -> bool
+{
+    if elf.syms.iter().any(|symbol| elf.strtab.get_at(symbol.st_name) == Some("__scs_guard"))
+    {
+        return true;
+    }
+
+    if elf.header.e_machine != EM_AARCH64
+    {
+        return false;
+    }
+
+    elf.section_headers.iter()
+        .filter(|shdr| shdr.sh_flags & SHF_EXECINSTR as u64 != 0 && shdr.sh_type != SHT_NOBITS)
+        .filter_map(|shdr| return_section_range(shdr, data))
+        .any(|code| code.chunks_exact(4).any(|insn| read_le_u32(insn, 0) == Some(SCS_PUSH_LR)))
+}
+
+
+/* GNU_PROPERTY_X86_FEATURE_1_AND bitmask of the NT_GNU_PROPERTY_TYPE_0 note, None when the property is absent */
+fn return_x86_feature_1(elf: &Elf, data: &[u8]) -> Option<u32>
+{
+    /* Properties are padded to 8 bytes on ELF64 and 4 bytes on ELF32 */
+    let align: usize = match elf.is_64 { true => 8, false => 4 };
+
+    let read_word = |bytes: &[u8], offset: usize| -> Option<u32> {
+        let word: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+
+        Some(match elf.little_endian
+        {
+            true => u32::from_le_bytes(word),
+            false => u32::from_be_bytes(word)
+        })
+    };
+
+    for note in elf.iter_note_sections(data, Some(".note.gnu.property"))?.flatten().filter(|note| note.n_type == NT_GNU_PROPERTY_TYPE_0 && note.name == "GNU")
+    {
+        let mut offset: usize = 0;
+
+        while let (Some(pr_type), Some(pr_datasz)) = (read_word(note.desc, offset), read_word(note.desc, offset + 4))
+        {
+            if pr_type == GNU_PROPERTY_X86_FEATURE_1_AND
+            {
+                return read_word(note.desc, offset + 8);
+            }
+
+            offset += (8 + pr_datasz as usize).div_ceil(align) * align;
+        }
+    }
+
+    None
+}
+
+
+/* (functions starting with endbr64/endbr32, defined STT_FUNC symbols), duplicates between .symtab and .dynsym counted once */
+fn return_endbr_functions(elf: &Elf, data: &[u8]) -> (usize, usize)
+{
+    let endbr: [u8; 4] = match elf.is_64 { true => ENDBR64, false => ENDBR32 };
+
+    let mut addresses: Vec<u64> = elf.syms.iter().chain(elf.dynsyms.iter())
+        .filter(|symbol| symbol.st_type() == STT_FUNC && symbol.st_shndx != SHN_UNDEF as usize && symbol.st_value != 0)
+        .map(|symbol| symbol.st_value)
+        .collect();
+
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    let landing_pads: usize = addresses.iter()
+        .filter_map(|address| {
+            let shdr = elf.section_headers.iter()
+                .find(|shdr| shdr.sh_type != SHT_NOBITS && shdr.sh_flags & SHF_EXECINSTR as u64 != 0 && shdr.sh_addr <= *address && *address - shdr.sh_addr < shdr.sh_size)?;
+            let offset: usize = (*address - shdr.sh_addr) as usize;
+
+            return_section_range(shdr, data)?.get(offset..offset + 4)
+        })
+        .filter(|prologue| *prologue == endbr)
+        .count();
+
+    (landing_pads, addresses.len())
+}
+
+
+fn return_bind_now_status(elf: &Elf) -> (&'static str, i32)
+{
+    match (elf.dynamic.is_some(), has_bind_now(elf))
+    {
+        (false, _) => ("BIND_NOW: N/A (statically linked)", 0),
+        (true, true) => ("BIND_NOW: enabled", 0),
+        (true, false) => ("BIND_NOW: disabled (lazy binding — GOT writable during execution)", 1)
+    }
+}
+
+
+fn return_elf_binding_mode(elf: &Elf) -> ElfBindingMode
+{
+    if elf.dynamic.is_none()
+    {
+        return ElfBindingMode::NotApplicable;
+    }
+
+    match (has_bind_now(elf), has_gnu_relro(elf))
+    {
+        (true, true) => ElfBindingMode::FullRelro,
+        (true, false) => ElfBindingMode::Eager,
+        (false, _) => ElfBindingMode::Lazy
+    }
+}
+
+
+/* (level, imported __*_chk functions, imported plain functions that have a _chk variant) */
+fn return_fortify_status(elf: &Elf) -> (FortifyLevel, Vec<String>, Vec<String>)
+{
+    let chk_pattern: Regex = Regex::new(r"^__.*_chk$").expect("Failed to compile FORTIFY pattern!");
+
+    let imports: Vec<&str> = elf.dynsyms.iter()
+        .filter(|symbol| symbol.is_import())
+        .filter_map(|symbol| elf.dynstrtab.get_at(symbol.st_name))
+        .collect();
+
+    let fortified: Vec<String> = imports.iter()
+        .filter(|name| chk_pattern.is_match(name))
+        .map(|name| name.to_string())
+        .collect();
+
+    let unfortified: Vec<String> = imports.iter()
+        .filter(|name| FORTIFIABLE_FUNCTIONS.contains(name))
+        .map(|name| name.to_string())
+        .collect();
+
+    let level: FortifyLevel = match (fortified.is_empty(), unfortified.is_empty())
+    {
+        (true, true) => FortifyLevel::NotApplicable,
+        (true, false) => FortifyLevel::Disabled,
+        (false, true) => FortifyLevel::Strong,
+        (false, false) => FortifyLevel::Partial
+    };
+
+    (level, fortified, unfortified)
+}
@@ -0,0 +1,1920 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Symbol tables, hash tables, imports and exports */
+
+use super::*;
+
+impl Arguments
+{
+    pub(crate) fn parse_dynamic_syms(&self, elf_obj: &Elf)
+    {
+        /* Dynamically accessible symbols table, every .dynstr string (library and version names too) unless --sort-symbols asks for the ordered .dynsym entries */
+        let listing: Vec<(String, String)> = match &self.sort_symbols
+        {
+            Some(key) => {
+                let mut symbols: Vec<SymbolInfo> = return_symbol_infos(&elf_obj.dynsyms, &elf_obj.dynstrtab);
+
+                symbols.retain(|symbol| !symbol.name.is_empty());
+                sort_symbols(&mut symbols, key);
+
+                /* Show the value the listing is ordered by */
+                symbols.into_iter().map(|symbol| {
+                    let value: String = match key
+                    {
+                        SymbolSortKey::Address { .. } => format!(" @ {:#x}", symbol.address),
+                        SymbolSortKey::Size => format!(" ({} bytes)", symbol.size),
+                        SymbolSortKey::Binding => format!(" [{}]", symbol.binding),
+                        SymbolSortKey::Type => format!(" [{}]", symbol.symbol_type),
+
+                        _ => String::new()
+                    };
+
+                    (symbol.name, value)
+                }).collect()
+            },
+
+            None => {
+                let elf_dym_sym: &Strtab<'_> = &elf_obj.dynstrtab;
+                let strings: Vec<&str> = elf_dym_sym.to_vec().expect("Failed to convert dynamic symbol table to vector!");
+
+                strings.into_iter().map(|string| (string.to_string(), String::new())).collect()
+            }
+        };
+
+        let elf_dymsym_vec: Vec<&str> = listing.iter().map(|(name, _)| name.as_str()).collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("symbols", &elf_dymsym_vec);
+            self.insert_document("libraries", &self.parse_dynamic_libs(elf_obj));
+            return;
+        }
+        
+        let mut c: i32 = 0;
+
+        println!("Dynamic Symbols Table =>\n");
+
+        for (mut i, (name, value)) in listing.iter().enumerate()
+        {
+            i += 1;
+
+            println!("\t {i}. {name}{value}");
+            c += 1;
+        }
+
+        println!("\n[DYNSYMS] {c} dynamic symbols found.");
+        self.print_dynamic_libs(elf_obj, self.parse_dynamic_libs(elf_obj));
+    }
+
+
+    pub(super) fn parse_annotated_symtab(&self, elf_obj: &Elf, data: &[u8])
+    {
+        if elf_obj.syms.is_empty()
+        {
+            eprintln!("Error - binary has no static symbol table (.symtab), it may have been stripped!");
+            std::process::exit(-1);
+        }
+
+        /* Address sorted (address, file, line) rows from every DWARF line program */
+        let line_rows: Vec<(u64, String, u64)> = return_dwarf_line_rows(elf_obj, data);
+
+        /* -gsplit-dwarf: the skeleton keeps .debug_line, the function DIEs live in the companion .dwo files */
+        let (is_dwo_file, split_units): (bool, Vec<SplitDwarfRecord>) = detect_split_dwarf(elf_obj, data);
+
+        let dwo_locations: HashMap<String, (String, u64)> = split_units.iter()
+            .filter(|unit| unit.exists)
+            .filter_map(|unit| fs::read(&unit.path).ok())
+            .flat_map(|dwo_data| return_dwo_decl_locations(&dwo_data))
+            .collect();
+
+        let mut order: Vec<SymbolInfo> = return_symbol_infos(&elf_obj.syms, &elf_obj.strtab);
+        sort_symbols(&mut order, self.sort_symbols.as_ref().unwrap_or(&SymbolSortKey::Index));
+
+        let records: Vec<AnnotatedSymbolRecord> = order.iter().filter_map(|info| elf_obj.syms.get(info.index)).map(|symbol| {
+            let location: Option<&(u64, String, u64)> = match symbol.st_type() == STT_FUNC && symbol.st_value != 0
+            {
+                true => {
+                    let idx: usize = line_rows.partition_point(|row| row.0 < symbol.st_value);
+
+                    line_rows.get(idx).filter(|row| row.0 < symbol.st_value + symbol.st_size.max(1))
+                },
+
+                false => None
+            };
+
+            /* No line row covers the symbol, fall back to the declaration recorded in a companion .dwo */
+            let location: Option<(u64, String, u64)> = location.cloned().or_else(|| {
+                let (file, line): &(String, u64) = dwo_locations.get(elf_obj.strtab.get_at(symbol.st_name)?)?;
+
+                Some((symbol.st_value, file.clone(), *line))
+            });
+
+            AnnotatedSymbolRecord {
+                name: elf_obj.strtab.get_at(symbol.st_name).map(|name| name.to_string()),
+                value: symbol.st_value,
+                size: symbol.st_size,
+                symbol_type: sym::type_to_str(symbol.st_type()).to_string(),
+                bind: sym::bind_to_str(symbol.st_bind()).to_string(),
+
+                /* Only the basename is kept, full compilation directories make the table unreadable */
+                source_file: location.as_ref().map(|row| Path::new(&row.1).file_name().map_or(row.1.clone(), |f| f.to_string_lossy().to_string())),
+                line: location.map(|row| row.2)
+            }
+        }).collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("annotated_symbols", &records);
+
+            if !split_units.is_empty()
+            {
+                self.insert_document("split_dwarf", &split_units);
+            }
+
+            return;
+        }
+
+        println!("\nAnnotated Symbol Table =>");
+
+        if is_dwo_file
+        {
+            println!("\n* This file is a split DWARF object (.dwo), its line tables carry no addresses.");
+        }
+
+        for unit in &split_units
+        {
+            println!("\n* Split DWARF: {} -> {} ({})", unit.dwo_name, unit.path, match unit.exists
+            {
+                true => "found, used for symbols without line information",
+                false => "missing"
+            });
+        }
+
+        if line_rows.is_empty()
+        {
+            println!("\n* No DWARF line information found (.debug_line), source columns left blank.");
+        }
+
+        let mut symbol_table: Table = self.new_table();
+
+        symbol_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Bind").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Source File").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Line").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            symbol_table.add_row(vec![
+                Cell::new(record.name.as_deref().unwrap_or("Not defined")).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}", record.value)),
+                Cell::new(format!("{} bytes", record.size)),
+                Cell::new(&record.symbol_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&record.bind).fg(Color::Yellow),
+                Cell::new(record.source_file.as_deref().unwrap_or("")).fg(Color::Cyan),
+                Cell::new(record.line.map_or(String::new(), |line| line.to_string()))
+            ]);
+        }
+
+        println!("\n{symbol_table}");
+        println!("\n{} symbols, {} annotated with source locations.", records.len(), records.iter().filter(|r| r.line.is_some()).count());
+    }
+
+
+    /* Returns true when any exported symbol was removed, added or resized */
+    pub(crate) fn compare_symbols(&self, old_path: &str, new_path: &str) -> bool
+    {
+        let old_data: Vec<u8> = fs::read(old_path).expect("Failed to read old binary file data!");
+        let new_data: Vec<u8> = fs::read(new_path).expect("Failed to read new binary file data!");
+
+        let old_elf: Elf = Elf::parse(&old_data).expect("Failed to parse old binary as ELF!");
+        let new_elf: Elf = Elf::parse(&new_data).expect("Failed to parse new binary as ELF!");
+
+        let old_syms: Vec<SymbolDiffRecord> = return_exported_symbols(&old_elf);
+        let new_syms: Vec<SymbolDiffRecord> = return_exported_symbols(&new_elf);
+
+        let find = |syms: &'_ [SymbolDiffRecord], record: &SymbolDiffRecord| -> Option<u64> {
+            syms.iter().find(|s| s.name == record.name && s.table == record.table).map(|s| s.size)
+        };
+
+        let removed: Vec<&SymbolDiffRecord> = old_syms.iter().filter(|sym| find(&new_syms, sym).is_none()).collect();
+        let added: Vec<&SymbolDiffRecord> = new_syms.iter().filter(|sym| find(&old_syms, sym).is_none()).collect();
+
+        let changed: Vec<SymbolChangeRecord> = old_syms.iter().filter_map(|sym| match find(&new_syms, sym)
+        {
+            Some(new_size) if new_size != sym.size => Some(SymbolChangeRecord {
+                name: sym.name.clone(),
+                table: sym.table.clone(),
+                old_size: sym.size,
+                new_size
+            }),
+
+            _ => None
+        }).collect();
+
+        let has_changes: bool = !removed.is_empty() || !added.is_empty() || !changed.is_empty();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("removed", &removed);
+            self.insert_document("added", &added);
+            self.insert_document("changed", &changed);
+
+            return has_changes;
+        }
+
+        println!("\nSymbol Comparison => {old_path} -> {new_path}");
+
+        let mut diff_table: Table = self.new_table();
+
+        diff_table.set_header(vec![
+                Cell::new("Change").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Table").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for sym in &removed
+        {
+            diff_table.add_row(vec![
+                Cell::new("REMOVED").fg(Color::Red).add_attribute(Attribute::Bold),
+                Cell::new(&sym.table),
+                Cell::new(&sym.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{} bytes", sym.size))
+            ]);
+        }
+
+        for sym in &added
+        {
+            diff_table.add_row(vec![
+                Cell::new("ADDED").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new(&sym.table),
+                Cell::new(&sym.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{} bytes", sym.size))
+            ]);
+        }
+
+        for sym in &changed
+        {
+            diff_table.add_row(vec![
+                Cell::new("CHANGED").fg(Color::Yellow).add_attribute(Attribute::Bold),
+                Cell::new(&sym.table),
+                Cell::new(&sym.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{} \u{2192} {} bytes", sym.old_size, sym.new_size))
+            ]);
+        }
+
+        match has_changes
+        {
+            true => println!("\n{diff_table}"),
+            false => println!("\n* No exported symbol changes detected.")
+        }
+
+        println!("\n{} removed, {} added, {} changed.", removed.len(), added.len(), changed.len());
+
+        has_changes
+    }
+
+
+    /* Scriptable check, exits 0 when the stripped state matches what the caller expects */
+    pub(super) fn check_stripped(&self, elf_obj: &Elf, expect_stripped: bool)
+    {
+        let stripped: bool = is_stripped(elf_obj);
+
+        match stripped
+        {
+            true => println!("binary is stripped"),
+            false => println!("binary is NOT stripped")
+        }
+
+        std::process::exit((stripped != expect_stripped) as i32);
+    }
+
+
+    pub(super) fn parse_stripping_level(&self, elf_obj: &Elf)
+    {
+        let has_section = |predicate: &dyn Fn(&str) -> bool| -> bool {
+            elf_obj.section_headers.iter().any(|shdr| elf_obj.shdr_strtab.get_at(shdr.sh_name).is_some_and(predicate))
+        };
+
+        let symtab: bool = has_section(&|name| name == ".symtab");
+        let debug_info: bool = has_section(&|name| name.starts_with(".debug_"));
+        let dynsym: bool = has_section(&|name| name == ".dynsym");
+
+        let level: StrippingLevel = match (symtab, debug_info, dynsym)
+        {
+            (true, true, _) => StrippingLevel::Unstripped,
+            (true, false, _) => StrippingLevel::DebugOnly,
+            (false, true, _) => StrippingLevel::SymbolsOnly,
+            (false, false, true) => StrippingLevel::DynSymOnly,
+            (false, false, false) => StrippingLevel::Full
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("stripping", &StrippingRecord {
+                level: level.get_type(),
+                description: level.get_description(),
+                symtab,
+                debug_info,
+                dynsym
+            });
+
+            return;
+        }
+
+        println!("Stripping level: {} \u{2014} {}", level.get_type(), level.get_description());
+    }
+
+
+    /* Bare number output so the result can be used directly in shell arithmetic */
+    pub(super) fn print_symbol_count(&self, elf_obj: &Elf, count_imports: bool)
+    {
+        let (imports, exports): (Vec<Sym>, Vec<Sym>) = classify_dynamic_symbols(elf_obj);
+
+        let (key, count): (&str, usize) = match count_imports
+        {
+            true => ("imports", imports.len()),
+            false => ("exports", exports.len())
+        };
+
+        match self.is_structured_output()
+        {
+            true => self.insert_document(key, &count),
+            false => println!("{count}")
+        }
+    }
+
+    /* Only undefined .dynsym entries are imports, a binary defining its own strcpy is not using libc's */
+    pub(super) fn audit_imports(&self, elf_obj: &Elf)
+    {
+        let (imports, _): (Vec<Sym>, Vec<Sym>) = classify_dynamic_symbols(elf_obj);
+        let imported: HashSet<&str> = imports.iter().filter_map(|symbol| elf_obj.dynstrtab.get_at(symbol.st_name)).collect();
+
+        let records: Vec<ImportAuditRecord> = DANGEROUS_IMPORTS.iter()
+            .filter(|(function, _, _, _)| imported.contains(function))
+            .map(|(function, severity, risk, alternative)| ImportAuditRecord {
+                function: function.to_string(),
+                severity: severity.to_string(),
+                risk: risk.to_string(),
+                alternative: alternative.to_string()
+            })
+            .collect();
+
+        let exit_code: i32 = match records.iter().any(|record| record.severity == "HIGH") { true => 1, false => 0 };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("import_audit", &records);
+            self.flush_document();
+
+            std::process::exit(exit_code);
+        }
+
+        if records.is_empty()
+        {
+            println!("No dangerous or deprecated imports ({} imports checked)", imported.len());
+            std::process::exit(0);
+        }
+
+        let mut audit_table: Table = self.new_table();
+
+        audit_table.set_header(vec![
+                Cell::new("Function").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Severity").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Risk").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Safer Alternative").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            audit_table.add_row(vec![
+                Cell::new(&record.function).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match record.severity.as_str()
+                {
+                    "HIGH" => Cell::new(&record.severity).fg(Color::Red),
+                    "MEDIUM" => Cell::new(&record.severity).fg(Color::Yellow),
+                    _ => Cell::new(&record.severity).fg(Color::DarkGrey)
+                },
+                Cell::new(&record.risk).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&record.alternative).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\nImport Audit =>");
+        println!("\n{audit_table}");
+
+        println!("\n{} flagged imports: {} HIGH, {} MEDIUM, {} LOW.",
+            records.len(),
+            records.iter().filter(|record| record.severity == "HIGH").count(),
+            records.iter().filter(|record| record.severity == "MEDIUM").count(),
+            records.iter().filter(|record| record.severity == "LOW").count()
+        );
+
+        std::process::exit(exit_code);
+    }
+
+
+    /* Walks every bucket of the SysV .hash table, long chains mean slow symbol lookups in the dynamic linker */
+    pub(super) fn parse_sysv_hash(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let stats: HashStatsRecord = match return_sysv_hash_stats(elf_obj, data)
+        {
+            Ok(stats) => stats,
+
+            Err(reason) => {
+                eprintln!("Error - {reason}");
+                std::process::exit(-1);
+            }
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("hash_stats", &stats);
+
+            return;
+        }
+
+        println!("\nSysV Hash Table ({}) =>", stats.section);
+
+        let mut hash_table: Table = self.new_table();
+
+        hash_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (property, value) in [
+            ("Total buckets", stats.nbucket.to_string()),
+            ("Non-empty buckets", stats.non_empty_buckets.to_string()),
+            ("Total chains (symbols)", stats.nchain.to_string()),
+            ("Average chain length", format!("{:.2}", stats.average_chain_length)),
+            ("Maximum chain length", stats.max_chain_length.to_string())
+        ]
+        {
+            hash_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{hash_table}");
+
+        println!("\nChain Length Histogram =>");
+
+        let mut histogram_table: Table = self.new_table();
+
+        histogram_table.set_header(vec![
+                Cell::new("Length").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Buckets").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Distribution").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let peak: u32 = stats.histogram.iter().copied().max().unwrap_or(0);
+
+        for (length, &count) in stats.histogram.iter().enumerate()
+        {
+            histogram_table.add_row(vec![
+                Cell::new(length).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(count),
+                Cell::new(return_histogram_bar(count, peak, 40)).fg(Color::Cyan)
+            ]);
+        }
+
+        println!("\n{histogram_table}");
+    }
+
+
+    /* Per ELF convention an STT_FILE symbol precedes the local symbols of its translation unit, so ownership is positional */
+    pub(super) fn parse_per_file_symbols(&self, elf_obj: &Elf)
+    {
+        if elf_obj.syms.is_empty()
+        {
+            eprintln!("Error - binary has no static symbol table (.symtab), it may have been stripped!");
+            std::process::exit(-1);
+        }
+
+        let mut groups: Vec<FileSymbolsRecord> = Vec::new();
+
+        for symbol in elf_obj.syms.iter().skip(1)
+        {
+            let name: Option<String> = elf_obj.strtab.get_at(symbol.st_name).map(|name| name.to_string());
+
+            if symbol.st_type() == sym::STT_FILE
+            {
+                groups.push(FileSymbolsRecord { file: name.unwrap_or_else(|| String::from("Not defined")), symbols: Vec::new() });
+                continue;
+            }
+
+            /* Globals are not owned by any file, and section symbols carry no useful name */
+            if symbol.st_bind() != sym::STB_LOCAL || symbol.st_type() == sym::STT_SECTION
+            {
+                continue;
+            }
+
+            /* Locals emitted before the first STT_FILE (linker generated) */
+            if groups.is_empty()
+            {
+                groups.push(FileSymbolsRecord { file: String::from("(no file symbol)"), symbols: Vec::new() });
+            }
+
+            if let Some(group) = groups.last_mut()
+            {
+                group.symbols.push(FileSymbolRecord {
+                    name,
+                    value: symbol.st_value,
+                    size: symbol.st_size,
+                    symbol_type: sym::type_to_str(symbol.st_type()).to_string()
+                });
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("per_file_symbols", &groups);
+            return;
+        }
+
+        println!("\nPer File Symbols =>");
+
+        for group in &groups
+        {
+            let mut file_table: Table = self.new_table();
+
+            file_table.set_header(vec![
+                    Cell::new(&group.file).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in &group.symbols
+            {
+                file_table.add_row(vec![
+                    Cell::new(record.name.as_deref().unwrap_or("Not defined")).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(format!("{:#x}", record.value)),
+                    Cell::new(format!("{} bytes", record.size)),
+                    Cell::new(&record.symbol_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                ]);
+            }
+
+            println!("\n{file_table}");
+        }
+
+        println!("\n{} source files, {} local symbols.", groups.len(), groups.iter().map(|group| group.symbols.len()).sum::<usize>());
+    }
+
+
+    /* Exports of the DT_NEEDED libraries present on this system are authoritative, the bundled database is the fallback */
+    pub(super) fn reconstruct_imports(&self, elf_obj: &Elf)
+    {
+        let (imports, _): (Vec<Sym>, Vec<Sym>) = classify_dynamic_symbols(elf_obj);
+        let needed_exports: Vec<(String, Vec<String>)> = return_needed_exports(elf_obj, &self.file);
+
+        let records: Vec<ImportOriginRecord> = imports.iter()
+            .filter_map(|symbol| elf_obj.dynstrtab.get_at(symbol.st_name))
+            .map(|name| {
+                let (library, confidence): (&str, &str) = needed_exports.iter()
+                    .find(|(_, exports)| exports.iter().any(|export| export == name))
+                    .map(|(library, _)| (library.as_str(), "high"))
+                    .or_else(|| KNOWN_LIBRARY_SYMBOLS.iter().find(|(_, symbols)| symbols.contains(&name)).map(|(library, _)| (*library, "medium")))
+                    .or_else(|| KNOWN_LIBRARY_PREFIXES.iter().find(|(prefix, _)| name.starts_with(prefix)).map(|(_, library)| (*library, "low")))
+                    .unwrap_or(("unknown", "none"));
+
+                ImportOriginRecord { symbol: name.to_string(), library: library.to_string(), confidence: confidence.to_string() }
+            })
+            .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("reconstructed_imports", &records);
+            return;
+        }
+
+        println!("\nReconstructed Imports =>");
+
+        if needed_exports.len() < elf_obj.libraries.len()
+        {
+            println!("\n* {} of {} DT_NEEDED libraries were not found on this system, falling back to the symbol database.", elf_obj.libraries.len() - needed_exports.len(), elf_obj.libraries.len());
+        }
+
+        let mut import_table: Table = self.new_table();
+
+        import_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Library").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Confidence").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            let confidence_color: Color = match record.confidence.as_str()
+            {
+                "high" => Color::Green,
+                "medium" => Color::Yellow,
+                "low" => Color::DarkYellow,
+
+                _ => Color::Red
+            };
+
+            import_table.add_row(vec![
+                Cell::new(&record.symbol).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.library),
+                Cell::new(&record.confidence).fg(confidence_color)
+            ]);
+        }
+
+        println!("\n{import_table}");
+    }
+
+    /* Direct DT_NEEDED exports only, like ld --no-copy-dt-needed-entries, symbols a dependency of a dependency provides count as missing */
+    pub(super) fn parse_unique_imports(&self, elf_obj: &Elf)
+    {
+        let (imports, _): (Vec<Sym>, Vec<Sym>) = classify_dynamic_symbols(elf_obj);
+        let needed_exports: Vec<(String, Vec<String>)> = return_needed_exports(elf_obj, &self.file);
+
+        let exported: HashSet<&str> = needed_exports.iter().flat_map(|(_, exports)| exports.iter().map(|export| export.as_str())).collect();
+
+        let libraries_not_found: Vec<String> = elf_obj.libraries.iter()
+            .filter(|library| !needed_exports.iter().any(|(found, _)| found == *library))
+            .map(|library| library.to_string())
+            .collect();
+
+        let unverifiable: bool = !libraries_not_found.is_empty();
+
+        let missing: Vec<MissingImportRecord> = imports.iter()
+            .filter_map(|symbol| elf_obj.dynstrtab.get_at(symbol.st_name).map(|name| (name, symbol.st_bind() == STB_WEAK)))
+            .filter(|(name, _)| !exported.contains(name))
+            .map(|(name, weak)| MissingImportRecord { symbol: name.to_string(), weak, unverifiable })
+            .collect();
+
+        let report: UniqueImportsRecord = UniqueImportsRecord {
+            imports: imports.len(),
+            libraries_checked: needed_exports.iter().map(|(library, _)| library.clone()).collect(),
+            libraries_not_found,
+            missing
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("unique_imports", &report);
+            return;
+        }
+
+        println!("\nUnique Imports =>");
+
+        println!("\nChecked {} imports against {} of {} DT_NEEDED libraries: {}",
+            report.imports,
+            report.libraries_checked.len(),
+            elf_obj.libraries.len(),
+            report.libraries_checked.join(", ")
+        );
+
+        if !report.libraries_not_found.is_empty()
+        {
+            println!("* Not found on this system, exports unchecked: {}", report.libraries_not_found.join(", "));
+        }
+
+        if report.missing.is_empty()
+        {
+            println!("\n* Every imported symbol is exported by a required library.");
+            return;
+        }
+
+        let mut missing_table: Table = self.new_table();
+
+        missing_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Status").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &report.missing
+        {
+            missing_table.add_row(vec![
+                Cell::new(&record.symbol).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match (record.weak, record.unverifiable)
+                {
+                    (true, _) => Cell::new("weak, may stay unresolved").fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                    (false, true) => Cell::new("unverifiable, not in the libraries checked").fg(Color::DarkYellow),
+                    (false, false) => Cell::new("MISSING from required libraries").fg(Color::Red)
+                }
+            ]);
+        }
+
+        println!("\n{missing_table}");
+
+        match report.libraries_not_found.is_empty()
+        {
+            true => println!("\n{} missing ({} weak).", report.missing.len(), report.missing.iter().filter(|record| record.weak).count()),
+            false => println!("\n{} unverifiable ({} weak), {} required libraries were not found.", report.missing.len(), report.missing.iter().filter(|record| record.weak).count(), report.libraries_not_found.len())
+        }
+    }
+
+    /* Written to stdout so it can be redirected straight into the consuming tool's file */
+    pub(super) fn export_symbols(&self, elf_obj: &Elf)
+    {
+        let format: &str = match self.get_flag_values("--export-symbols").first()
+        {
+            Some(format) if matches!(format.as_str(), "nm" | "gdb" | "lldb" | "json") => format,
+
+            _ => {
+                eprintln!("Error - usage: binarymagic <TARGET> --export-symbols <nm|gdb|lldb|json>");
+                std::process::exit(-1);
+            }
+        };
+
+        let mut symbols: Vec<ExportedSymbolRecord> = Vec::new();
+
+        /* .symtab is a superset of .dynsym when present, the dynamic table only fills in stripped binaries */
+        let (symtab, strtab): (&Symtab, &Strtab) = match elf_obj.syms.len()
+        {
+            0 => (&elf_obj.dynsyms, &elf_obj.dynstrtab),
+            _ => (&elf_obj.syms, &elf_obj.strtab)
+        };
+
+        for symbol in symtab.iter()
+        {
+            let name: &str = match strtab.get_at(symbol.st_name)
+            {
+                Some(name) if !name.is_empty() => name,
+                _ => continue
+            };
+
+            if matches!(symbol.st_type(), sym::STT_SECTION | sym::STT_FILE)
+            {
+                continue;
+            }
+
+            symbols.push(ExportedSymbolRecord {
+                address: symbol.st_value,
+                size: symbol.st_size,
+                symbol_type: return_nm_type(elf_obj, &symbol),
+                name: name.to_string()
+            });
+        }
+
+        symbols.sort_by_key(|symbol| symbol.address);
+
+        let defined = || symbols.iter().filter(|symbol| !matches!(symbol.symbol_type, 'U' | 'w' | 'v'));
+
+        match format
+        {
+            "nm" => {
+                let mut by_name: Vec<&ExportedSymbolRecord> = symbols.iter().collect();
+
+                /* nm lists by name unless -n is given */
+                by_name.sort_by(|a, b| a.name.cmp(&b.name));
+
+                for symbol in by_name
+                {
+                    match symbol.symbol_type
+                    {
+                        'U' | 'w' | 'v' => println!("{:>width$} {} {}", "", symbol.symbol_type, symbol.name, width = 16),
+                        _ => println!("{:016x} {} {}", symbol.address, symbol.symbol_type, symbol.name)
+                    }
+                }
+            },
+
+            /* Neither debugger can add bare symbols without an object file, convenience variables give `break *$name` instead */
+            "gdb" => {
+                println!("# GDB command file generated by BinaryMagic for {}, load with: source <file>", self.file);
+                println!("# Unrelocated addresses, add the load base for PIE/shared objects");
+
+                for symbol in defined()
+                {
+                    println!("set ${} = (void *) {:#x}", return_debugger_identifier(&symbol.name), symbol.address);
+                }
+            },
+
+            "lldb" => {
+                println!("# LLDB command file generated by BinaryMagic for {}, load with: command source <file>", self.file);
+                println!("# Unrelocated addresses, add the load base for PIE/shared objects");
+
+                for symbol in defined()
+                {
+                    println!("expr -- void *${} = (void *) {:#x}", return_debugger_identifier(&symbol.name), symbol.address);
+                }
+            },
+
+            _ => println!("{}", serde_json::to_string_pretty(&symbols).unwrap_or_default())
+        }
+    }
+
+    pub(super) fn parse_stripped_functions(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let functions: Vec<RecoveredFunctionRecord> = return_eh_frame_fdes(elf_obj, data).into_iter()
+            .map(|(start, size)| RecoveredFunctionRecord {
+                name: format!("func_{start:x}"),
+                start,
+                end: start.saturating_add(size),
+                size,
+                symbol: return_function_at(elf_obj, start)
+            })
+            .collect();
+
+        let report: RecoveredFunctionReportRecord = RecoveredFunctionReportRecord {
+            fde_count: functions.len(),
+            dynamic_function_count: elf_obj.dynsyms.iter().filter(|symbol| symbol.st_type() == STT_FUNC && !symbol.is_import()).count(),
+            functions
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("stripped_functions", &report);
+            return;
+        }
+
+        println!("\nFunctions Recovered From .eh_frame =>");
+
+        if report.functions.is_empty()
+        {
+            println!("\n* No FDE found (.eh_frame missing or built with -fno-asynchronous-unwind-tables).");
+            return;
+        }
+
+        let mut function_table: Table = self.new_table();
+
+        function_table.set_header(vec![
+                Cell::new("Function").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address Range").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Symbol").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for function in &report.functions
+        {
+            function_table.add_row(vec![
+                Cell::new(&function.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}-{:#x}", function.start, function.end)).fg(Color::Yellow),
+                Cell::new(function.size).fg(Color::Yellow),
+                Cell::new(function.symbol.as_deref().unwrap_or("")).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{function_table}");
+
+        /* One FDE per function that has unwind info, hand written assembly and some CRT stubs have none */
+        println!("\n{} FDEs (lower bound on the function count), {} exported functions in .dynsym.", report.fde_count, report.dynamic_function_count);
+
+        if report.fde_count < report.dynamic_function_count
+        {
+            println!("Warning: fewer FDEs than exported functions, part of the code was built without unwind tables.");
+        }
+    }
+
+    /* IFUNC symbols point at a resolver, the loader calls it and stores the returned implementation through R_*_IRELATIVE */
+    pub(super) fn parse_ifunc(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let ctx: Ctx = return_elf_ctx(elf_obj);
+
+        /* RELA addends (REL: the implicit addend at r_offset is not read here) of the IRELATIVE relocations are the resolver addresses */
+        let mut irelative_resolvers: Vec<u64> = Vec::new();
+        let mut irelative_relocations: usize = 0;
+
+        for shdr in elf_obj.section_headers.iter().filter(|shdr| shdr.sh_type == SHT_REL || shdr.sh_type == SHT_RELA)
+        {
+            let relocs: RelocSection = RelocSection::parse(data, shdr.sh_offset as usize, shdr.sh_size as usize, shdr.sh_type == SHT_RELA, ctx).unwrap_or_default();
+
+            for relocation in relocs.iter().filter(|relocation| is_irelative(reloc::r_to_str(relocation.r_type, elf_obj.header.e_machine)))
+            {
+                irelative_relocations += 1;
+
+                if let Some(addend) = relocation.r_addend
+                {
+                    irelative_resolvers.push(addend as u64);
+                }
+            }
+        }
+
+        let report: IfuncReportRecord = IfuncReportRecord {
+            symbols: [(".symtab", &elf_obj.syms, &elf_obj.strtab), (".dynsym", &elf_obj.dynsyms, &elf_obj.dynstrtab)].iter()
+                .flat_map(|(table, symtab, strtab)| symtab.iter()
+                    .filter(|symbol| symbol.st_type() == STT_GNU_IFUNC)
+                    .map(|symbol| IfuncRecord {
+                        name: strtab.get_at(symbol.st_name).unwrap_or("").to_string(),
+                        table: table.to_string(),
+                        resolver: symbol.st_value,
+                        size: symbol.st_size,
+                        binding: sym::bind_to_str(symbol.st_bind()).to_string(),
+                        visibility: sym::visibility_to_str(symbol.st_visibility()).to_string(),
+                        irelative: irelative_resolvers.contains(&symbol.st_value)
+                    })
+                    .collect::<Vec<IfuncRecord>>())
+                .collect(),
+
+            irelative_relocations
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("ifunc", &report);
+            return;
+        }
+
+        println!("\nGNU Indirect Functions (STT_GNU_IFUNC) =>");
+
+        if report.symbols.is_empty()
+        {
+            println!("\n* No STT_GNU_IFUNC symbols in .symtab or .dynsym.");
+        }
+        else
+        {
+            let mut ifunc_table: Table = self.new_table();
+
+            ifunc_table.set_header(vec![
+                    Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Table").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Resolver").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Binding").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Visibility").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("IRELATIVE").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in &report.symbols
+            {
+                ifunc_table.add_row(vec![
+                    Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(&record.table),
+                    Cell::new(format!("{:#x}", record.resolver)).fg(Color::Yellow),
+                    Cell::new(record.size).fg(Color::Yellow),
+                    Cell::new(&record.binding).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                    Cell::new(&record.visibility).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+
+                    match record.irelative
+                    {
+                        true => Cell::new(CHECK).fg(Color::Magenta).add_attribute(Attribute::Bold),
+                        false => Cell::new(CROSS).fg(Color::DarkGrey).add_attribute(Attribute::Dim)
+                    }
+                ]);
+            }
+
+            println!("\n{ifunc_table}");
+        }
+
+        /* Exported IFUNCs are bound through GLOB_DAT/JUMP_SLOT by their users, IRELATIVE only covers the local calls */
+        println!("\n{} STT_GNU_IFUNC symbols, {} R_*_IRELATIVE relocations (resolved at startup, not through R_*_GLOB_DAT).",
+            report.symbols.len(), report.irelative_relocations);
+    }
+
+    pub(super) fn parse_unresolved_weak(&self, elf_obj: &Elf)
+    {
+        let needed_exports: Vec<(String, Vec<String>)> = return_needed_exports(elf_obj, &self.file);
+
+        let records: Vec<UnresolvedWeakRecord> = elf_obj.dynsyms.iter()
+            .filter(|symbol| symbol.st_bind() == STB_WEAK && symbol.st_shndx == SHN_UNDEF as usize)
+            .filter_map(|symbol| elf_obj.dynstrtab.get_at(symbol.st_name))
+            .filter(|name| !name.is_empty())
+            .map(|name| UnresolvedWeakRecord {
+                symbol: name.to_string(),
+                provided_by: needed_exports.iter()
+                    .find(|(_, exports)| exports.iter().any(|export| export == name))
+                    .map(|(library, _)| library.clone())
+            })
+            .collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("unresolved_weak", &records);
+            return;
+        }
+
+        println!("\nUndefined Weak Symbols =>");
+
+        if records.is_empty()
+        {
+            println!("\n* No undefined weak symbols in .dynsym.");
+            return;
+        }
+
+        if needed_exports.len() < elf_obj.libraries.len()
+        {
+            println!("\n* {} of {} DT_NEEDED libraries were not found on this system, their exports could not be checked.", elf_obj.libraries.len() - needed_exports.len(), elf_obj.libraries.len());
+        }
+
+        let mut weak_table: Table = self.new_table();
+
+        weak_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Resolution").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            weak_table.add_row(vec![
+                Cell::new(&record.symbol).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+
+                match &record.provided_by
+                {
+                    Some(library) => Cell::new(format!("provided by {library}")).fg(Color::Green),
+                    None => Cell::new("UNRESOLVED \u{2014} will be 0 at runtime").fg(Color::Red).add_attribute(Attribute::Bold)
+                }
+            ]);
+        }
+
+        println!("\n{weak_table}");
+
+        let unresolved: usize = records.iter().filter(|record| record.provided_by.is_none()).count();
+
+        println!("\n{} undefined weak symbols, {} unresolved (callers must check them against NULL).", records.len(), unresolved);
+    }
+
+    pub(super) fn compare_hash_tables(&self, elf_obj: &Elf, data: &[u8])
+    {
+        let sysv: Option<HashStatsRecord> = return_sysv_hash_stats(elf_obj, data).ok();
+        let gnu: Option<GnuHashStatsRecord> = return_gnu_hash_stats(elf_obj, data).ok();
+
+        let note: String = match (&sysv, &gnu)
+        {
+            (Some(_), Some(_)) => String::from("GNU hash provides O(1) average lookup; SysV hash has O(K/N) where K=symbols, N=buckets. glibc uses DT_GNU_HASH and only keeps DT_HASH for older loaders."),
+            (None, Some(_)) => String::from("No SysV .hash: glibc looks symbols up through DT_GNU_HASH, loaders without GNU hash support (glibc < 2.5, some embedded loaders) cannot resolve symbols from this object."),
+            (Some(_), None) => String::from("No .gnu.hash: the dynamic linker falls back to the SysV DT_HASH table for every lookup, without bloom filter rejection of missing symbols (link with --hash-style=gnu or both)."),
+            (None, None) => String::from("No symbol hash table: nothing can be looked up in this object at run time (static binary or relocatable object).")
+        };
+
+        let comparison: HashComparisonRecord = HashComparisonRecord { sysv, gnu, note };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("hash_table_stats", &comparison);
+            return;
+        }
+
+        println!("\nHash Table Comparison =>");
+
+        let mut compare_table: Table = self.new_table();
+
+        compare_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new(comparison.sysv.as_ref().map_or(String::from("SysV (missing)"), |sysv| format!("SysV ({})", sysv.section))).fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new(comparison.gnu.as_ref().map_or(String::from("GNU (missing)"), |gnu| format!("GNU ({})", gnu.section))).fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let sysv = comparison.sysv.as_ref();
+        let gnu = comparison.gnu.as_ref();
+
+        for (property, sysv_value, gnu_value) in [
+            ("Buckets", sysv.map(|sysv| sysv.nbucket.to_string()), gnu.map(|gnu| gnu.nbuckets.to_string())),
+            ("Hashed symbols", sysv.map(|sysv| sysv.histogram.iter().enumerate().map(|(length, &count)| length as u32 * count).sum::<u32>().to_string()), gnu.map(|gnu| gnu.symbols.to_string())),
+            ("Non-empty buckets", sysv.map(|sysv| sysv.non_empty_buckets.to_string()), gnu.map(|gnu| gnu.non_empty_buckets.to_string())),
+            ("Average chain length", sysv.map(|sysv| format!("{:.2}", sysv.average_chain_length)), gnu.map(|gnu| format!("{:.2}", gnu.average_chain_length))),
+            ("Maximum chain length", sysv.map(|sysv| sysv.max_chain_length.to_string()), gnu.map(|gnu| gnu.max_chain_length.to_string())),
+            ("Bloom filter words", sysv.map(|_| String::from("none")), gnu.map(|gnu| gnu.bloom_size.to_string())),
+
+            /* SysV compares the name of every chain entry, GNU compares hashes first and names only on a full hash match */
+            ("Worst case lookup", sysv.map(|sysv| format!("{} strcmp", sysv.max_chain_length)), gnu.map(|gnu| format!("bloom + {} hash compares", gnu.max_chain_length)))
+        ]
+        {
+            compare_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(sysv_value.unwrap_or_else(|| String::from("-"))).fg(Color::Yellow),
+                Cell::new(gnu_value.unwrap_or_else(|| String::from("-"))).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{compare_table}");
+        println!("\n* {}", comparison.note);
+    }
+}
+
+
+/* --audit-imports database: (function, severity, risk, safer alternative) */
+const DANGEROUS_IMPORTS: [(&str, &str, &str, &str); 16] = [
+    ("gets", "HIGH", "buffer overflow (no bounds checking)", "fgets"),
+    ("system", "HIGH", "command injection (runs /bin/sh -c)", "posix_spawn/execve with an argument vector"),
+    ("popen", "HIGH", "command injection (runs /bin/sh -c)", "pipe + posix_spawn/execve"),
+    ("strcpy", "MEDIUM", "buffer overflow (unbounded copy)", "strlcpy or snprintf"),
+    ("strcat", "MEDIUM", "buffer overflow (unbounded append)", "strlcat or snprintf"),
+    ("sprintf", "MEDIUM", "buffer overflow (unbounded format output)", "snprintf"),
+    ("vsprintf", "MEDIUM", "buffer overflow (unbounded format output)", "vsnprintf"),
+    ("setuid", "MEDIUM", "privilege change, an unchecked failure keeps the old privileges", "setresuid with its return value checked"),
+    ("setgid", "MEDIUM", "privilege change, an unchecked failure keeps the old privileges", "setresgid with its return value checked"),
+    ("seteuid", "MEDIUM", "privilege change, the real/saved ids can restore the old privileges", "setresuid with its return value checked"),
+    ("setegid", "MEDIUM", "privilege change, the real/saved ids can restore the old privileges", "setresgid with its return value checked"),
+    ("dlopen", "LOW", "runtime code loading (a relative or empty path searches the library path)", "dlopen with an absolute path"),
+    ("rand", "LOW", "non-cryptographic RNG (predictable)", "getrandom or arc4random"),
+    ("rand_r", "LOW", "non-cryptographic RNG (predictable)", "getrandom or arc4random"),
+    ("srand", "LOW", "non-cryptographic RNG seeding (predictable)", "getrandom or arc4random"),
+    ("random", "LOW", "non-cryptographic RNG (predictable)", "getrandom or arc4random")
+];
+
+/* Bundled database of well known exports, an exact hit is a medium confidence match */
+const KNOWN_LIBRARY_SYMBOLS: [(&str, &[&str]); 5] = [
+    ("libc.so.6", &[
+        "printf", "fprintf", "sprintf", "snprintf", "puts", "putchar", "fputs", "fwrite", "fread", "fopen", "fclose",
+        "fflush", "malloc", "calloc", "realloc", "free", "memcpy", "memmove", "memset", "memcmp", "strlen", "strcmp",
+        "strncmp", "strcpy", "strncpy", "strcat", "strchr", "strrchr", "strstr", "strdup", "atoi", "strtol", "strtoul",
+        "exit", "abort", "getenv", "open", "close", "read", "write", "lseek", "mmap", "munmap", "fork", "execve",
+        "waitpid", "signal", "sigaction", "time", "qsort", "__libc_start_main", "__cxa_finalize", "__stack_chk_fail",
+        "__errno_location", "setlocale", "isatty", "ioctl"
+    ]),
+    ("libm.so.6", &[
+        "sqrt", "sqrtf", "pow", "powf", "exp", "log", "log10", "log2", "sin", "cos", "tan", "asin", "acos", "atan",
+        "atan2", "floor", "ceil", "fabs", "fmod", "round", "trunc", "hypot", "cbrt"
+    ]),
+    ("libdl.so.2", &["dlopen", "dlsym", "dlclose", "dlerror", "dladdr", "dlvsym"]),
+    ("libpthread.so.0", &["sem_init", "sem_wait", "sem_post", "sem_destroy"]),
+    ("libstdc++.so.6", &["__cxa_allocate_exception", "__cxa_throw", "__cxa_begin_catch", "__cxa_end_catch", "__gxx_personality_v0"])
+];
+
+/* Name prefixes reserved by a library's API, a low confidence fallback */
+const KNOWN_LIBRARY_PREFIXES: [(&str, &str); 8] = [
+    ("pthread_", "libpthread.so.0"),
+    ("SSL_", "libssl.so"),
+    ("TLS_", "libssl.so"),
+    ("EVP_", "libcrypto.so"),
+    ("BIO_", "libcrypto.so"),
+    ("ERR_", "libcrypto.so"),
+    ("_ZNSt", "libstdc++.so.6"),
+    ("_ZSt", "libstdc++.so.6")
+];
+
+enum StrippingLevel
+{
+    Full,               /* no .symtab, no .debug_*, no .dynsym */
+    DebugOnly,          /* .debug_* stripped, .symtab kept (strip --strip-debug) */
+    SymbolsOnly,        /* .symtab stripped, .debug_* kept */
+    Unstripped,         /* .symtab and .debug_* present */
+    DynSymOnly          /* only .dynsym left, the usual release build */
+}
+
+impl StrippingLevel
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            StrippingLevel::Full => String::from("Full"),
+            StrippingLevel::DebugOnly => String::from("DebugOnly"),
+            StrippingLevel::SymbolsOnly => String::from("SymbolsOnly"),
+            StrippingLevel::Unstripped => String::from("Unstripped"),
+            StrippingLevel::DynSymOnly => String::from("DynSymOnly")
+        }
+    }
+
+    fn get_description(&self) -> String
+    {
+        match *self
+        {
+            StrippingLevel::Full => String::from("No symbol information at all, every function has to be recovered from the code itself."),
+            StrippingLevel::DebugOnly => String::from("Debug info was stripped but the static symbol table remains, function names are available without source locations."),
+            StrippingLevel::SymbolsOnly => String::from("The static symbol table was removed while DWARF was kept (unusual), names and source locations must come from the debug info."),
+            StrippingLevel::Unstripped => String::from("Static symbols and DWARF debug info are both present, full names and source locations are available."),
+            StrippingLevel::DynSymOnly => String::from("Only dynamic imports/exports remain, internal functions are anonymous (typical release binary).")
+        }
+    }
+}
+
+/* Companion .dwo named by a skeleton compilation unit (DW_AT_dwo_name / DW_AT_GNU_dwo_name) */
+#[derive(Serialize)]
+struct SplitDwarfRecord
+{
+    dwo_name: String,
+    path: String,
+    exists: bool
+}
+
+#[derive(Serialize)]
+struct AnnotatedSymbolRecord
+{
+    name: Option<String>,
+    value: u64,
+    size: u64,
+    symbol_type: String,
+    bind: String,
+    source_file: Option<String>,
+    line: Option<u64>
+}
+
+#[derive(Serialize)]
+struct ImportOriginRecord
+{
+    symbol: String,
+    library: String,
+    confidence: String
+}
+
+#[derive(Serialize)]
+struct MissingImportRecord
+{
+    symbol: String,
+    /* Weak undefined references may legitimately stay unresolved (resolve to 0) */
+    weak: bool,
+    /* A DT_NEEDED library was not found, it may be the one exporting the symbol */
+    unverifiable: bool
+}
+
+#[derive(Serialize)]
+struct UniqueImportsRecord
+{
+    imports: usize,
+    libraries_checked: Vec<String>,
+    /* DT_NEEDED entries not found on this system, their exports were not checked */
+    libraries_not_found: Vec<String>,
+    missing: Vec<MissingImportRecord>
+}
+
+#[derive(Serialize)]
+struct ImportAuditRecord
+{
+    function: String,
+    severity: String,
+    risk: String,
+    alternative: String
+}
+
+#[derive(Serialize)]
+struct RecoveredFunctionRecord
+{
+    name: String,
+    start: u64,
+    end: u64,
+    size: u64,
+    /* Symbol covering the start address when one survived stripping (.symtab or .dynsym) */
+    symbol: Option<String>
+}
+
+#[derive(Serialize)]
+struct RecoveredFunctionReportRecord
+{
+    functions: Vec<RecoveredFunctionRecord>,
+    fde_count: usize,
+    dynamic_function_count: usize
+}
+
+#[derive(Serialize)]
+struct ExportedSymbolRecord
+{
+    address: u64,
+    size: u64,
+    /* nm(1) type letter */
+    #[serde(rename = "type")]
+    symbol_type: char,
+    name: String
+}
+
+#[derive(Serialize)]
+struct FileSymbolRecord
+{
+    name: Option<String>,
+    value: u64,
+    size: u64,
+    symbol_type: String
+}
+
+#[derive(Serialize)]
+struct FileSymbolsRecord
+{
+    file: String,
+    symbols: Vec<FileSymbolRecord>
+}
+
+#[derive(Serialize)]
+struct GnuHashStatsRecord
+{
+    section: String,
+    nbuckets: u32,
+    symoffset: u32,
+    bloom_size: u32,
+    symbols: u32,
+    non_empty_buckets: u32,
+    average_chain_length: f64,
+    max_chain_length: u32
+}
+
+#[derive(Serialize)]
+struct HashComparisonRecord
+{
+    sysv: Option<HashStatsRecord>,
+    gnu: Option<GnuHashStatsRecord>,
+    note: String
+}
+
+#[derive(Serialize)]
+struct HashStatsRecord
+{
+    section: String,
+    nbucket: u32,
+    nchain: u32,
+    non_empty_buckets: u32,
+    average_chain_length: f64,
+    max_chain_length: u32,
+    /* histogram[n] = number of buckets whose chain holds n symbols */
+    histogram: Vec<u32>
+}
+
+#[derive(Serialize)]
+struct SymbolDiffRecord
+{
+    name: String,
+    table: String,
+    address: u64,
+    size: u64
+}
+
+#[derive(Serialize)]
+struct IfuncRecord
+{
+    name: String,
+    table: String,
+    resolver: u64,
+    size: u64,
+    binding: String,
+    visibility: String,
+    /* An R_*_IRELATIVE relocation with this resolver as addend exists */
+    irelative: bool
+}
+
+#[derive(Serialize)]
+struct IfuncReportRecord
+{
+    symbols: Vec<IfuncRecord>,
+    irelative_relocations: usize
+}
+
+#[derive(Serialize)]
+struct SymbolChangeRecord
+{
+    name: String,
+    table: String,
+    old_size: u64,
+    new_size: u64
+}
+
+/* Sortable view of a symbol table entry, index is its position in the ELF table */
+struct SymbolInfo
+{
+    index: usize,
+    name: String,
+    address: u64,
+    size: u64,
+    binding: String,
+    symbol_type: String
+}
+
+#[derive(Serialize)]
+struct UnresolvedWeakRecord
+{
+    symbol: String,
+    /* First DT_NEEDED library exporting the symbol, None when it will be 0 at runtime */
+    provided_by: Option<String>
+}
+
+#[derive(Serialize)]
+struct StrippingRecord
+{
+    level: String,
+    description: String,
+    symtab: bool,
+    debug_info: bool,
+    dynsym: bool
+}
+
+
+/* (initial location, length) of every FDE in .eh_frame, sorted by address */
+fn return_eh_frame_fdes(elf: &Elf, data: &[u8]) -> Vec<(u64, u64)>
+{
+    let endian: RunTimeEndian = match elf.little_endian
+    {
+        true => RunTimeEndian::Little,
+        false => RunTimeEndian::Big
+    };
+
+    let section_address = |name: &str| -> u64 {
+        elf.section_headers.iter()
+            .find(|shdr| elf.shdr_strtab.get_at(shdr.sh_name) == Some(name))
+            .map_or(0, |shdr| shdr.sh_addr)
+    };
+
+    let eh_frame: EhFrame<EndianSlice<RunTimeEndian>> = EhFrame::new(return_section_data(elf, data, ".eh_frame").unwrap_or(&[]), endian);
+
+    /* Pointer encodings are relative to these sections (DW_EH_PE_pcrel, textrel, datarel) */
+    let bases: BaseAddresses = BaseAddresses::default()
+        .set_eh_frame(section_address(".eh_frame"))
+        .set_eh_frame_hdr(section_address(".eh_frame_hdr"))
+        .set_text(section_address(".text"))
+        .set_got(section_address(".got"));
+
+    let mut fdes: Vec<(u64, u64)> = Vec::new();
+    let mut entries = eh_frame.entries(&bases);
+
+    while let Ok(Some(entry)) = entries.next()
+    {
+        if let CieOrFde::Fde(partial) = entry
+        {
+            if let Ok(fde) = partial.parse(|section, bases, offset| section.cie_from_offset(bases, offset))
+            {
+                fdes.push((fde.initial_address(), fde.len()));
+            }
+        }
+    }
+
+    fdes.sort();
+    fdes
+}
+
+
+/* nm(1) type letter, lowercase for local symbols */
+fn return_nm_type(elf: &Elf, symbol: &Sym) -> char
+{
+    let weak: bool = symbol.st_bind() == STB_WEAK;
+    let object: bool = symbol.st_type() == STT_OBJECT;
+
+    let letter: char = match symbol.st_shndx as u32
+    {
+        SHN_UNDEF if weak => return match object { true => 'v', false => 'w' },
+        SHN_UNDEF => return 'U',
+        SHN_ABS => 'A',
+        SHN_COMMON => 'C',
+        _ if weak => return match object { true => 'V', false => 'W' },
+        _ if symbol.st_type() == STT_GNU_IFUNC => return 'i',
+
+        _ => match elf.section_headers.get(symbol.st_shndx)
+        {
+            Some(shdr) if shdr.sh_flags & SHF_EXECINSTR as u64 != 0 => 'T',
+            Some(shdr) if shdr.sh_type == SHT_NOBITS => 'B',
+            Some(shdr) if shdr.sh_flags & SHF_WRITE as u64 != 0 => 'D',
+            Some(shdr) if shdr.sh_flags & SHF_ALLOC as u64 != 0 => 'R',
+
+            _ => 'N'
+        }
+    };
+
+    match symbol.st_bind() == sym::STB_LOCAL
+    {
+        true => letter.to_ascii_lowercase(),
+        false => letter
+    }
+}
+
+
+/* Debugger convenience variables only take [A-Za-z0-9_], versioned or C++ names are flattened */
+fn return_debugger_identifier(name: &str) -> String
+{
+    name.chars().map(|c| match c.is_ascii_alphanumeric() { true => c, false => '_' }).collect()
+}
+
+
+/* Split the named dynamic symbols into imports (undefined) and exports (defined global/weak) */
+fn classify_dynamic_symbols(elf: &Elf) -> (Vec<Sym>, Vec<Sym>)
+{
+    let named = elf.dynsyms.iter().filter(|symbol| elf.dynstrtab.get_at(symbol.st_name).is_some_and(|name| !name.is_empty()));
+
+    let (imports, defined): (Vec<Sym>, Vec<Sym>) = named.partition(|symbol| symbol.st_shndx == SHN_UNDEF as usize);
+    let exports: Vec<Sym> = defined.into_iter().filter(|symbol| symbol.st_bind() == STB_GLOBAL || symbol.st_bind() == STB_WEAK).collect();
+
+    (imports, exports)
+}
+
+
+/* Defined global/weak symbols from both the static (.symtab) and dynamic (.dynsym) tables */
+fn return_exported_symbols(elf: &Elf) -> Vec<SymbolDiffRecord>
+{
+    let mut exported: Vec<SymbolDiffRecord> = Vec::new();
+
+    for (table, symtab, strtab) in [("static", &elf.syms, &elf.strtab), ("dynamic", &elf.dynsyms, &elf.dynstrtab)]
+    {
+        for symbol in symtab.iter()
+        {
+            let bind: u8 = symbol.st_bind();
+
+            if symbol.st_shndx == SHN_UNDEF as usize || (bind != STB_GLOBAL && bind != STB_WEAK)
+            {
+                continue;
+            }
+
+            if let Some(name) = strtab.get_at(symbol.st_name).filter(|name| !name.is_empty())
+            {
+                exported.push(SymbolDiffRecord {
+                    name: name.to_string(),
+                    table: table.to_string(),
+                    address: symbol.st_value,
+                    size: symbol.st_size
+                });
+            }
+        }
+    }
+
+    exported
+}
+
+
+/* (library, exported names) for every DT_NEEDED entry that could be found (RUNPATH/RPATH, then the system dirs) and parsed */
+fn return_needed_exports(elf: &Elf, file: &str) -> Vec<(String, Vec<String>)>
+{
+    let search_dirs: Vec<PathBuf> = return_library_search_dirs(elf, file);
+
+    elf.libraries.iter()
+        .filter_map(|&library| {
+            let lib_data: Vec<u8> = return_needed_library(elf, library, &search_dirs)?;
+            let lib_elf: Elf = Elf::parse(&lib_data).ok()?;
+
+            let (_, exports): (Vec<Sym>, Vec<Sym>) = classify_dynamic_symbols(&lib_elf);
+
+            Some((library.to_string(), exports.iter().filter_map(|symbol| lib_elf.dynstrtab.get_at(symbol.st_name).map(|name| name.to_string())).collect()))
+        })
+        .collect()
+}
+
+fn return_symbol_infos(symtab: &Symtab, strtab: &Strtab) -> Vec<SymbolInfo>
+{
+    symtab.iter().enumerate().map(|(index, symbol)| SymbolInfo {
+        index,
+        name: strtab.get_at(symbol.st_name).unwrap_or("").to_string(),
+        address: symbol.st_value,
+        size: symbol.st_size,
+        binding: sym::bind_to_str(symbol.st_bind()).to_string(),
+        symbol_type: sym::type_to_str(symbol.st_type()).to_string()
+    }).collect()
+}
+
+/* Stable sort, ties keep their ELF table order */
+fn sort_symbols(symbols: &mut [SymbolInfo], key: &SymbolSortKey)
+{
+    match *key
+    {
+        SymbolSortKey::Index => symbols.sort_by_key(|symbol| symbol.index),
+
+        /* Case folded first so "Foo" and "foo" sit together, then code point order */
+        SymbolSortKey::Name => symbols.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.name.cmp(&b.name))),
+
+        SymbolSortKey::Address { zero_last } => symbols.sort_by_key(|symbol| (zero_last && symbol.address == 0, symbol.address)),
+        SymbolSortKey::Size => symbols.sort_by_key(|symbol| symbol.size),
+        SymbolSortKey::Binding => symbols.sort_by(|a, b| a.binding.cmp(&b.binding)),
+        SymbolSortKey::Type => symbols.sort_by(|a, b| a.symbol_type.cmp(&b.symbol_type))
+    }
+}
+
+/* Walks every bucket of the SysV .hash table (nbucket, nchain, buckets[], chains[]) */
+fn return_sysv_hash_stats(elf: &Elf, data: &[u8]) -> Result<HashStatsRecord, String>
+{
+    let hash_shdr = match elf.section_headers.iter().find(|shdr| shdr.sh_type == SHT_HASH)
+    {
+        Some(shdr) => shdr,
+        None => return Err(String::from("binary has no SysV hash table (SHT_HASH), only DT_GNU_HASH or none at all!"))
+    };
+
+    let section_name: String = elf.shdr_strtab.get_at(hash_shdr.sh_name).unwrap_or(".hash").to_string();
+    let hash_bytes: &[u8] = return_section_range(hash_shdr, data).unwrap_or(&[]);
+
+    /* Both ELF32 and ELF64 use 32-bit hash words */
+    let words: Vec<u32> = return_hash_words(elf, hash_bytes);
+
+    if words.len() < 2
+    {
+        return Err(format!("{section_name} is too small to hold nbucket/nchain!"));
+    }
+
+    let nbucket: u32 = words[0];
+    let nchain: u32 = words[1];
+
+    let (buckets, chains): (&[u32], &[u32]) = match words[2..].split_at_checked(nbucket as usize)
+    {
+        Some((buckets, chains)) if chains.len() >= nchain as usize => (buckets, &chains[..nchain as usize]),
+
+        _ => return Err(format!("{section_name} is truncated (nbucket={nbucket}, nchain={nchain})!"))
+    };
+
+    let mut chain_lengths: Vec<u32> = Vec::with_capacity(buckets.len());
+
+    for &head in buckets
+    {
+        let mut length: u32 = 0;
+        let mut index: u32 = head;
+
+        /* STN_UNDEF terminates a chain, the nchain bound guards against cyclic (corrupt) chains */
+        while index != 0 && (index as usize) < chains.len() && length < nchain
+        {
+            length += 1;
+            index = chains[index as usize];
+        }
+
+        chain_lengths.push(length);
+    }
+
+    let max_chain_length: u32 = chain_lengths.iter().copied().max().unwrap_or(0);
+    let non_empty_buckets: u32 = chain_lengths.iter().filter(|&&length| length != 0).count() as u32;
+    let total_entries: u32 = chain_lengths.iter().sum();
+
+    let mut histogram: Vec<u32> = vec![0; max_chain_length as usize + 1];
+
+    for &length in &chain_lengths
+    {
+        histogram[length as usize] += 1;
+    }
+
+    let stats: HashStatsRecord = HashStatsRecord {
+        section: section_name,
+        nbucket,
+        nchain,
+        non_empty_buckets,
+        /* Averaged over non-empty buckets, i.e. the expected probe count for a symbol that is present */
+        average_chain_length: match non_empty_buckets { 0 => 0.0, _ => total_entries as f64 / non_empty_buckets as f64 },
+        max_chain_length,
+        histogram
+    };
+
+    Ok(stats)
+}
+
+
+/* .gnu.hash: nbuckets, symoffset, bloom_size, bloom_shift, bloom[] (class sized words), buckets[], chain[] (bit 0 ends a chain) */
+fn return_gnu_hash_stats(elf: &Elf, data: &[u8]) -> Result<GnuHashStatsRecord, String>
+{
+    let hash_shdr = match elf.section_headers.iter().find(|shdr| shdr.sh_type == SHT_GNU_HASH)
+    {
+        Some(shdr) => shdr,
+        None => return Err(String::from("binary has no GNU hash table (SHT_GNU_HASH)!"))
+    };
+
+    let section_name: String = elf.shdr_strtab.get_at(hash_shdr.sh_name).unwrap_or(".gnu.hash").to_string();
+    let hash_bytes: &[u8] = return_section_range(hash_shdr, data).unwrap_or(&[]);
+    let words: Vec<u32> = return_hash_words(elf, hash_bytes);
+
+    if words.len() < 4
+    {
+        return Err(format!("{section_name} is too small to hold its header!"));
+    }
+
+    let (nbuckets, symoffset, bloom_size): (u32, u32, u32) = (words[0], words[1], words[2]);
+
+    /* Bloom filter words are 64-bit on ELFCLASS64, i.e. two hash words each */
+    let bloom_words: usize = bloom_size as usize * match elf.is_64 { true => 2, false => 1 };
+
+    let buckets: &[u32] = match words.get(4 + bloom_words..4 + bloom_words + nbuckets as usize)
+    {
+        Some(buckets) => buckets,
+        None => return Err(format!("{section_name} is truncated (nbuckets={nbuckets}, bloom_size={bloom_size})!"))
+    };
+
+    let chains: &[u32] = &words[4 + bloom_words + nbuckets as usize..];
+
+    let mut chain_lengths: Vec<u32> = Vec::with_capacity(buckets.len());
+
+    for &head in buckets
+    {
+        let mut length: u32 = 0;
+
+        /* 0 marks an empty bucket, otherwise the chain starts at dynsym index head */
+        if head >= symoffset && head != 0
+        {
+            for &hash in chains.iter().skip((head - symoffset) as usize)
+            {
+                length += 1;
+
+                if hash & 1 != 0
+                {
+                    break;
+                }
+            }
+        }
+
+        chain_lengths.push(length);
+    }
+
+    let non_empty_buckets: u32 = chain_lengths.iter().filter(|&&length| length != 0).count() as u32;
+    let symbols: u32 = chain_lengths.iter().sum();
+
+    Ok(GnuHashStatsRecord {
+        section: section_name,
+        nbuckets,
+        symoffset,
+        bloom_size,
+        symbols,
+        non_empty_buckets,
+        average_chain_length: match non_empty_buckets { 0 => 0.0, _ => symbols as f64 / non_empty_buckets as f64 },
+        max_chain_length: chain_lengths.iter().copied().max().unwrap_or(0)
+    })
+}
+
+/* Hash sections are arrays of 32-bit words in the byte order of the target */
+fn return_hash_words(elf: &Elf, bytes: &[u8]) -> Vec<u32>
+{
+    bytes.chunks_exact(4)
+        .map(|word: &[u8]| {
+            let raw: [u8; 4] = [word[0], word[1], word[2], word[3]];
+
+            match elf.little_endian
+            {
+                true => u32::from_le_bytes(raw),
+                false => u32::from_be_bytes(raw)
+            }
+        })
+        .collect()
+}
+
+/* A binary is stripped once it carries neither a static symbol table nor DWARF debug sections */
+fn is_stripped(elf: &Elf) -> bool
+{
+    !elf.section_headers.iter().any(|shdr| match elf.shdr_strtab.get_at(shdr.sh_name)
+    {
+        Some(name) => name == ".symtab" || name.starts_with(".debug_"),
+        None => false
+    })
+}
+
+
+/* Unicode bar scaled so that `peak` fills `width` cells, eighth blocks give sub-cell resolution */
+fn return_histogram_bar(count: u32, peak: u32, width: u32) -> String
+{
+    const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    if peak == 0
+    {
+        return String::new();
+    }
+
+    let eighths: u64 = count as u64 * width as u64 * 8 / peak as u64;
+    let mut bar: String = "█".repeat((eighths / 8) as usize);
+
+    let remainder: u64 = eighths % 8;
+
+    if remainder != 0
+    {
+        bar.push(PARTIAL_BLOCKS[remainder as usize]);
+    }
+
+    bar
+}
+
+
+/* (file is itself split DWARF, companion .dwo files referenced by skeleton units) */
+fn detect_split_dwarf(elf: &Elf, data: &[u8]) -> (bool, Vec<SplitDwarfRecord>)
+{
+    let is_dwo_file: bool = elf.section_headers.iter()
+        .filter_map(|shdr| elf.shdr_strtab.get_at(shdr.sh_name))
+        .any(|name| name == ".debug_info.dwo" || name.starts_with(".gnu.debuglto_"));
+
+    let endian: RunTimeEndian = match elf.little_endian
+    {
+        true => RunTimeEndian::Little,
+        false => RunTimeEndian::Big
+    };
+
+    let load_section = |id: SectionId| -> Result<EndianSlice<RunTimeEndian>, gimli::Error> {
+        Ok(EndianSlice::new(return_section_data(elf, data, id.name()).unwrap_or(&[]), endian))
+    };
+
+    let mut split_units: Vec<SplitDwarfRecord> = Vec::new();
+
+    let dwarf = match Dwarf::load(load_section)
+    {
+        Ok(dwarf) => dwarf,
+        Err(_) => return (is_dwo_file, split_units)
+    };
+
+    let mut units = dwarf.units();
+
+    while let Ok(Some(unit_header)) = units.next()
+    {
+        let unit = match dwarf.unit(unit_header)
+        {
+            Ok(unit) => unit,
+            Err(_) => continue
+        };
+
+        let dwo_name: String = match unit.dwo_name()
+        {
+            Ok(Some(value)) => match dwarf.attr_string(&unit, value)
+            {
+                Ok(name) => name.to_string_lossy().to_string(),
+                Err(_) => continue
+            },
+
+            _ => continue
+        };
+
+        /* DW_AT_dwo_name is relative to the DW_AT_comp_dir of the skeleton unit */
+        let path: String = match &unit.comp_dir
+        {
+            Some(comp_dir) => Path::new(comp_dir.to_string_lossy().as_ref()).join(&dwo_name).to_string_lossy().to_string(),
+            None => dwo_name.clone()
+        };
+
+        split_units.push(SplitDwarfRecord { exists: Path::new(&path).exists(), dwo_name, path });
+    }
+
+    (is_dwo_file, split_units)
+}
+
+
+/* Function name -> (compilation unit file, DW_AT_decl_line) from the split units of a .dwo file */
+fn return_dwo_decl_locations(data: &[u8]) -> HashMap<String, (String, u64)>
+{
+    let mut locations: HashMap<String, (String, u64)> = HashMap::new();
+
+    let elf: Elf = match Elf::parse(data)
+    {
+        Ok(elf) => elf,
+        Err(_) => return locations
+    };
+
+    let endian: RunTimeEndian = match elf.little_endian
+    {
+        true => RunTimeEndian::Little,
+        false => RunTimeEndian::Big
+    };
+
+    let load_section = |id: SectionId| -> Result<EndianSlice<RunTimeEndian>, gimli::Error> {
+        let section: &[u8] = id.dwo_name().and_then(|name| return_section_data(&elf, data, name)).unwrap_or(&[]);
+
+        Ok(EndianSlice::new(section, endian))
+    };
+
+    let mut dwarf = match Dwarf::load(load_section)
+    {
+        Ok(dwarf) => dwarf,
+        Err(_) => return locations
+    };
+
+    /* Makes string offsets and DWARF 4 GNU units resolve the way they do inside a .dwo */
+    dwarf.file_type = DwarfFileType::Dwo;
+
+    let mut units = dwarf.units();
+
+    while let Ok(Some(unit_header)) = units.next()
+    {
+        let unit = match dwarf.unit(unit_header)
+        {
+            Ok(unit) => unit,
+            Err(_) => continue
+        };
+
+        let unit_file: String = unit.name.map_or(String::new(), |name| name.to_string_lossy().to_string());
+        let mut entries = unit.entries();
+
+        while let Ok(Some(entry)) = entries.next_dfs()
+        {
+            if entry.tag() != gimli::DW_TAG_subprogram
+            {
+                continue;
+            }
+
+            let name: Option<String> = entry.attr_value(gimli::DW_AT_name)
+                .and_then(|value| dwarf.attr_string(&unit, value).ok())
+                .map(|name| name.to_string_lossy().to_string());
+
+            let line: Option<u64> = entry.attr_value(gimli::DW_AT_decl_line).and_then(|value| value.udata_value());
+
+            if let (Some(name), Some(line)) = (name, line)
+            {
+                locations.insert(name, (unit_file.clone(), line));
+            }
+        }
+    }
+
+    locations
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /* Every key is a stable sort, ties keep their symbol table order */
+    #[test]
+    fn symbol_sort_keys()
+    {
+        let symbol = |index: usize, name: &str, address: u64, size: u64, binding: &str| -> SymbolInfo {
+            SymbolInfo { index, name: name.to_string(), address, size, binding: binding.to_string(), symbol_type: String::from("FUNC") }
+        };
+
+        let mut symbols: Vec<SymbolInfo> = vec![
+            symbol(0, "beta", 0x20, 8, "GLOBAL"),
+            symbol(1, "alpha", 0, 4, "WEAK"),
+            symbol(2, "Alpha", 0x10, 8, "LOCAL"),
+            symbol(3, "_start", 0, 4, "GLOBAL")
+        ];
+
+        let order = |symbols: &[SymbolInfo]| -> Vec<usize> { symbols.iter().map(|symbol| symbol.index).collect() };
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Name);
+        assert_eq!(order(&symbols), vec![3, 2, 1, 0]);
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Index);
+        assert_eq!(order(&symbols), vec![0, 1, 2, 3]);
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Address { zero_last: false });
+        assert_eq!(order(&symbols), vec![1, 3, 2, 0]);
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Address { zero_last: true });
+        assert_eq!(order(&symbols), vec![2, 0, 1, 3]);
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Index);
+        sort_symbols(&mut symbols, &SymbolSortKey::Size);
+        assert_eq!(order(&symbols), vec![1, 3, 0, 2]);
+
+        sort_symbols(&mut symbols, &SymbolSortKey::Index);
+        sort_symbols(&mut symbols, &SymbolSortKey::Binding);
+        assert_eq!(order(&symbols), vec![0, 3, 2, 1]);
+    }
+}
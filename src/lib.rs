@@ -0,0 +1,252 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/*
+    Library entry point of BinaryMagic.
+
+    analyze_elf() parses the binary once and hands back every result in a single ElfAnalysis value,
+    API users read analysis.sections, analysis.security, ... instead of calling the individual parsers.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use goblin::elf::Elf;
+use goblin::elf::header::{self as elf_header, ET_DYN};
+use goblin::elf::program_header::{self as elf_program_header, PT_GNU_RELRO, PT_GNU_STACK, PT_LOAD, PF_R, PF_W, PF_X};
+use goblin::elf::section_header;
+use goblin::elf::sym::{self, Symtab};
+use goblin::elf::dynamic::{DT_FLAGS, DT_FLAGS_1, DT_BIND_NOW, DF_BIND_NOW, DF_1_NOW};
+use goblin::strtab::Strtab;
+use goblin::container::Endian;
+
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ElfHeader
+{
+    pub class: String,
+    pub endianness: String,
+    pub file_type: String,
+    pub machine: String,
+    pub entry: u64,
+    pub flags: u32,
+    pub program_header_offset: u64,
+    pub section_header_offset: u64,
+    pub program_header_count: u16,
+    pub section_header_count: u16
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Section
+{
+    pub index: usize,
+
+    /* None when sh_name does not resolve in the section name string table */
+    pub name: Option<String>,
+    pub section_type: String,
+
+    /* Raw sh_type, OS and processor specific values have no name in section_type */
+    pub raw_type: u32,
+    pub flags: u64,
+    pub address: u64,
+    pub offset: u64,
+    pub size: u64,
+    pub entry_size: u64,
+    pub alignment: u64
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Symbol
+{
+    pub index: usize,
+    pub name: String,
+    pub value: u64,
+    pub size: u64,
+    pub symbol_type: String,
+    pub binding: String,
+    pub visibility: String,
+    pub section_index: usize
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Library
+{
+    pub name: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgramHeader
+{
+    pub segment_type: String,
+    pub flags: String,
+    pub offset: u64,
+    pub virtual_address: u64,
+    pub physical_address: u64,
+    pub file_size: u64,
+    pub memory_size: u64,
+    pub alignment: u64
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SecurityReport
+{
+    pub relro: String,
+    pub bind_now: bool,
+    pub nx: bool,
+    pub pie: bool,
+    pub canary: bool
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ElfAnalysis
+{
+    pub header: ElfHeader,
+    pub sections: Vec<Section>,
+    pub symbols: Vec<Symbol>,
+    pub dynamic_symbols: Vec<Symbol>,
+    pub imports: Vec<Library>,
+    pub program_headers: Vec<ProgramHeader>,
+    pub security: SecurityReport
+}
+
+
+/* Parse the ELF32/ELF64 binary once and collect every analysis result, the error is goblin's parse failure */
+pub fn analyze_elf(data: &[u8]) -> Result<ElfAnalysis, String>
+{
+    let elf_obj: Elf = Elf::parse(data).map_err(|err| err.to_string())?;
+
+    Ok(ElfAnalysis {
+        header: return_header(&elf_obj),
+        sections: return_sections(&elf_obj),
+        symbols: return_symbols(&elf_obj.syms, &elf_obj.strtab),
+        dynamic_symbols: return_symbols(&elf_obj.dynsyms, &elf_obj.dynstrtab),
+        imports: elf_obj.libraries.iter().map(|library| Library { name: library.to_string() }).collect(),
+        program_headers: return_program_headers(&elf_obj),
+        security: return_security_report(&elf_obj)
+    })
+}
+
+
+fn return_header(elf: &Elf) -> ElfHeader
+{
+    ElfHeader {
+        class: match elf.is_64 { true => "ELF64", false => "ELF32" }.to_string(),
+        endianness: match elf.header.endianness() { Ok(Endian::Big) => "Big", _ => "Little" }.to_string(),
+        file_type: elf_header::et_to_str(elf.header.e_type).to_string(),
+        machine: elf_header::machine_to_str(elf.header.e_machine).to_string(),
+        entry: elf.header.e_entry,
+        flags: elf.header.e_flags,
+        program_header_offset: elf.header.e_phoff,
+        section_header_offset: elf.header.e_shoff,
+        program_header_count: elf.header.e_phnum,
+        section_header_count: elf.header.e_shnum
+    }
+}
+
+
+fn return_sections(elf: &Elf) -> Vec<Section>
+{
+    elf.section_headers.iter().enumerate()
+        .map(|(index, shdr)| Section {
+            index,
+            name: elf.shdr_strtab.get_at(shdr.sh_name).map(|name| name.to_string()),
+            section_type: section_header::sht_to_str(shdr.sh_type).to_string(),
+            raw_type: shdr.sh_type,
+            flags: shdr.sh_flags,
+            address: shdr.sh_addr,
+            offset: shdr.sh_offset,
+            size: shdr.sh_size,
+            entry_size: shdr.sh_entsize,
+            alignment: shdr.sh_addralign
+        })
+        .collect()
+}
+
+
+/* Index 0 (the reserved null symbol) is kept so Symbol::index matches the symbol table index used by relocations */
+fn return_symbols(symtab: &Symtab, strtab: &Strtab) -> Vec<Symbol>
+{
+    symtab.iter().enumerate()
+        .map(|(index, symbol)| Symbol {
+            index,
+            name: strtab.get_at(symbol.st_name).unwrap_or("").to_string(),
+            value: symbol.st_value,
+            size: symbol.st_size,
+            symbol_type: sym::type_to_str(symbol.st_type()).to_string(),
+            binding: sym::bind_to_str(symbol.st_bind()).to_string(),
+            visibility: sym::visibility_to_str(symbol.st_visibility()).to_string(),
+            section_index: symbol.st_shndx
+        })
+        .collect()
+}
+
+
+fn return_program_headers(elf: &Elf) -> Vec<ProgramHeader>
+{
+    elf.program_headers.iter()
+        .map(|phdr| ProgramHeader {
+            segment_type: elf_program_header::pt_to_str(phdr.p_type).to_string(),
+            flags: format!("{}{}{}",
+                match phdr.p_flags & PF_R != 0 { true => 'R', false => '-' },
+                match phdr.p_flags & PF_W != 0 { true => 'W', false => '-' },
+                match phdr.p_flags & PF_X != 0 { true => 'X', false => '-' }
+            ),
+            offset: phdr.p_offset,
+            virtual_address: phdr.p_vaddr,
+            physical_address: phdr.p_paddr,
+            file_size: phdr.p_filesz,
+            memory_size: phdr.p_memsz,
+            alignment: phdr.p_align
+        })
+        .collect()
+}
+
+
+/* Same rules as the CLI checks: --security (RELRO/binding), --check-nx (stack and W^X), --check-pie and --check-canary (.dynstr) */
+fn return_security_report(elf: &Elf) -> SecurityReport
+{
+    let bind_now: bool = match &elf.dynamic
+    {
+        Some(dynamic) => dynamic.dyns.iter().any(|d| match d.d_tag
+        {
+            DT_BIND_NOW => true,
+            DT_FLAGS => d.d_val & DF_BIND_NOW != 0,
+            DT_FLAGS_1 => d.d_val & DF_1_NOW != 0,
+
+            _ => false
+        }),
+
+        None => false
+    };
+
+    let relro: &str = match (elf.program_headers.iter().any(|phdr| phdr.p_type == PT_GNU_RELRO), bind_now)
+    {
+        (true, true) => "Full",
+        (true, false) => "Partial",
+        (false, _) => "None"
+    };
+
+    SecurityReport {
+        relro: relro.to_string(),
+        bind_now,
+        nx: return_nx(elf),
+        pie: elf.header.e_type == ET_DYN && elf.soname.is_none() && elf.entry != 0,
+        canary: elf.dynstrtab.to_vec().unwrap_or_default().contains(&"__stack_chk_fail")
+    }
+}
+
+
+/* A non-executable stack only counts when no section or PT_LOAD segment is both writable and executable */
+fn return_nx(elf: &Elf) -> bool
+{
+    let stack_nx: bool = elf.program_headers.iter().any(|phdr| phdr.p_type == PT_GNU_STACK && phdr.p_flags & PF_X == 0);
+
+    let writable_code: u64 = (section_header::SHF_WRITE | section_header::SHF_EXECINSTR) as u64;
+
+    let wx_section: bool = elf.section_headers.iter().any(|shdr| shdr.sh_flags & writable_code == writable_code);
+    let wx_segment: bool = elf.program_headers.iter().any(|phdr| phdr.p_type == PT_LOAD && phdr.p_flags & PF_W != 0 && phdr.p_flags & PF_X != 0);
+
+    stack_nx && !wx_section && !wx_segment
+}
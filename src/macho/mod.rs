@@ -0,0 +1,1248 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Mach-O analysis */
+
+use super::*;
+
+impl ProgramArgumentMethod
+{
+    pub(crate) fn start_mach_method_selector(&self, args: &Arguments, macho: &MachO, data: &[u8], flag: &str)
+    {
+        match &self
+        {
+            ProgramArgumentMethod::MachSymbols => args.parse_macho_symbols(macho),
+            ProgramArgumentMethod::MachImports => args.parse_macho_imports(macho, data),
+            ProgramArgumentMethod::MachExports => args.parse_macho_exports(macho),
+            ProgramArgumentMethod::MachCodeSign => args.parse_macho_code_signature(macho, data),
+            ProgramArgumentMethod::MachSegments => args.parse_macho_segments(macho),
+            ProgramArgumentMethod::MachUuid => args.extract_macho_uuid(macho),
+            ProgramArgumentMethod::MachDyldInfo => args.parse_macho_dyld_info(macho, data),
+
+            _ => {
+                eprintln!("Error - option \"{}\" is not supported for Mach-O binaries!", flag);
+                std::process::exit(-1);
+            }
+        }
+    }
+}
+
+impl Arguments
+{
+    fn parse_macho_symbols(&self, macho: &MachO)
+    {
+        let verbose: bool = self.flags.iter().any(|flag| flag == "--verbose");
+
+        /* n_sect is an ordinal over every section of every segment, in load command order */
+        let section_names: Vec<String> = macho.segments.iter()
+            .flat_map(|segment| segment.sections().unwrap_or_default())
+            .map(|(section, _)| format!("{},{}", section.segname().unwrap_or("?"), section.name().unwrap_or("?")))
+            .collect();
+
+        let mut records: Vec<MachSymbolRecord> = Vec::new();
+        let mut hidden_stabs: usize = 0;
+
+        for (name, nlist) in macho.symbols().filter_map(|symbol| symbol.ok())
+        {
+            /* Symbolic debugging entries (N_SO, N_FUN, N_OSO...) are noise unless explicitly requested */
+            if nlist.is_stab() && !verbose
+            {
+                hidden_stabs += 1;
+                continue;
+            }
+
+            records.push(MachSymbolRecord {
+                name: name.to_string(),
+                value: nlist.n_value,
+                section_index: nlist.n_sect,
+                section: nlist.n_sect.checked_sub(1).and_then(|index| section_names.get(index)).cloned(),
+                symbol_type: return_macho_symbol_type(&nlist),
+                external: nlist.is_global(),
+                /* Undefined external with a zero value, a non-zero value would make it a common symbol */
+                import: nlist.is_global() && nlist.get_type() == N_UNDF && nlist.n_value == 0
+            });
+        }
+
+        let imports: usize = records.iter().filter(|record| record.import).count();
+        let exports: usize = records.iter().filter(|record| record.external && !record.import).count();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_symbols", &records);
+            return;
+        }
+
+        println!("\nMach-O Symbols =>");
+
+        let mut symbol_table: Table = self.new_table();
+
+        symbol_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("External").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            let section: String = match &record.section
+            {
+                Some(section_name) => format!("{} ({})", record.section_index, section_name),
+                None => String::from("NO_SECT")
+            };
+
+            symbol_table.add_row(vec![
+                match record.import
+                {
+                    true => Cell::new(&record.name).fg(Color::Red).add_attribute(Attribute::Bold),
+                    false => Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold)
+                },
+
+                Cell::new(format!("{:#x}", record.value)).fg(Color::Yellow),
+                Cell::new(section),
+                Cell::new(&record.symbol_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+
+                match record.external
+                {
+                    true => Cell::new(CHECK).fg(Color::Green).add_attribute(Attribute::Bold),
+                    false => Cell::new(CROSS).fg(Color::Red).add_attribute(Attribute::Dim)
+                }
+            ]);
+        }
+
+        println!("\n{symbol_table}");
+        println!("\n{} symbols: {} imports (undefined externals), {} exports, {} local.",
+            records.len(), imports, exports, records.len() - imports - exports);
+
+        if hidden_stabs > 0
+        {
+            println!("* {} debugging (N_STAB) entries hidden, pass --verbose to show them", hidden_stabs);
+        }
+    }
+
+    fn parse_macho_imports(&self, macho: &MachO, data: &[u8])
+    {
+        let imports = match macho.imports()
+        {
+            Ok(imports) => imports,
+
+            Err(err) => {
+                eprintln!("Error - unable to decode the dyld bind opcodes: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        /* goblin only keeps the dylib paths, the versions live in the LC_*_DYLIB commands themselves */
+        let mut dylib_versions: HashMap<String, String> = HashMap::new();
+        let mut weak_dylibs: Vec<String> = Vec::new();
+
+        for cmd in &macho.load_commands
+        {
+            let (dylib, weak) = match &cmd.command
+            {
+                CommandVariant::LoadDylib(dylib) | CommandVariant::LoadUpwardDylib(dylib)
+                    | CommandVariant::ReexportDylib(dylib) | CommandVariant::LazyLoadDylib(dylib) => (dylib, false),
+                CommandVariant::LoadWeakDylib(dylib) => (dylib, true),
+
+                _ => continue
+            };
+
+            let name: String = match data.get(cmd.offset + dylib.dylib.name as usize..)
+            {
+                Some(bytes) => String::from_utf8_lossy(bytes.split(|&b| b == 0).next().unwrap_or(&[])).to_string(),
+                None => continue
+            };
+
+            if weak
+            {
+                weak_dylibs.push(name.clone());
+            }
+
+            dylib_versions.insert(name, return_dylib_version(dylib.dylib.current_version));
+        }
+
+        let mut records: Vec<MachImportRecord> = imports.iter().map(|import| MachImportRecord {
+            name: import.name.to_string(),
+            dylib: import.dylib.to_string(),
+            dylib_version: dylib_versions.get(import.dylib).cloned(),
+            weak: import.is_weak || weak_dylibs.iter().any(|dylib| dylib == import.dylib),
+
+            bind_type: match (import.is_weak, import.is_lazy)
+            {
+                (true, _) => String::from("weak"),
+                (false, true) => String::from("lazy"),
+                (false, false) => String::from("regular")
+            }
+        }).collect();
+
+        /* Group by dylib, keeping the bind order inside each library */
+        records.sort_by(|a, b| a.dylib.cmp(&b.dylib));
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_imports", &records);
+            return;
+        }
+
+        println!("\nMach-O Imports =>");
+
+        let mut libraries: Vec<&str> = records.iter().map(|record| record.dylib.as_str()).collect();
+        libraries.dedup();
+
+        for library in &libraries
+        {
+            let version: &str = dylib_versions.get(*library).map_or("unknown version", |version| version.as_str());
+
+            let mut import_table: Table = self.new_table();
+
+            import_table.set_header(vec![
+                    Cell::new(format!("{library} ({version})")).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Weak").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Bind Type").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in records.iter().filter(|record| record.dylib == *library)
+            {
+                import_table.add_row(vec![
+                    Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+
+                    match record.weak
+                    {
+                        true => Cell::new(CHECK).fg(Color::Yellow).add_attribute(Attribute::Bold),
+                        false => Cell::new("")
+                    },
+
+                    Cell::new(&record.bind_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                ]);
+            }
+
+            println!("\n{import_table}");
+        }
+
+        println!("\n{} imports from {} libraries: {}", records.len(), libraries.len(), libraries.join(", "));
+    }
+
+    pub(crate) fn parse_macho_dyld_info(&self, macho: &MachO, data: &[u8])
+    {
+        let dyld_info: MachDyldInfoRecord = return_macho_dyld_info(macho, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_dyld_info", &dyld_info);
+            return;
+        }
+
+        if !dyld_info.present
+        {
+            let chained: bool = macho.load_commands.iter().any(|cmd| matches!(cmd.command, CommandVariant::DyldChainedFixups(_)));
+
+            match chained
+            {
+                true => println!("\nNo LC_DYLD_INFO, this binary uses chained fixups (LC_DYLD_CHAINED_FIXUPS) instead of rebase/bind opcodes."),
+                false => println!("\nNo LC_DYLD_INFO or LC_DYLD_INFO_ONLY load command.")
+            }
+
+            return;
+        }
+
+        println!("\nRebase Operations =>");
+
+        let mut rebase_table: Table = self.new_table();
+
+        rebase_table.set_header(vec![
+                Cell::new("Segment").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for rebase in &dyld_info.rebases
+        {
+            rebase_table.add_row(vec![
+                Cell::new(&rebase.segment).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&rebase.section).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}", rebase.address)).fg(Color::Yellow),
+                Cell::new(&rebase.rebase_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{rebase_table}");
+
+        println!("\nBind Operations =>");
+
+        let mut bind_table: Table = self.new_table();
+
+        bind_table.set_header(vec![
+                Cell::new("Segment").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Symbol").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Library").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Kind").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for bind in &dyld_info.binds
+        {
+            let symbol: String = match bind.addend
+            {
+                0 => bind.symbol.clone(),
+                addend => format!("{}{:+#x}", bind.symbol, addend)
+            };
+
+            bind_table.add_row(vec![
+                Cell::new(&bind.segment).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&bind.section).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}", bind.address)).fg(Color::Yellow),
+                Cell::new(symbol).fg(Color::Yellow),
+                Cell::new(&bind.library),
+                Cell::new(&bind.kind).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(bind.flags.join(" | ")).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{bind_table}");
+
+        println!("\n{} rebases, {} binds ({} lazy, {} weak).",
+            dyld_info.rebases.len(),
+            dyld_info.binds.len(),
+            dyld_info.binds.iter().filter(|bind| bind.kind == "lazy").count(),
+            dyld_info.binds.iter().filter(|bind| bind.kind == "weak").count()
+        );
+    }
+
+    fn parse_macho_exports(&self, macho: &MachO)
+    {
+        let exports = match macho.exports()
+        {
+            Ok(exports) => exports,
+
+            Err(err) => {
+                eprintln!("Error - unable to decode the export trie: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        /* Trie addresses are offsets from the mach header, i.e. from the segment mapping file offset 0 (__TEXT) */
+        let image_base: u64 = macho.segments.iter()
+            .find(|segment| segment.fileoff == 0 && segment.filesize != 0)
+            .map_or(0, |segment| segment.vmaddr);
+
+        let records: Vec<MachExportRecord> = exports.iter().map(|export| {
+            let (kind, address, resolver_address, reexport_library, reexport_symbol) = match &export.info
+            {
+                ExportInfo::Regular { address, flags } => match SymbolKind::new(*flags)
+                {
+                    SymbolKind::Absolute => ("absolute", Some(*address), None, None, None),
+                    SymbolKind::ThreadLocal => ("thread-local", Some(image_base + address), None, None, None),
+
+                    _ => ("regular", Some(image_base + address), None, None, None)
+                },
+
+                ExportInfo::Reexport { lib, lib_symbol_name, .. } => (
+                    "reexport", None, None, Some(lib.to_string()), Some(lib_symbol_name.unwrap_or(&export.name).to_string())
+                ),
+
+                ExportInfo::Stub { stub_offset, resolver_offset, .. } => (
+                    "stub-and-resolver", Some(image_base + *stub_offset.as_ref()), Some(image_base + *resolver_offset.as_ref()), None, None
+                )
+            };
+
+            let flags: u64 = match &export.info
+            {
+                ExportInfo::Regular { flags, .. } | ExportInfo::Reexport { flags, .. } | ExportInfo::Stub { flags, .. } => *flags
+            };
+
+            MachExportRecord {
+                name: export.name.clone(),
+                kind: kind.to_string(),
+                weak: flags & EXPORT_SYMBOL_FLAGS_WEAK_DEFINITION != 0,
+                address,
+                resolver_address,
+                reexport_library,
+                reexport_symbol
+            }
+        }).collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_exports", &records);
+            return;
+        }
+
+        println!("\nMach-O Exports =>");
+
+        let mut export_table: Table = self.new_table();
+
+        export_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Kind").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Details").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            let details: String = match (&record.resolver_address, &record.reexport_library, &record.reexport_symbol)
+            {
+                (Some(resolver), _, _) => format!("resolver {:#x}", resolver),
+                (_, Some(library), Some(symbol)) => format!("{symbol} from {library}"),
+
+                _ => String::new()
+            };
+
+            export_table.add_row(vec![
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+
+                match record.weak
+                {
+                    true => Cell::new(format!("{} (weak)", record.kind)).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                    false => Cell::new(&record.kind).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                },
+
+                Cell::new(record.address.map_or(String::new(), |address| format!("{:#x}", address))).fg(Color::Yellow),
+                Cell::new(details)
+            ]);
+        }
+
+        println!("\n{export_table}");
+        println!("\n{} exported symbols.", records.len());
+    }
+
+    fn parse_macho_code_signature(&self, macho: &MachO, data: &[u8])
+    {
+        let signature: Option<&[u8]> = macho.load_commands.iter().find_map(|cmd| match &cmd.command
+        {
+            CommandVariant::CodeSignature(linkedit) => data.get(linkedit.dataoff as usize..(linkedit.dataoff as usize).saturating_add(linkedit.datasize as usize)),
+            _ => None
+        });
+
+        let mut record: MachCodeSignatureRecord = MachCodeSignatureRecord {
+            status: CodeSigningStatus::Unsigned.get_type(),
+            blobs: Vec::new(),
+            identifier: None,
+            hash_type: None,
+            platform: None,
+            flags: Vec::new(),
+            entitlements: None
+        };
+
+        let read_u32 = |blob: &[u8], offset: usize| -> Option<u32> {
+            blob.get(offset..offset + 4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        };
+
+        if let Some(superblob) = signature
+        {
+            if read_u32(superblob, 0) != Some(CSMAGIC_EMBEDDED_SIGNATURE)
+            {
+                eprintln!("Error - LC_CODE_SIGNATURE does not point to an embedded signature SuperBlob!");
+                std::process::exit(-1);
+            }
+
+            let count: usize = read_u32(superblob, 8).unwrap_or(0) as usize;
+            let mut has_cms: bool = false;
+
+            for index in 0..count
+            {
+                let (slot_type, offset) = match (read_u32(superblob, 12 + index * 8), read_u32(superblob, 16 + index * 8))
+                {
+                    (Some(slot_type), Some(offset)) => (slot_type, offset),
+                    _ => break
+                };
+
+                let blob: &[u8] = superblob.get(offset as usize..).unwrap_or(&[]);
+                let magic: u32 = read_u32(blob, 0).unwrap_or(0);
+                let size: u32 = read_u32(blob, 4).unwrap_or(0);
+                let blob: &[u8] = blob.get(..size as usize).unwrap_or(blob);
+
+                match slot_type
+                {
+                    /* The primary CodeDirectory wins over the alternate (SHA-256) ones */
+                    0 | 0x1000..=0x1004 if magic == CSMAGIC_CODEDIRECTORY && (slot_type == 0 || record.identifier.is_none()) => {
+                        let flags: u32 = read_u32(blob, 12).unwrap_or(0);
+
+                        record.identifier = read_u32(blob, 20)
+                            .and_then(|ident| blob.get(ident as usize..))
+                            .map(|bytes| String::from_utf8_lossy(bytes.split(|&b| b == 0).next().unwrap_or(&[])).to_string());
+
+                        record.hash_type = blob.get(37).map(|hash_type| return_code_sign_hash_type(*hash_type));
+                        record.platform = blob.get(38).copied();
+
+                        record.flags = [(CS_ADHOC, "adhoc"), (CS_RUNTIME, "runtime"), (CS_LINKER_SIGNED, "linker-signed")].iter()
+                            .filter(|(flag, _)| flags & flag != 0)
+                            .map(|(_, name)| name.to_string())
+                            .collect();
+                    },
+
+                    5 if magic == CSMAGIC_EMBEDDED_ENTITLEMENTS => {
+                        record.entitlements = blob.get(8..).map(|xml| String::from_utf8_lossy(xml).to_string());
+                    },
+
+                    /* An empty wrapper (just the 8 byte header) is what linker/ad-hoc signing leaves behind */
+                    0x10000 if magic == CSMAGIC_BLOBWRAPPER => has_cms = size > 8,
+
+                    _ => ()
+                }
+
+                record.blobs.push(MachCodeSignBlobRecord {
+                    slot: return_code_sign_slot_type(slot_type),
+                    magic,
+                    offset,
+                    size
+                });
+            }
+
+            record.status = match has_cms
+            {
+                true => CodeSigningStatus::Developer.get_type(),
+                false => CodeSigningStatus::AdHoc.get_type()
+            };
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_code_signature", &record);
+            return;
+        }
+
+        println!("\nMach-O Code Signature =>");
+
+        if signature.is_none()
+        {
+            println!("\nNo LC_CODE_SIGNATURE load command, the binary is {}", record.status);
+            return;
+        }
+
+        let mut blob_table: Table = self.new_table();
+
+        blob_table.set_header(vec![
+                Cell::new("Slot").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Magic").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for blob in &record.blobs
+        {
+            blob_table.add_row(vec![
+                Cell::new(&blob.slot).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#010x}", blob.magic)).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(format!("{:#x}", blob.offset)).fg(Color::Yellow),
+                Cell::new(format!("{} bytes", blob.size)).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{blob_table}");
+
+        if let Some(identifier) = &record.identifier
+        {
+            println!("\nIdentifier : {identifier}");
+            println!("Hash Type  : {}", record.hash_type.as_deref().unwrap_or("unknown"));
+            println!("Platform   : {}", record.platform.unwrap_or(0));
+
+            println!("Flags      : {}", match record.flags.is_empty()
+            {
+                true => String::from("none"),
+                false => record.flags.join(", ")
+            });
+        }
+
+        println!("\nThe binary is {}", record.status);
+
+        if let Some(entitlements) = &record.entitlements
+        {
+            println!("\nEntitlements =>\n\n{}", return_pretty_xml(entitlements));
+        }
+    }
+
+    pub(crate) fn extract_macho_uuid(&self, macho_obj: &MachO)
+    {
+        let uuid: Option<String> = return_macho_uuid(macho_obj);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("uuid", &uuid);
+            return;
+        }
+
+        match uuid
+        {
+            Some(uuid) => println!("\nUUID: {uuid}"),
+            None => println!("\nNo UUID (not linked with -uuid).")
+        }
+    }
+
+    fn parse_macho_segments(&self, macho: &MachO)
+    {
+        let records: Vec<MachSegmentRecord> = macho.segments.iter().map(|segment| MachSegmentRecord {
+            name: segment.name().unwrap_or("").to_string(),
+            vmaddr: segment.vmaddr,
+            vmsize: segment.vmsize,
+            fileoff: segment.fileoff,
+            filesize: segment.filesize,
+            maxprot: return_macho_protection(segment.maxprot),
+            initprot: return_macho_protection(segment.initprot),
+            nsects: segment.nsects,
+
+            sections: segment.sections().unwrap_or_default().iter().map(|(section, _)| MachSectionRecord {
+                name: section.name().unwrap_or("").to_string(),
+                address: section.addr,
+                size: section.size,
+                offset: section.offset,
+                align: section.align,
+                section_type: return_macho_section_type(section.flags),
+                attributes: return_macho_section_attributes(section.flags)
+            }).collect()
+        }).collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("macho_segments", &records);
+            return;
+        }
+
+        println!("\nMach-O Segments =>");
+
+        let mut segment_table: Table = self.new_table();
+
+        segment_table.set_header(vec![
+                Cell::new("Segment").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("VM Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("VM Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("File Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("File Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Max/Init Prot").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Sections").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            segment_table.add_row(vec![
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#x}", record.vmaddr)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", record.vmsize)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", record.fileoff)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", record.filesize)).fg(Color::Yellow),
+                Cell::new(format!("{}/{}", record.maxprot, record.initprot)).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(record.nsects).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{segment_table}");
+
+        for record in records.iter().filter(|record| !record.sections.is_empty())
+        {
+            let mut section_table: Table = self.new_table();
+
+            section_table.set_header(vec![
+                    Cell::new(format!("{} sections", record.name)).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Address").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Align").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Attributes").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for section in &record.sections
+            {
+                section_table.add_row(vec![
+                    Cell::new(&section.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(format!("{:#x}", section.address)).fg(Color::Yellow),
+                    Cell::new(format!("{} bytes", section.size)).fg(Color::Yellow),
+                    Cell::new(format!("{:#x}", section.offset)).fg(Color::Yellow),
+                    Cell::new(format!("2^{}", section.align)).fg(Color::Yellow),
+                    Cell::new(&section.section_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                    Cell::new(section.attributes.join(" | "))
+                ]);
+            }
+
+            println!("\n{section_table}");
+        }
+
+        println!("\n{} segments, {} sections.", records.len(), records.iter().map(|record| record.sections.len()).sum::<usize>());
+    }
+}
+
+
+/* Fixups decoded per LC_DYLD_INFO opcode stream before giving up on it, real binaries stay far below */
+const MACH_DYLD_MAX_FIXUPS: usize = 1 << 20;
+
+/* Code signing blob magics and CodeDirectory flags (xnu osfmk/kern/cs_blobs.h), all big endian */
+const CSMAGIC_EMBEDDED_SIGNATURE: u32 = 0xfade0cc0;
+const CSMAGIC_CODEDIRECTORY: u32 = 0xfade0c02;
+const CSMAGIC_EMBEDDED_ENTITLEMENTS: u32 = 0xfade7171;
+const CSMAGIC_BLOBWRAPPER: u32 = 0xfade0b01;
+const CS_ADHOC: u32 = 0x0000_0002;
+const CS_RUNTIME: u32 = 0x0001_0000;
+const CS_LINKER_SIGNED: u32 = 0x0002_0000;
+
+enum CodeSigningStatus
+{
+    Unsigned,           /* no LC_CODE_SIGNATURE load command */
+    AdHoc,              /* CodeDirectory only (CS_ADHOC), no CMS signature */
+    Developer           /* CMS blob carrying a certificate chain */
+}
+
+impl CodeSigningStatus
+{
+    fn get_type(&self) -> String
+    {
+        match *self
+        {
+            CodeSigningStatus::Unsigned => String::from("unsigned"),
+            CodeSigningStatus::AdHoc => String::from("ad-hoc signed"),
+            CodeSigningStatus::Developer => String::from("developer signed")
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MachSymbolRecord
+{
+    name: String,
+    value: u64,
+    section_index: usize,
+    /* "__SEGMENT,__section" resolved from the 1-based n_sect ordinal, None for NO_SECT */
+    section: Option<String>,
+    symbol_type: String,
+    external: bool,
+    import: bool
+}
+
+#[derive(Serialize)]
+struct MachRebaseRecord
+{
+    segment: String,
+    section: String,
+    address: u64,
+    rebase_type: String
+}
+
+#[derive(Serialize)]
+struct MachBindRecord
+{
+    segment: String,
+    section: String,
+    address: u64,
+    symbol: String,
+    library: String,
+    /* Which opcode stream bound it: regular, weak or lazy */
+    kind: String,
+    bind_type: String,
+    addend: i64,
+    /* BIND_SYMBOL_FLAGS_WEAK_IMPORT / BIND_SYMBOL_FLAGS_NON_WEAK_DEFINITION */
+    flags: Vec<String>
+}
+
+#[derive(Serialize)]
+struct MachDyldInfoRecord
+{
+    present: bool,
+    rebases: Vec<MachRebaseRecord>,
+    binds: Vec<MachBindRecord>
+}
+
+#[derive(Serialize)]
+struct MachImportRecord
+{
+    name: String,
+    dylib: String,
+    dylib_version: Option<String>,
+    weak: bool,
+    bind_type: String
+}
+
+#[derive(Serialize)]
+struct MachExportRecord
+{
+    name: String,
+    kind: String,
+    weak: bool,
+    /* Symbol address, or the stub address of a stub-and-resolver export (None for reexports) */
+    address: Option<u64>,
+    resolver_address: Option<u64>,
+    reexport_library: Option<String>,
+    reexport_symbol: Option<String>
+}
+
+#[derive(Serialize)]
+struct MachSectionRecord
+{
+    name: String,
+    address: u64,
+    size: u64,
+    offset: u32,
+    /* Power of two exponent, as stored in the section header */
+    align: u32,
+    section_type: String,
+    attributes: Vec<String>
+}
+
+#[derive(Serialize)]
+struct MachSegmentRecord
+{
+    name: String,
+    vmaddr: u64,
+    vmsize: u64,
+    fileoff: u64,
+    filesize: u64,
+    maxprot: String,
+    initprot: String,
+    nsects: u32,
+    sections: Vec<MachSectionRecord>
+}
+
+#[derive(Serialize)]
+struct MachCodeSignBlobRecord
+{
+    slot: String,
+    magic: u32,
+    offset: u32,
+    size: u32
+}
+
+#[derive(Serialize)]
+struct MachCodeSignatureRecord
+{
+    status: String,
+    blobs: Vec<MachCodeSignBlobRecord>,
+    /* CodeDirectory fields, None when the slot is missing or truncated */
+    identifier: Option<String>,
+    hash_type: Option<String>,
+    platform: Option<u8>,
+    flags: Vec<String>,
+    entitlements: Option<String>
+}
+
+
+/* VM_PROT_READ/WRITE/EXECUTE as an rwx string */
+fn return_macho_protection(prot: u32) -> String
+{
+    format!("{}{}{}",
+        match prot & 0x1 != 0 { true => "r", false => "-" },
+        match prot & 0x2 != 0 { true => "w", false => "-" },
+        match prot & 0x4 != 0 { true => "x", false => "-" }
+    )
+}
+
+fn return_macho_section_type(flags: u32) -> String
+{
+    match flags & mach_constants::SECTION_TYPE
+    {
+        mach_constants::S_REGULAR => String::from("S_REGULAR"),
+        mach_constants::S_ZEROFILL => String::from("S_ZEROFILL"),
+        mach_constants::S_CSTRING_LITERALS => String::from("S_CSTRING_LITERALS"),
+        mach_constants::S_4BYTE_LITERALS => String::from("S_4BYTE_LITERALS"),
+        mach_constants::S_8BYTE_LITERALS => String::from("S_8BYTE_LITERALS"),
+        mach_constants::S_LITERAL_POINTERS => String::from("S_LITERAL_POINTERS"),
+        mach_constants::S_NON_LAZY_SYMBOL_POINTERS => String::from("S_NON_LAZY_SYMBOL_POINTERS"),
+        mach_constants::S_LAZY_SYMBOL_POINTERS => String::from("S_LAZY_SYMBOL_POINTERS"),
+        mach_constants::S_SYMBOL_STUBS => String::from("S_SYMBOL_STUBS"),
+        mach_constants::S_MOD_INIT_FUNC_POINTERS => String::from("S_MOD_INIT_FUNC_POINTERS"),
+        mach_constants::S_MOD_TERM_FUNC_POINTERS => String::from("S_MOD_TERM_FUNC_POINTERS"),
+        mach_constants::S_COALESCED => String::from("S_COALESCED"),
+        mach_constants::S_GB_ZEROFILL => String::from("S_GB_ZEROFILL"),
+        mach_constants::S_INTERPOSING => String::from("S_INTERPOSING"),
+        mach_constants::S_16BYTE_LITERALS => String::from("S_16BYTE_LITERALS"),
+        mach_constants::S_DTRACE_DOF => String::from("S_DTRACE_DOF"),
+        mach_constants::S_LAZY_DYLIB_SYMBOL_POINTERS => String::from("S_LAZY_DYLIB_SYMBOL_POINTERS"),
+        mach_constants::S_THREAD_LOCAL_REGULAR => String::from("S_THREAD_LOCAL_REGULAR"),
+        mach_constants::S_THREAD_LOCAL_ZEROFILL => String::from("S_THREAD_LOCAL_ZEROFILL"),
+        mach_constants::S_THREAD_LOCAL_VARIABLES => String::from("S_THREAD_LOCAL_VARIABLES"),
+        mach_constants::S_THREAD_LOCAL_VARIABLE_POINTERS => String::from("S_THREAD_LOCAL_VARIABLE_POINTERS"),
+        mach_constants::S_THREAD_LOCAL_INIT_FUNCTION_POINTERS => String::from("S_THREAD_LOCAL_INIT_FUNCTION_POINTERS"),
+
+        section_type => format!("S_UNKNOWN({:#x})", section_type)
+    }
+}
+
+fn return_macho_section_attributes(flags: u32) -> Vec<String>
+{
+    [
+        (mach_constants::S_ATTR_PURE_INSTRUCTIONS, "PURE_INSTRUCTIONS"),
+        (mach_constants::S_ATTR_NO_TOC, "NO_TOC"),
+        (mach_constants::S_ATTR_STRIP_STATIC_SYMS, "STRIP_STATIC_SYMS"),
+        (mach_constants::S_ATTR_NO_DEAD_STRIP, "NO_DEAD_STRIP"),
+        (mach_constants::S_ATTR_LIVE_SUPPORT, "LIVE_SUPPORT"),
+        (mach_constants::S_ATTR_SELF_MODIFYING_CODE, "SELF_MODIFYING_CODE"),
+        (mach_constants::S_ATTR_DEBUG, "DEBUG"),
+        (mach_constants::S_ATTR_SOME_INSTRUCTIONS, "SOME_INSTRUCTIONS"),
+        (mach_constants::S_ATTR_EXT_RELOC, "EXT_RELOC"),
+        (mach_constants::S_ATTR_LOC_RELOC, "LOC_RELOC")
+    ].iter()
+        .filter(|(attribute, _)| flags & attribute != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+/* (segment, section) names containing the address, "?" when no section covers it */
+fn return_macho_location(macho: &MachO, address: u64) -> (String, String)
+{
+    match macho.segments.iter().find(|segment| segment.vmaddr <= address && address - segment.vmaddr < segment.vmsize)
+    {
+        Some(segment) => {
+            let section: String = segment.sections().unwrap_or_default().iter()
+                .find(|(section, _)| section.addr <= address && address - section.addr < section.size)
+                .map_or(String::from("?"), |(section, _)| section.name().unwrap_or("?").to_string());
+
+            (segment.name().unwrap_or("?").to_string(), section)
+        },
+
+        None => (String::from("?"), String::from("?"))
+    }
+}
+
+
+fn read_uleb128(data: &[u8], offset: &mut usize) -> u64
+{
+    let mut value: u64 = 0;
+    let mut shift: u32 = 0;
+
+    while let Some(&byte) = data.get(*offset)
+    {
+        *offset += 1;
+
+        if shift < 64
+        {
+            value |= ((byte & 0x7f) as u64) << shift;
+        }
+
+        shift += 7;
+
+        if byte & 0x80 == 0
+        {
+            break;
+        }
+    }
+
+    value
+}
+
+
+fn read_sleb128(data: &[u8], offset: &mut usize) -> i64
+{
+    let mut value: i64 = 0;
+    let mut shift: u32 = 0;
+    let mut byte: u8 = 0;
+
+    while let Some(&next) = data.get(*offset)
+    {
+        *offset += 1;
+        byte = next;
+
+        if shift < 64
+        {
+            value |= ((byte & 0x7f) as i64) << shift;
+        }
+
+        shift += 7;
+
+        if byte & 0x80 == 0
+        {
+            break;
+        }
+    }
+
+    /* Sign extend from the last byte's bit 6 */
+    if shift < 64 && byte & 0x40 != 0
+    {
+        value |= -1i64 << shift;
+    }
+
+    value
+}
+
+
+/* REBASE_TYPE_* / BIND_TYPE_* share their values */
+fn return_macho_fixup_type(fixup_type: u8) -> String
+{
+    match fixup_type
+    {
+        1 => String::from("pointer"),
+        2 => String::from("text-abs32"),
+        3 => String::from("text-pcrel32"),
+
+        _ => format!("unknown ({fixup_type})")
+    }
+}
+
+
+/* Interprets the LC_DYLD_INFO(_ONLY) rebase, bind, weak bind and lazy bind opcode streams (<mach-o/loader.h>) */
+fn return_macho_dyld_info(macho: &MachO, data: &[u8]) -> MachDyldInfoRecord
+{
+    let info = macho.load_commands.iter().find_map(|cmd| match &cmd.command
+    {
+        CommandVariant::DyldInfo(info) | CommandVariant::DyldInfoOnly(info) => Some(info),
+        _ => None
+    });
+
+    let mut record: MachDyldInfoRecord = MachDyldInfoRecord { present: info.is_some(), rebases: Vec::new(), binds: Vec::new() };
+
+    let info = match info
+    {
+        Some(info) => info,
+        None => return record
+    };
+
+    let pointer_size: u64 = match macho.is_64 { true => 8, false => 4 };
+    let stream = |offset: u32, size: u32| -> &[u8] {
+        (offset as usize).checked_add(size as usize).and_then(|end| data.get(offset as usize..end)).unwrap_or(&[])
+    };
+
+    /* [vmaddr, vmaddr + vmsize) of the segment selected by a SET_SEGMENT_AND_OFFSET opcode */
+    let segment_range = |index: u64| -> (u64, u64) {
+        macho.segments.get(index as usize).map_or((0, 0), |segment| (segment.vmaddr, segment.vmaddr.saturating_add(segment.vmsize)))
+    };
+
+    /* Opcode counts are untrusted, a repeat stops once it walks out of its segment or the stream hits the fixup cap */
+    let in_bounds = |address: u64, (start, end): (u64, u64), fixups: usize| -> bool {
+        start <= address && address < end && fixups < MACH_DYLD_MAX_FIXUPS
+    };
+
+    /* Rebase opcodes */
+    let opcodes: &[u8] = stream(info.rebase_off, info.rebase_size);
+    let (mut position, mut address, mut rebase_type): (usize, u64, u8) = (0, 0, 1);
+    let mut segment: (u64, u64) = (0, 0);
+
+    let rebase = |address: u64, rebase_type: u8, rebases: &mut Vec<MachRebaseRecord>| {
+        let (segment, section): (String, String) = return_macho_location(macho, address);
+        rebases.push(MachRebaseRecord { segment, section, address, rebase_type: return_macho_fixup_type(rebase_type) });
+    };
+
+    while let Some(&byte) = opcodes.get(position)
+    {
+        position += 1;
+
+        let immediate: u8 = byte & 0x0f;
+
+        match byte & 0xf0
+        {
+            0x00 => break,
+            0x10 => rebase_type = immediate,
+            0x20 => {
+                segment = segment_range(immediate as u64);
+                address = segment.0.wrapping_add(read_uleb128(opcodes, &mut position));
+            },
+
+            0x30 => address = address.wrapping_add(read_uleb128(opcodes, &mut position)),
+            0x40 => address = address.wrapping_add(immediate as u64 * pointer_size),
+
+            0x50 | 0x60 => {
+                let count: u64 = match byte & 0xf0 { 0x50 => immediate as u64, _ => read_uleb128(opcodes, &mut position) };
+
+                for _ in 0..count
+                {
+                    if !in_bounds(address, segment, record.rebases.len())
+                    {
+                        break;
+                    }
+
+                    rebase(address, rebase_type, &mut record.rebases);
+                    address = address.wrapping_add(pointer_size);
+                }
+            },
+
+            0x70 => {
+                if in_bounds(address, segment, record.rebases.len())
+                {
+                    rebase(address, rebase_type, &mut record.rebases);
+                }
+
+                address = address.wrapping_add(read_uleb128(opcodes, &mut position)).wrapping_add(pointer_size);
+            },
+
+            0x80 => {
+                let (count, skip): (u64, u64) = (read_uleb128(opcodes, &mut position), read_uleb128(opcodes, &mut position));
+
+                for _ in 0..count
+                {
+                    if !in_bounds(address, segment, record.rebases.len())
+                    {
+                        break;
+                    }
+
+                    rebase(address, rebase_type, &mut record.rebases);
+                    address = address.wrapping_add(skip).wrapping_add(pointer_size);
+                }
+            },
+
+            _ => break
+        }
+    }
+
+    /* Bind opcodes, the lazy stream separates its entries with BIND_OPCODE_DONE instead of ending there */
+    for (kind, offset, size) in [("regular", info.bind_off, info.bind_size), ("weak", info.weak_bind_off, info.weak_bind_size), ("lazy", info.lazy_bind_off, info.lazy_bind_size)]
+    {
+        let opcodes: &[u8] = stream(offset, size);
+        let (mut position, mut address, mut bind_type, mut addend): (usize, u64, u8, i64) = (0, 0, 1, 0);
+        let (mut library, mut symbol, mut flags): (String, String, u8) = (String::new(), String::new(), 0);
+        let mut segment: (u64, u64) = (0, 0);
+        let first_bind: usize = record.binds.len();
+
+        let library_name = |ordinal: i64| -> String {
+            match ordinal
+            {
+                0 => String::from("self"),
+                -1 => String::from("main executable"),
+                -2 => String::from("flat lookup"),
+                -3 => String::from("weak lookup"),
+
+                ordinal => macho.libs.get(ordinal as usize).map_or(format!("ordinal {ordinal}"), |lib| lib.to_string())
+            }
+        };
+
+        let bind = |address: u64, binds: &mut Vec<MachBindRecord>, library: &str, symbol: &str, bind_type: u8, addend: i64, flags: u8| {
+            let (segment, section): (String, String) = return_macho_location(macho, address);
+
+            binds.push(MachBindRecord {
+                segment,
+                section,
+                address,
+                symbol: symbol.to_string(),
+                library: match kind { "weak" => String::from("weak lookup"), _ => library.to_string() },
+                kind: kind.to_string(),
+                bind_type: return_macho_fixup_type(bind_type),
+                addend,
+                flags: [(0x1, "WEAK_IMPORT"), (0x8, "NON_WEAK_DEFINITION")].iter()
+                    .filter(|(flag, _)| flags & flag != 0)
+                    .map(|(_, name)| name.to_string())
+                    .collect()
+            });
+        };
+
+        while let Some(&byte) = opcodes.get(position)
+        {
+            position += 1;
+
+            let immediate: u8 = byte & 0x0f;
+
+            match byte & 0xf0
+            {
+                0x00 if kind == "lazy" => (),
+                0x00 => break,
+                0x10 => library = library_name(immediate as i64),
+                0x20 => library = library_name(read_uleb128(opcodes, &mut position) as i64),
+
+                /* Special ordinals are small negative numbers stored sign extended in the immediate */
+                0x30 => library = library_name(match immediate { 0 => 0, immediate => (immediate | 0xf0) as i8 as i64 }),
+
+                0x40 => {
+                    let name: &[u8] = opcodes.get(position..).unwrap_or(&[]).split(|&b| b == 0).next().unwrap_or(&[]);
+
+                    symbol = String::from_utf8_lossy(name).to_string();
+                    flags = immediate;
+                    position += name.len() + 1;
+                },
+
+                0x50 => bind_type = immediate,
+                0x60 => addend = read_sleb128(opcodes, &mut position),
+                0x70 => {
+                    segment = segment_range(immediate as u64);
+                    address = segment.0.wrapping_add(read_uleb128(opcodes, &mut position));
+                },
+
+                0x80 => address = address.wrapping_add(read_uleb128(opcodes, &mut position)),
+
+                0x90..=0xb0 => {
+                    if in_bounds(address, segment, record.binds.len() - first_bind)
+                    {
+                        bind(address, &mut record.binds, &library, &symbol, bind_type, addend, flags);
+                    }
+
+                    address = address.wrapping_add(match byte & 0xf0
+                    {
+                        0x90 => pointer_size,
+                        0xa0 => read_uleb128(opcodes, &mut position).wrapping_add(pointer_size),
+                        _ => immediate as u64 * pointer_size + pointer_size
+                    });
+                },
+
+                0xc0 => {
+                    let (count, skip): (u64, u64) = (read_uleb128(opcodes, &mut position), read_uleb128(opcodes, &mut position));
+
+                    for _ in 0..count
+                    {
+                        if !in_bounds(address, segment, record.binds.len() - first_bind)
+                        {
+                            break;
+                        }
+
+                        bind(address, &mut record.binds, &library, &symbol, bind_type, addend, flags);
+                        address = address.wrapping_add(skip).wrapping_add(pointer_size);
+                    }
+                },
+
+                /* BIND_OPCODE_THREADED belongs to the arm64e chained format, not decoded here */
+                _ => break
+            }
+        }
+    }
+
+    record
+}
+
+
+/* LC_UUID as the canonical 8-4-4-4-12 string, dwarfdump --uuid prints the same (uppercase) */
+fn return_macho_uuid(macho: &MachO) -> Option<String>
+{
+    macho.load_commands.iter().find_map(|load_command| match &load_command.command
+    {
+        CommandVariant::Uuid(uuid_command) => {
+            let hex: String = uuid_command.uuid.iter().map(|b| format!("{:02X}", b)).collect();
+            Some(format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32]))
+        },
+
+        _ => None
+    })
+}
+
+
+fn return_macho_symbol_type(nlist: &Nlist) -> String
+{
+    match nlist.is_stab()
+    {
+        true => format!("N_STAB ({:#04x})", nlist.n_type),
+        false => nlist.type_str().to_string()
+    }
+}
+
+/* dylib versions are packed as xxxx.yy.zz (16/8/8 bits) */
+fn return_dylib_version(version: u32) -> String
+{
+    format!("{}.{}.{}", version >> 16, (version >> 8) & 0xff, version & 0xff)
+}
+
+/* SuperBlob index slot types (CSSLOT_*) */
+fn return_code_sign_slot_type(slot_type: u32) -> String
+{
+    match slot_type
+    {
+        0 => String::from("Code Directory"),
+        1 => String::from("Info.plist"),
+        2 => String::from("Requirements"),
+        3 => String::from("Resource Directory"),
+        4 => String::from("Application"),
+        5 => String::from("Entitlements"),
+        7 => String::from("DER Entitlements"),
+        0x1000..=0x1004 => format!("Alternate Code Directory {}", slot_type - 0x1000),
+        0x10000 => String::from("CMS Signature"),
+        _ => format!("Unknown ({:#x})", slot_type)
+    }
+}
+
+fn return_code_sign_hash_type(hash_type: u8) -> String
+{
+    match hash_type
+    {
+        1 => String::from("SHA-1"),
+        2 => String::from("SHA-256"),
+        3 => String::from("SHA-256 (truncated)"),
+        4 => String::from("SHA-384"),
+        _ => format!("unknown ({hash_type})")
+    }
+}
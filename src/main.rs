@@ -5,14 +5,30 @@
 */
 
 use std::fs;
-use std::env;
-use std::path::Path;
-use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::borrow::Cow;
+
+use sha2::{Digest, Sha256};
+use regex::{Regex, RegexBuilder};
+use wildmatch::WildMatch;
+use std::path::{Path, PathBuf};
+use std::cell::{Cell as StdCell, RefCell};
+use std::sync::OnceLock;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/* Library half of the crate (src/lib.rs), the structured analysis the CLI tables are built from */
+use binarymagic::{analyze_elf, ElfAnalysis};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
 
 use unindent::Unindent;
 
+use clap::{Arg, ArgGroup, ArgMatches, Command, CommandFactory, FromArgMatches};
+use clap::parser::ValueSource;
+
 use comfy_table::*;
-use comfy_table::presets::UTF8_BORDERS_ONLY;
+use comfy_table::presets::{UTF8_BORDERS_ONLY, UTF8_FULL, ASCII_FULL, ASCII_NO_BORDERS, ASCII_MARKDOWN, NOTHING};
 
 use goblin::Object;
 use goblin::elf::Elf;
@@ -20,15 +36,97 @@ use goblin::elf::header::*;
 use goblin::strtab::Strtab;
 use goblin::container::Endian;
 use goblin::elf64::header::SIZEOF_IDENT;
+use goblin::elf::program_header::PT_GNU_RELRO;
+use goblin::elf::dynamic::{self as elf_dynamic, DT_FLAGS, DT_FLAGS_1, DT_BIND_NOW, DF_BIND_NOW, DF_1_NOW, DT_TEXTREL, DF_TEXTREL};
+use goblin::elf::dynamic::{DT_NEEDED, DT_SONAME, DT_RPATH, DT_RUNPATH};
+use goblin::elf::program_header::{self as program_header, ProgramHeader, PT_LOAD};
+use goblin::container::{Container, Ctx};
+use goblin::elf::sym::{self, Sym, Symtab, STT_FUNC, STT_OBJECT, STT_GNU_IFUNC, STB_GLOBAL, STB_WEAK};
+use goblin::elf::reloc::{self, RelocSection};
+use goblin::elf::symver::VER_FLG_BASE;
+use goblin::elf::compression_header::ELFCOMPRESS_ZLIB;
+use goblin::elf::note::{NT_GNU_ABI_TAG, NT_GNU_PROPERTY_TYPE_0, NT_PRSTATUS, NT_PRPSINFO, NT_SIGINFO, NT_FILE, ELF_NOTE_OS_LINUX, ELF_NOTE_OS_GNU, ELF_NOTE_OS_SOLARIS2, ELF_NOTE_OS_FREEBSD};
+
+use goblin::pe::PE;
+use goblin::mach::{Mach, MachO};
+use goblin::mach::symbols::{Nlist, N_UNDF};
+use goblin::mach::load_command::CommandVariant;
+use goblin::mach::exports::{ExportInfo, SymbolKind, EXPORT_SYMBOL_FLAGS_WEAK_DEFINITION};
+use goblin::mach::constants as mach_constants;
+use goblin::pe::utils::find_offset;
+use goblin::pe::options::ParseOptions;
+use goblin::pe::debug::IMAGE_DEBUG_TYPE_CODEVIEW;
+use goblin::pe::header::{COFF_MACHINE_ARM, COFF_MACHINE_ARMNT};
+
+use gimli::{BaseAddresses, CieOrFde, Dwarf, DwarfFileType, EhFrame, EndianSlice, RunTimeEndian, SectionId, UnwindSection};
+
+use wasmparser::{Parser, Payload, TypeRef, ExternalKind};
+
+use capstone::prelude::*;
+use capstone::Insn;
+
+use cms::content_info::ContentInfo;
+use cms::signed_data::{SignedData, SignerIdentifier};
+use cms::cert::CertificateChoices;
+use x509_cert::Certificate;
+use x509_cert::der::{Decode, SliceReader};
+use x509_cert::der::oid::ObjectIdentifier;
 
 /* Import all pre-defined elf section header flag attribute values */
 use goblin::elf64::section_header::*;
 
-/* Custom section header flags */
-const SHF_WRITE_ALLOC: u32 = SHF_WRITE | SHF_ALLOC;
-const SHF_ASM_INST_ALLOC: u32 = SHF_ALLOC | SHF_EXECINSTR;
+/* Analysis methods per target format, plus the shared output plumbing */
+mod elf;
+mod macho;
+mod output;
+mod pe;
+mod wasm;
+
+use wasm::{return_wasm_module, WasmModule};
+
+
+/* WebAssembly modules start with "\0asm", goblin reports them as Object::Unknown */
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+
+
+/* --fmt-table-theme names and the comfy_table preset each one selects */
+const TABLE_THEMES: [(&str, &str); 6] = [
+    ("utf8-borders", UTF8_BORDERS_ONLY),
+    ("utf8-full", UTF8_FULL),
+    ("ascii-full", ASCII_FULL),
+    ("ascii-no-borders", ASCII_NO_BORDERS),
+    ("ascii-markdown", ASCII_MARKDOWN),
+    ("minimal", NOTHING)
+];
+
+/* clap help sections, --fmt-* and the other output options are global and never dispatched */
+const ANALYSIS_HEADING: &str = "Analysis";
+const CHECKS_HEADING: &str = "Checks (exit code)";
+const COMPARISON_HEADING: &str = "Comparison";
+const OUTPUT_HEADING: &str = "Output formats";
+
+/* Options that only modify other analysis methods and are never dispatched themselves */
+const MODIFIER_PARAMS: [&str; 10] = ["--out", "--max-depth", "--hash-section", "--verbose", "--case-sensitive", "--show-padding", "--reloc-ns", "--elf-type-filter", "--demangle", "--depth"];
+
+
+/* Plain (non table) status lines of the exit code checks */
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/* Instructions walked back from a gadget terminator unless --max-depth says otherwise, and the most it may ask for */
+const DEFAULT_GADGET_DEPTH: usize = 5;
+const MAX_GADGET_DEPTH: usize = 64;
+
+/* Cost of one symbolic relocation (cache warm symbol lookup) unless --reloc-ns says otherwise, a full millisecond is the ceiling */
+const DEFAULT_RELOC_NS: u64 = 50;
+const MAX_RELOC_NS: u64 = 1_000_000;
+
+
+/* Instructions --show-entry-point-context disassembles when --depth is not given, and the most it may ask for */
+const DEFAULT_ENTRY_DEPTH: usize = 20;
+const MAX_ENTRY_DEPTH: usize = 4096;
 
-const SHF_UNDEFINED: u32 = 0; const SHF_UNDEFINED_STR: &str = "SHF_UNDEFINED";
 
 /* Terminal styling options */
 const OMEGA: &str = "\u{03a9}";
@@ -36,337 +134,1037 @@ const CHECK: &str = "\u{2713}";
 const CROSS: &str = "\u{2717}";
 
 const PARAM_DATA_LIMIT: usize = 1;
-const ELF_MAGIC_LEN: usize = 4;
 
-const SINGULAR_CALLER: bool = true;
-const MULTI_CALLER: bool = !SINGULAR_CALLER;
 
-enum ElfSectionType
+enum ProgramArgumentMethod
+{
+    Sections,
+    DynamicSymbols,
+    DynamicLibraries,
+    Security,
+    SymTabAnnotated,
+    PeDebug,
+    SizeBreakdown,
+    ArchInfo,
+    CheckStripped,
+    CheckNotStripped,
+    Dynamic,
+    ImportsCount,
+    ExportsCount,
+    SectionContent,
+    CheckPie,
+    DumpSectionRaw,
+    HashStats,
+    PerFileSymbols,
+    PeClr,
+    RebaseSections,
+    FindGadgets,
+    PeCodeSign,
+    SectionGaps,
+    InterpElf,
+    PltSize,
+    KernelModule,
+    ReconstructImports,
+    HashCompare,
+    CompressedSections,
+    MachSymbols,
+    CountRelocations,
+    PeResources,
+    CheckNx,
+    FindString,
+    MachImports,
+    MachExports,
+    TimelineJson,
+    ListSectionTypes,
+    SegmentSections,
+    CheckFortify,
+    StripDetectLevel,
+    MachCodeSign,
+    PeImportsFilter,
+    PeCfg,
+    AbiTag,
+    CheckRelro,
+    CrossSectionRefs,
+    UnresolvedWeak,
+    PeEntropy,
+    ElfClassCheck,
+    RelocStats,
+    MachSegments,
+    HashTableStats,
+    CountPerType,
+    CheckCanary,
+    PeLoadConfig,
+    ArchCompat,
+    Ifunc,
+    ShowAbsentFlags,
+    LoadOrder,
+    CheckTextrel,
+    MachUuid,
+    PeBaseRelocs,
+    CheckBindnow,
+    ExecSections,
+    WritableSections,
+    XrefString,
+    StrippedFunctions,
+    EmitIdaScript,
+    EmitGhidraScript,
+    ShowEntryPointContext,
+    Core,
+    ExportSymbols,
+    CheckSafestack,
+    MachDyldInfo,
+    PeLoadTime,
+    PatchByte,
+    CheckShadowStack,
+    FindFormatStrings,
+    UniqueImports,
+    SectionTypeExtensions,
+    EntryToMain,
+    OverlapCheck,
+    AuditImports
+}
+
+
+enum SymbolSortKey
+{
+    Index,                          /* ELF symbol table order (readelf), the --sym-tab-annotated default */
+    Name,
+    Address { zero_last: bool },    /* --zero-last moves FILE/ABS/undefined symbols at 0 to the end */
+    Size,
+    Binding,
+    Type
+}
+
+enum NumberFormat
 {
-    ShtNull,            /* 0 = marks the section header as inactive */
-    ShtProgBits,        /* 1 = holds information defined by the program, whose format and meaning are determined solely by the program */
-    ShtSymTab,          /* 2 = hold a symbol table */
-    ShtStrTab,          /* 3 = section holds a string table. An object file may have multiple string table sections */
-    ShtRela,            /* 4 = section holds relocation entries with explicit addends, such as type Elf32_Rela for the 32-bit class of object files or type Elf64_Rela for the 64-bit class of object files */
-    ShtHash,            /* 5 = section holds a symbol hash table. Currently, an object file may have only one hash table*/
-    ShtDynamic,         /* 6 = section holds information for dynamic linking. Currently, an object file may have only one dynamic section */
-    ShtNote,            /* 7 = section holds information that marks the file in some way */
-    ShtNoBits,          /* 8 = section of this type occupies no space in the file but otherwise resembles ShtProgBits */
-    ShtRel,             /* 9 = section holds relocation entries without explicit addends. An object file may have multiple relocation sections */
-    ShtShLib,           /* 10 = section type is reserved but has unspecified semantics */
-    ShtDynSym,          /* 11 = hold a symbol table */
-    ShtInitArray,       /* 14 = section contains an array of pointers to initialization functions */
-    ShtFiniArray,       /* 15 = section contains an array of pointers to termination functions */
-    ShtPreInitArray,    /* 16 = section contains an array of pointers to functions that are invoked before all other initialization functions */
-    ShtGroup,           /* 17 = section defines a section group. A section group is a set of sections that are related and that must be treated specially by the linker */
-    ShtSymTabShndx,    /* 18 = section is associated with a section of type ShtSymTab and is required if any of the section header indexes referenced by that symbol table contain the escape value SHN_XINDEX */
-
-    // ShtLoos,            /* 0x60000000 = values in this inclusive range are reserved for operating system-specific semantics */
-    // ShtHios,            /* 0x6fffffff = values in this inclusive range are reserved for operating system-specific semantics */
-    // ShtLoProc,          /* 0x70000000 = values in this inclusive range are reserved for processor-specific semantics */
-    // ShtHiProc,          /* 0x7fffffff = values in this inclusive range are reserved for processor-specific semantics */    
-    // ShtLoUser,          /* 0x80000000 = this value specifies the lower bound of the range of indexes reserved for application programs */
-    // ShtHiUser           /* 0xffffffff = this value specifies the upper bound of the range of indexes reserved for application programs */
+    Decimal,            /* default, plain integers */
+    Hex                 /* --hex-offsets / --file-offsets-hex, 0x prefixed */
 }
 
-impl ElfSectionType 
+impl NumberFormat
 {
-    fn get_type(self: &Self) -> String
+    fn format(&self, value: u64) -> String
     {
         match *self
         {
-            ElfSectionType::ShtNull => "SHT_NULL".to_string(),
-            ElfSectionType::ShtProgBits => "SHT_PROGBITS".to_string(),
-            ElfSectionType::ShtSymTab => "SHT_SYMTAB".to_string(),
-            ElfSectionType::ShtStrTab => "SHT_STRTAB".to_string(),
-            ElfSectionType::ShtRela => "SHT_RELA".to_string(),
-            ElfSectionType::ShtHash => "SHT_HASH".to_string(),
-            ElfSectionType::ShtDynamic => "SHT_DYNAMIC".to_string(),
-            ElfSectionType::ShtNote => "SHT_NOTE".to_string(),
-            ElfSectionType::ShtNoBits => "SHT_NOBITS".to_string(),
-            ElfSectionType::ShtRel => "SHT_REL".to_string(),
-            ElfSectionType::ShtShLib => "SHT_SHLIB".to_string(),
-            ElfSectionType::ShtDynSym => "SHT_DYNSYM".to_string(),
-            ElfSectionType::ShtInitArray => "SHT_INIT_ARRAY".to_string(),
-            ElfSectionType::ShtFiniArray => "SHT_FINI_ARRAY".to_string(),
-            ElfSectionType::ShtPreInitArray => "SHT_PREINIT_ARRAY".to_string(),
-            ElfSectionType::ShtGroup => "SHT_GROUP".to_string(),
-            ElfSectionType::ShtSymTabShndx => "SHT_SYMTAB_SHNDX".to_string(),
+            NumberFormat::Decimal => value.to_string(),
+            NumberFormat::Hex => format!("{:#x}", value)
         }
     }
 }
 
-enum ElfObjectType
+enum OutputFormat
 {
-    EtNone,
-    EtRel,
-    EtExec,
-    EtDyn,
-    EtCore
+    Table,              /* default, human readable comfy_table output */
+    Yaml,               /* --fmt-yaml, single YAML document keyed by analysis method */
+    Json,               /* --json, single JSON object keyed by analysis method */
+    Ndjson              /* --machine-readable/--ndjson, one JSON object per record, streamed as produced */
 }
 
-impl ElfObjectType
+
+#[derive(Serialize)]
+struct TypeCountRecord
 {
-    fn get_type(self: &Self) -> String
-    {
-        match *self
-        {
-            ElfObjectType::EtNone => return String::from("ET_NONE (No file type)"),
-            ElfObjectType::EtRel => return String::from("ET_REL (Relocatable file)"),
-            ElfObjectType::EtExec => return String::from("ET_EXEC (Executable file)"),
-            ElfObjectType::EtDyn => return String::from("ET_DYN (Shared object file)"),
-            ElfObjectType::EtCore => return String::from("ET_CORE (Core file)")
-        };
-    }
+    #[serde(rename = "type")]
+    type_name: String,
+    count: usize
 }
 
-enum ProgramArgumentMethod
+
+#[derive(Serialize)]
+struct CodeDataSizeRecord
 {
-    Sections,
-    DynamicSymbols,
-    DynamicLibraries
+    code: u64,
+    ro_data: u64,
+    rw_data: u64
 }
 
-impl ProgramArgumentMethod
+
+#[derive(Serialize)]
+struct ScannedElfRecord
 {
-    fn start_method_selector(self: &Self, args: &Arguments, elf_obj: &Elf) -> ()
-    {
-        match &self
-        {
-            ProgramArgumentMethod::Sections => args.parse_header_sections(&elf_obj, SINGULAR_CALLER),
-            ProgramArgumentMethod::DynamicSymbols => args.parse_dynamic_syms(&elf_obj, SINGULAR_CALLER),
-
-            ProgramArgumentMethod::DynamicLibraries => { 
-                let libs: HashMap<String, _> = args.parse_dynamic_libs(&elf_obj);
-                
-                args.print_dynamic_libs(libs);
-                std::process::exit(0);
-            }
-        }
-    }
+    path: String,
+    elf_type: String,
+    machine: String,
+    class: String
+}
+
+#[derive(Serialize)]
+struct DirectoryScanRecord
+{
+    directory: String,
+    files_scanned: usize,
+    elf_files: Vec<ScannedElfRecord>,
+    /* ELF type -> files left out by --elf-type-filter */
+    skipped_by_filter: Vec<TypeCountRecord>
+}
+
+
+#[derive(Serialize)]
+struct FileTypeRecord
+{
+    file_type: String,
+    magic: String
+}
+
+
+/*
+    Command line interface, every analysis flag is a field; parse_args() turns it into the runtime Arguments.
+    Two structs on purpose: analysis flags run in the order they were typed (--dyn-syms --sections prints the
+    symbols first), which a derived struct loses and only the ArgMatches indices carry, and Arguments owns the
+    output state (document, NDJSON counter) shared by the analysis methods, which clap has nothing to parse into.
+*/
+#[derive(clap::Parser)]
+#[command(name = "binarymagic", about = "BINARYMAGIC - v1.1 (ALPHA)", arg_required_else_help = true, args_override_self = true,
+    after_help = "Multiple analysis flags may be combined, e.g. binarymagic <TARGET> --sections --dyn-syms --security\n\
+                  WebAssembly modules support --sections, --dyn-libs (imports) and --dyn-syms (exports)")]
+#[command(group(ArgGroup::new("symbol_scripts").args(["emit_ida_script", "emit_ghidra_script"]).multiple(true)))]
+struct CliArguments
+{
+    #[arg(value_name = "TARGET", help = "ELF32/ELF64, PE, Mach-O or WebAssembly binary to analyze")]
+    file: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the section header table of the ELF32/ELF64 binary")]
+    sections: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the dynamic symbol table of the ELF32/ELF64 binary")]
+    dyn_syms: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the dynamic library table of the ELF32/ELF64 binary")]
+    dyn_libs: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the hardening (RELRO/BIND_NOW) status of the ELF32/ELF64 binary, or the NX/ASLR/CFG status of a PE binary")]
+    security: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the static symbol table annotated with DWARF source file/line info")]
+    sym_tab_annotated: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the debug directory entries (CodeView PDB path/GUID) of the PE binary")]
+    pe_debug: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the .NET CLR header (runtime version, flags, strong name) of a managed PE binary")]
+    pe_clr: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the resource tree (type, name, language, data RVA/size) and the manifest of the PE binary")]
+    pe_resources: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the Authenticode signature (certificate type, signer CN, validity) of the PE binary")]
+    pe_code_sign: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the FORTIFY_SOURCE level from the imported __*_chk and unprotected libc functions")]
+    check_fortify: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "classify how far the binary was stripped (Full, DynSymOnly, DebugOnly, SymbolsOnly, Unstripped)")]
+    strip_detect_level: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the size of the ELF32/ELF64 binary broken down by section category")]
+    size: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the architecture specific header flags (e_flags) of the ELF32/ELF64 binary")]
+    arch_info: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the dynamic section entries (and prelink state) of the ELF32/ELF64 binary")]
+    dynamic: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "print the number of imported dynamic symbols")]
+    imports_count: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "print the number of exported dynamic symbols")]
+    exports_count: bool,
+
+    #[arg(long, value_name = "name", help_heading = ANALYSIS_HEADING,
+        help = "dump a section interpreted by its type (strings, symbols, relocations, notes, hex)")]
+    section_content: Option<String>,
+
+    #[arg(long, requires = "section_content", help_heading = ANALYSIS_HEADING,
+        help = "with --section-content, also dump the alignment padding up to the next region, labelled and greyed out")]
+    show_padding: bool,
+
+    #[arg(long, value_name = "name", help_heading = ANALYSIS_HEADING,
+        help = "write the raw bytes of a section to stdout (or to the --out file)")]
+    dump_section_raw: Option<String>,
+
+    #[arg(long, value_name = "file", requires = "dump_section_raw", help_heading = ANALYSIS_HEADING,
+        help = "with --dump-section-raw, write the bytes to <file> instead of stdout")]
+    out: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the SysV hash table (.hash) bucket/chain statistics of the ELF32/ELF64 binary")]
+    hash_stats: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "compare the SysV (.hash) and GNU (.gnu.hash) hash tables: buckets, chain lengths, worst case lookup cost, and the dynamic linker fallback when one of them is missing")]
+    hash_table_stats: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the local symbols grouped by their owning source file (STT_FILE symbol)")]
+    per_file_symbols: bool,
+
+    #[arg(long, value_name = "base-hex", help_heading = ANALYSIS_HEADING,
+        help = "view section, segment and symbol addresses relocated to a new load base (display only)")]
+    rebase_sections: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the SHF_COMPRESSED (zlib/zstd) sections with their compressed/decompressed sizes")]
+    compressed_sections: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "print the file layout (headers, sections, gaps) as a JSON array of {name, start, end, type, permissions}")]
+    timeline_json: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "list every distinct section type with its count and sections, most common first")]
+    list_section_types: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "count the sections per section type and the symbols per symbol type (FUNC, OBJECT, ...)")]
+    count_per_type: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the sections contained in each program header segment (file range, .bss by memory range)")]
+    segment_sections: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the unused gaps/padding between the sections of the ELF32/ELF64 binary")]
+    section_gaps: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the file space taken by PLT stubs (.plt/.plt.sec/.plt.got) of the ELF32/ELF64 binary")]
+    plt_size: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the .modinfo fields (vermagic, license, depends) and exported symbols of a kernel module (.ko)")]
+    kernel_module: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the file header of the binary and of its interpreter (PT_INTERP dynamic linker)")]
+    interp_elf: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "check whether the binary can run on this machine (e_machine, class and endianness against the host, PT_INTERP dynamic linker present on this system)")]
+    arch_compat: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the library most likely providing each imported symbol (DT_NEEDED scan, then known symbol database)")]
+    reconstruct_imports: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the undefined weak symbols of .dynsym and whether a DT_NEEDED library on this system provides them, unresolved ones are 0 at runtime")]
+    unresolved_weak: bool,
+
+    #[arg(long, value_name = "regex", help_heading = ANALYSIS_HEADING,
+        help = "search the printable strings of every section for a regular expression (case insensitive by default)")]
+    find_string: Option<String>,
+
+    #[arg(long, requires = "find_string", help_heading = ANALYSIS_HEADING,
+        help = "with --find-string, match the regular expression case sensitively")]
+    case_sensitive: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the relocated pointers crossing section boundaries (source -> target section), flagging unnamed, non-standard or out of range targets")]
+    cross_section_refs: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the dynamic relocations by startup cost (RELATIVE, COPY, JUMP_SLOT, GLOB_DAT, TLS) and the estimated symbol lookup overhead of the symbolic ones")]
+    reloc_stats: bool,
+
+    #[arg(long, value_name = "N", requires = "reloc_stats", help_heading = ANALYSIS_HEADING,
+        value_parser = clap::value_parser!(u32).range(0..=MAX_RELOC_NS as i64),
+        help = "with --reloc-stats, nanoseconds of symbol lookup per symbolic relocation (default 50)")]
+    reloc_ns: Option<u32>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the relocations of every SHT_REL/SHT_RELA section counted by type, with a startup cost estimate")]
+    count_relocations: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the symbol table (value, section, N_TYPE, external bit) of a Mach-O binary")]
+    macho_symbols: bool,
+
+    #[arg(long, requires = "macho_symbols", help_heading = ANALYSIS_HEADING,
+        help = "with --macho-symbols, also list the debugging (N_STAB) entries")]
+    verbose: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the segments of a Mach-O binary (VM/file ranges, protections) and the sections of each (address, size, offset, alignment, type, attributes)")]
+    macho_segments: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the imported symbols of a Mach-O binary grouped by dylib (version, weak flag, bind type)")]
+    mach_o_imports: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the export trie of a Mach-O binary (kind, address, reexport source, stub resolver)")]
+    mach_o_exports: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the Shannon entropy of every PE section (> 7.0 flagged HIGH) and known packer section names")]
+    pe_entropy: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the Control Flow Guard state of a PE binary (GUARD_CF, guard flags, CF function table, dispatch pointer, EH continuation table)")]
+    cfg: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the load configuration directory of a PE binary (security cookie, SafeSEH table, CFG pointers and guard flags, dynamic value relocations, hybrid metadata)")]
+    pe_load_config: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the minimum kernel version (and OS) required by the binary from its .note.ABI-tag")]
+    notes_abi_tag: bool,
+
+    #[arg(long, value_name = "dll", help_heading = ANALYSIS_HEADING,
+        help = "view the imports of a PE binary from the matching DLLs only (case insensitive, glob patterns such as \"kernel*\" are supported)")]
+    pe_imports_filter: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the code signature SuperBlob of a Mach-O binary (slots, CodeDirectory identifier, hash type, flags, entitlements) and whether it is unsigned, ad-hoc or developer signed")]
+    macho_code_sign: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "identify the format from the first 16 bytes only (ELF, PE, Mach-O, fat/Java class, archives, compressed data, WASM, PDF, ...) and exit")]
+    file_type: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the GNU indirect function (STT_GNU_IFUNC) symbols with their resolver address, size and visibility, and the R_*_IRELATIVE relocations calling the resolvers at startup")]
+    ifunc: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the sections and segments missing expected hardening attributes: executable sections without SHF_ALLOC, writable executable sections and PF_W|PF_X loadable segments (W^X violations)")]
+    show_absent_flags: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "simulate the loading sequence step by step: program headers, PT_LOAD mappings with their permissions, stack, PT_INTERP hand-over, TLS, relocation and PT_GNU_RELRO protection, entry point")]
+    load_order: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the LC_UUID of a Mach-O binary, the identifier crash reports and dSYM bundles are matched on (the Mach-O build-id)")]
+    macho_uuid: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the PE base relocation blocks (.reloc) the loader applies when rebasing the image for ASLR")]
+    pe_base_relocs: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the executable (SHF_EXECINSTR) sections, their address ranges and W^X state")]
+    exec_sections: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the writable (SHF_WRITE) sections, .bss style zero-filled ones apart, and the writable memory total")]
+    writable_sections: bool,
+
+    #[arg(long, value_name = "string", help_heading = ANALYSIS_HEADING,
+        help = "find the lea/adr(p) instructions that load the address of a string literal (x86, x86_64, AArch64)")]
+    xref_string: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "recover function ranges from the .eh_frame FDEs (works on stripped binaries), labelled func_<address>")]
+    stripped_functions: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "print an IDAPython script that names every .symtab/.dynsym function and object via idc.set_name()")]
+    emit_ida_script: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "print a Ghidra Python script that labels every .symtab/.dynsym function and object, with DWARF file:line pre-comments")]
+    emit_ghidra_script: bool,
+
+    #[arg(long, requires = "symbol_scripts", help_heading = ANALYSIS_HEADING,
+        help = "with --emit-ida-script/--emit-ghidra-script, demangle C++ (Itanium) and legacy Rust symbol names")]
+    demangle: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "disassemble the first instructions at e_entry, resolving call/jump/memory targets to symbols")]
+    show_entry_point_context: bool,
+
+    #[arg(long, value_name = "N", requires = "show_entry_point_context", help_heading = ANALYSIS_HEADING,
+        value_parser = clap::value_parser!(u32).range(1..=MAX_ENTRY_DEPTH as i64),
+        help = "with --show-entry-point-context, number of instructions to disassemble (default 20)")]
+    depth: Option<u32>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "crash analysis of an ET_CORE dump: process info, crash signal, per-thread registers and the mapped file list")]
+    core: bool,
+
+    #[arg(long, value_name = "format", help_heading = ANALYSIS_HEADING,
+        help = "print the .symtab/.dynsym symbols as an nm listing, a GDB or LLDB command file, or JSON (nm, gdb, lldb, json)")]
+    export_symbols: Option<String>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "decode the LC_DYLD_INFO rebase and bind opcode streams of a Mach-O binary (segment, section, address, symbol, dylib, bind kind)")]
+    macho_dyld_info: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "estimate the loader cost of a PE image from its imported functions, base relocations and TLS callbacks (a heuristic for comparing builds, not a measurement)")]
+    pe_load_time: bool,
+
+    #[arg(long, num_args = 2, value_names = ["hex-offset", "hex-value"], help_heading = ANALYSIS_HEADING,
+        help = "write a copy of the ELF binary with the byte at <hex-offset> set to <hex-value> as <TARGET>.<offset>.patched \
+                (the original is never modified), show the instruction before/after and check the copy still parses")]
+    patch_byte: Option<Vec<String>>,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "find printf family calls (x86_64, AArch64) and classify their format argument: a .rodata format string, a non-constant value (suspicious) or not set within 5 instructions (potential false positive)")]
+    find_format_strings: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "list the imported symbols no DT_NEEDED library found on this system exports (MISSING from required libraries), a static link check")]
+    unique_imports: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the custom section types registered in ~/.config/binarymagic/section_types.toml ([section_types] 0x6ffffff0 = \"SHT_NAME\") and the sections using them")]
+    section_type_extensions: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "follow the first call of each function from e_entry (_start -> __libc_start_main -> main) for up to 10 calls and print the startup chain")]
+    entry_to_main: bool,
+
+    #[arg(long, help_heading = ANALYSIS_HEADING,
+        help = "view the unique ROP gadgets (ret/jmp reg/call reg) of the x86/x86_64 binary")]
+    find_gadgets: bool,
+
+    #[arg(long, value_name = "N", requires = "find_gadgets", help_heading = ANALYSIS_HEADING,
+        value_parser = clap::value_parser!(u32).range(1..=MAX_GADGET_DEPTH as i64),
+        help = "with --find-gadgets, instructions per gadget (default 5)")]
+    max_depth: Option<u32>,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the binary is stripped, 1 if it is not")]
+    check_stripped: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the binary is NOT stripped, 1 if it is")]
+    check_not_stripped: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 for a PIE executable, 1 for non-PIE (ET_EXEC), 2 for a shared library")]
+    check_pie: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the stack is non-executable (PT_GNU_STACK without PF_X), 1 if executable or W^X is violated, 2 if PT_GNU_STACK is missing")]
+    check_nx: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the ELF class (32/64-bit) matches the host pointer size, 1 if it does not")]
+    elf_class_check: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 for full RELRO (PT_GNU_RELRO + BIND_NOW), 1 for partial RELRO, 2 for no RELRO")]
+    check_relro: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if __stack_chk_fail and __stack_chk_guard are imported, 1 if neither is, 2 if only one of them is (x86 reads the guard from TLS, __stack_chk_fail alone is the usual case)")]
+    check_canary: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the binary has no text relocations, 1 if DT_TEXTREL or DF_TEXTREL is set")]
+    check_textrel: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if BIND_NOW (DT_FLAGS/DT_FLAGS_1) is set or the binary is static, 1 under lazy binding")]
+    check_bindnow: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if the Intel CET shadow stack (GNU_PROPERTY_X86_FEATURE_1_SHSTK) is enabled, 1 if not; also reports IBT and the functions starting with endbr64/endbr32")]
+    check_shadow_stack: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if no two PT_LOAD segments overlap in virtual memory ([p_vaddr, p_vaddr + p_memsz)), 1 and one error per overlap otherwise")]
+    overlap_check: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "list dangerous or deprecated imports (gets, strcpy, system, popen, setuid, rand, ...) with their risk and a safer alternative, exit 1 if any HIGH severity one is imported")]
+    audit_imports: bool,
+
+    #[arg(long, help_heading = CHECKS_HEADING,
+        help = "exit 0 if Clang SafeStack or ShadowCallStack (AArch64) is in use, 1 if neither is")]
+    check_safestack: bool,
+
+    #[arg(long, value_name = "sha256", help_heading = CHECKS_HEADING,
+        help = "exit 0 if the SHA-256 of the file (or of one ELF section) matches, 1 otherwise (after the other flags have run)")]
+    hash_compare: Option<String>,
+
+    #[arg(long, value_name = "name", requires = "hash_compare", help_heading = CHECKS_HEADING,
+        help = "with --hash-compare, hash one ELF section instead of the whole file")]
+    hash_section: Option<String>,
+
+    #[arg(long, num_args = 2, value_names = ["old-binary", "new-binary"], help_heading = COMPARISON_HEADING,
+        help = "diff the global/weak symbols of two ELF binaries (exit 1 on any change)")]
+    compare_symbols: Option<Vec<String>>,
+
+    #[arg(long, num_args = 2, value_names = ["old-binary", "new-binary"], help_heading = COMPARISON_HEADING,
+        help = "diff the section headers of two ELF binaries matched by name: deleted, modified (size/flags) and added sections \
+                with the net file size change (exit 1 on any change)")]
+    compare_sections: Option<Vec<String>>,
+
+    #[arg(long, value_name = "dir", help_heading = ANALYSIS_HEADING,
+        help = "list every ELF file below <dir> (type, machine, class), other files are skipped silently")]
+    scan_dir: Option<String>,
+
+    #[arg(long, value_name = "types", requires = "scan_dir", help_heading = ANALYSIS_HEADING,
+        help = "with --scan-dir, only report the ELF files of the given comma separated types (ET_EXEC, ET_DYN, ET_REL, ET_CORE)")]
+    elf_type_filter: Option<String>,
+
+    #[arg(long, help_heading = OUTPUT_HEADING,
+        help = "emit the analysis results as a single YAML document")]
+    fmt_yaml: bool,
+
+    #[arg(long, help_heading = OUTPUT_HEADING,
+        help = "emit the analysis results as a single JSON object")]
+    json: bool,
+
+    #[arg(long, visible_alias = "file-offsets-hex", help_heading = OUTPUT_HEADING,
+        help = "show offsets/sizes of the sections table and the file header in hexadecimal")]
+    hex_offsets: bool,
+
+    #[arg(long, value_name = "key", value_parser = ["index", "name", "address", "size", "binding", "type"], help_heading = OUTPUT_HEADING,
+        help = "order of the --sym-tab-annotated listing (default: ELF table order), --dyn-syms then lists the .dynsym entries instead of every .dynstr string")]
+    sort_symbols: Option<String>,
+
+    #[arg(long, requires = "sort_symbols", help_heading = OUTPUT_HEADING,
+        help = "with --sort-symbols address, put the symbols at address 0 (FILE/ABS/undefined) after the others")]
+    zero_last: bool,
+
+    #[arg(long, value_name = "name", value_parser = TABLE_THEMES.map(|(name, _)| name), help_heading = OUTPUT_HEADING,
+        help = "table style (default utf8-borders)")]
+    fmt_table_theme: Option<String>,
+
+    #[arg(long, help_heading = OUTPUT_HEADING,
+        help = "disable colored output (implies --fmt-table-theme ascii-full)")]
+    no_color: bool,
+
+    #[arg(long, help_heading = OUTPUT_HEADING,
+        help = "omit the informational trace lines printed around the tables (ELF identification packing of the file header)")]
+    quiet: bool,
+
+    #[arg(long, visible_alias = "ndjson", help_heading = OUTPUT_HEADING,
+        help = "emit newline delimited JSON, one object per record tagged with a \"type\" field, opened by a \"header\" object and closed by a \"summary\" object")]
+    machine_readable: bool
 }
 
 /* CLI options */
 struct Arguments 
 {
     file: String,
-    optional_param: String
+    flags: Vec<String>,
+    flag_values: HashMap<String, Vec<String>>,
+    output_format: OutputFormat,
+
+    /* Structured output is accumulated here so several analysis methods produce one document */
+    document: RefCell<Mapping>,
+
+    /* Objects written so far by the NDJSON stream, reported by the closing summary object */
+    streamed_records: StdCell<usize>,
+
+    /* Verdict of a check in a batch (--hash-compare), the process exits with it once every flag has run */
+    exit_code: StdCell<i32>,
+
+    /* --fmt-table-theme name (forced to "ascii-full" by --no-color) */
+    table_theme: String,
+    no_color: bool,
+    quiet: bool,
+
+    /* Offsets/sizes in the sections table and file header */
+    number_format: NumberFormat,
+
+    /* --sort-symbols order for the symbol listings, None keeps the unsorted --dyn-syms string listing */
+    sort_symbols: Option<SymbolSortKey>,
+
+    /* Numeric modifiers, already range checked by clap (--max-depth, --depth, --reloc-ns) */
+    gadget_depth: usize,
+    entry_depth: usize,
+    reloc_ns: u64
 }
 
 impl Arguments
 {
-    fn initialize_primary_object(self: &Self, target: &Vec<u8>, argument: &&str, args: &Arguments) -> ()
+
+
+    fn get_flag_values(&self, flag: &str) -> &[String]
+    {
+        self.flag_values.get(flag).map_or(&[], |values: &Vec<String>| values.as_slice())
+    }
+
+
+    fn initialize_primary_object(&self, target: &[u8])
     {
         /* Clone vector containing binary contents of the target executable */
-        let target_clone: Vec<u8> = target.clone();
+        let target_clone: Vec<u8> = target.to_vec();
 
-        // convert data to Elf
-        if let Ok(Object::Elf(obj)) = Object::parse(&target_clone)
+        /* WASM is not understood by goblin, it is walked with wasmparser instead */
+        let wasm_module: Option<WasmModule> = match target_clone.starts_with(&WASM_MAGIC)
         {
-            let start_enum: ProgramArgumentMethod;
+            true => Some(return_wasm_module(&target_clone)),
+            false => None
+        };
 
-            match argument
-            {
-                &"--sections" => start_enum = ProgramArgumentMethod::Sections,
-                &"--dyn-syms" => start_enum = ProgramArgumentMethod::DynamicSymbols,
-                &"--dyn-libs" => start_enum = ProgramArgumentMethod::DynamicLibraries,
+        // convert data to Elf/PE/Mach-O (parsed once, shared by every requested method)
+        let object: Option<Object> = match (&wasm_module, Object::parse(&target_clone))
+        {
+            (Some(_), _) => None,
+            (None, Ok(obj @ Object::Elf(_))) | (None, Ok(obj @ Object::PE(_))) => Some(obj),
+            (None, Ok(obj @ Object::Mach(Mach::Binary(_)))) => Some(obj),
 
-                /* Default throwback value if it is somehow not already specified previously */
-                _ => start_enum = ProgramArgumentMethod::Sections
+            _ => {
+                eprintln!("Object file is not supported at the moment!");
+                std::process::exit(-1);
             }
+        };
 
-            start_enum.start_method_selector(&args, &obj);
+        /* Raw bytes written to stdout must not be interleaved with the output of any other method */
+        let raw_stdout_dump: bool = self.flags.iter().any(|flag| flag == "--dump-section-raw")
+            && self.get_flag_values("--out").is_empty();
+
+        /* The NDJSON stream always opens with the header object of the target */
+        if let (OutputFormat::Ndjson, Some(Object::Elf(elf_obj))) = (&self.output_format, &object)
+        {
+            self.parse_file_header(elf_obj, "header");
         }
-        else 
+
+        /* --elf-class-check reports the mismatch itself */
+        if let (Some(Object::Elf(elf_obj)), false) = (&object, raw_stdout_dump || self.flags.iter().any(|flag| flag == "--elf-class-check"))
         {
-            eprintln!("Object file is not supported at the moment!");
-            std::process::exit(-1);
+            self.print_elf_class_notice(elf_obj);
+        }
+
+        for flag in &self.flags
+        {
+            if MODIFIER_PARAMS.contains(&flag.as_str())
+                || (raw_stdout_dump && flag != "--dump-section-raw")
+            {
+                continue;
+            }
+
+            let start_enum: ProgramArgumentMethod = match flag.as_str()
+            {
+                "--sections" => ProgramArgumentMethod::Sections,
+                "--dyn-syms" => ProgramArgumentMethod::DynamicSymbols,
+                "--dyn-libs" => ProgramArgumentMethod::DynamicLibraries,
+                "--security" => ProgramArgumentMethod::Security,
+                "--sym-tab-annotated" => ProgramArgumentMethod::SymTabAnnotated,
+                "--pe-debug" => ProgramArgumentMethod::PeDebug,
+                "--size" => ProgramArgumentMethod::SizeBreakdown,
+                "--arch-info" => ProgramArgumentMethod::ArchInfo,
+                "--check-stripped" => ProgramArgumentMethod::CheckStripped,
+                "--check-not-stripped" => ProgramArgumentMethod::CheckNotStripped,
+                "--dynamic" => ProgramArgumentMethod::Dynamic,
+                "--imports-count" => ProgramArgumentMethod::ImportsCount,
+                "--exports-count" => ProgramArgumentMethod::ExportsCount,
+                "--section-content" => ProgramArgumentMethod::SectionContent,
+                "--check-pie" => ProgramArgumentMethod::CheckPie,
+                "--dump-section-raw" => ProgramArgumentMethod::DumpSectionRaw,
+                "--hash-stats" => ProgramArgumentMethod::HashStats,
+                "--per-file-symbols" => ProgramArgumentMethod::PerFileSymbols,
+                "--pe-clr" => ProgramArgumentMethod::PeClr,
+                "--rebase-sections" => ProgramArgumentMethod::RebaseSections,
+                "--find-gadgets" => ProgramArgumentMethod::FindGadgets,
+                "--pe-code-sign" => ProgramArgumentMethod::PeCodeSign,
+                "--section-gaps" => ProgramArgumentMethod::SectionGaps,
+                "--interp-elf" => ProgramArgumentMethod::InterpElf,
+                "--plt-size" => ProgramArgumentMethod::PltSize,
+                "--kernel-module" => ProgramArgumentMethod::KernelModule,
+                "--reconstruct-imports" => ProgramArgumentMethod::ReconstructImports,
+                "--hash-compare" => ProgramArgumentMethod::HashCompare,
+                "--compressed-sections" => ProgramArgumentMethod::CompressedSections,
+                "--macho-symbols" => ProgramArgumentMethod::MachSymbols,
+                "--count-relocations" => ProgramArgumentMethod::CountRelocations,
+                "--pe-resources" => ProgramArgumentMethod::PeResources,
+                "--check-nx" => ProgramArgumentMethod::CheckNx,
+                "--find-string" => ProgramArgumentMethod::FindString,
+                "--mach-o-imports" => ProgramArgumentMethod::MachImports,
+                "--mach-o-exports" => ProgramArgumentMethod::MachExports,
+                "--timeline-json" => ProgramArgumentMethod::TimelineJson,
+                "--list-section-types" => ProgramArgumentMethod::ListSectionTypes,
+                "--segment-sections" => ProgramArgumentMethod::SegmentSections,
+                "--check-fortify" => ProgramArgumentMethod::CheckFortify,
+                "--strip-detect-level" => ProgramArgumentMethod::StripDetectLevel,
+                "--macho-code-sign" => ProgramArgumentMethod::MachCodeSign,
+                "--pe-imports-filter" => ProgramArgumentMethod::PeImportsFilter,
+                "--cfg" => ProgramArgumentMethod::PeCfg,
+                "--notes-abi-tag" => ProgramArgumentMethod::AbiTag,
+                "--check-relro" => ProgramArgumentMethod::CheckRelro,
+                "--cross-section-refs" => ProgramArgumentMethod::CrossSectionRefs,
+                "--unresolved-weak" => ProgramArgumentMethod::UnresolvedWeak,
+                "--pe-entropy" => ProgramArgumentMethod::PeEntropy,
+                "--elf-class-check" => ProgramArgumentMethod::ElfClassCheck,
+                "--reloc-stats" => ProgramArgumentMethod::RelocStats,
+                "--macho-segments" => ProgramArgumentMethod::MachSegments,
+                "--hash-table-stats" => ProgramArgumentMethod::HashTableStats,
+                "--count-per-type" => ProgramArgumentMethod::CountPerType,
+                "--check-canary" => ProgramArgumentMethod::CheckCanary,
+                "--pe-load-config" => ProgramArgumentMethod::PeLoadConfig,
+                "--arch-compat" => ProgramArgumentMethod::ArchCompat,
+                "--ifunc" => ProgramArgumentMethod::Ifunc,
+                "--show-absent-flags" => ProgramArgumentMethod::ShowAbsentFlags,
+                "--load-order" => ProgramArgumentMethod::LoadOrder,
+                "--check-textrel" => ProgramArgumentMethod::CheckTextrel,
+                "--macho-uuid" => ProgramArgumentMethod::MachUuid,
+                "--pe-base-relocs" => ProgramArgumentMethod::PeBaseRelocs,
+                "--check-bindnow" => ProgramArgumentMethod::CheckBindnow,
+                "--exec-sections" => ProgramArgumentMethod::ExecSections,
+                "--writable-sections" => ProgramArgumentMethod::WritableSections,
+                "--xref-string" => ProgramArgumentMethod::XrefString,
+                "--stripped-functions" => ProgramArgumentMethod::StrippedFunctions,
+                "--emit-ida-script" => ProgramArgumentMethod::EmitIdaScript,
+                "--emit-ghidra-script" => ProgramArgumentMethod::EmitGhidraScript,
+                "--show-entry-point-context" => ProgramArgumentMethod::ShowEntryPointContext,
+                "--core" => ProgramArgumentMethod::Core,
+                "--export-symbols" => ProgramArgumentMethod::ExportSymbols,
+                "--check-safestack" => ProgramArgumentMethod::CheckSafestack,
+                "--macho-dyld-info" => ProgramArgumentMethod::MachDyldInfo,
+                "--pe-load-time" => ProgramArgumentMethod::PeLoadTime,
+                "--patch-byte" => ProgramArgumentMethod::PatchByte,
+                "--check-shadow-stack" => ProgramArgumentMethod::CheckShadowStack,
+                "--find-format-strings" => ProgramArgumentMethod::FindFormatStrings,
+                "--unique-imports" => ProgramArgumentMethod::UniqueImports,
+                "--section-type-extensions" => ProgramArgumentMethod::SectionTypeExtensions,
+                "--entry-to-main" => ProgramArgumentMethod::EntryToMain,
+                "--overlap-check" => ProgramArgumentMethod::OverlapCheck,
+                "--audit-imports" => ProgramArgumentMethod::AuditImports,
+
+                /* Every flag clap accepts maps above, a missing arm must not quietly print the sections table */
+                _ => {
+                    eprintln!("Error - option \"{}\" has no handler!", flag);
+                    std::process::exit(-1);
+                }
+            };
+
+            match (&object, &wasm_module)
+            {
+                /* A core dump is program headers and notes only, section based methods would print an empty table */
+                (Some(Object::Elf(obj)), _) if obj.header.e_type == ET_CORE && flag == "--sections" => match self.is_structured_output()
+                {
+                    true => eprintln!("Core files have no sections; use --core for crash analysis."),
+                    false => println!("Core files have no sections; use --core for crash analysis.")
+                },
+                (Some(Object::Elf(obj)), _) => start_enum.start_method_selector(self, obj, &target_clone, flag),
+                (Some(Object::PE(pe)), _) => start_enum.start_pe_method_selector(self, pe, &target_clone, flag),
+                (Some(Object::Mach(Mach::Binary(macho))), _) => start_enum.start_mach_method_selector(self, macho, &target_clone, flag),
+                (None, Some(wasm)) => start_enum.start_wasm_method_selector(self, wasm, flag),
+
+                _ => ()
+            }
         }
     }
 
 
-    fn parse_header_sections(self: &Self, elf_obj: &Elf, is_caller_singular: bool) -> ()
+    /* Whole file by default, a single ELF section with --hash-section */
+    fn hash_compare(&self, elf_obj: Option<&Elf>, data: &[u8])
     {
-        /* Section header string table */
-        let elf_shdr_tab: &Strtab<'_> = &elf_obj.shdr_strtab;
-        println!("\nSection Headers =>");
-
-        let mut section_hdr_table: Table = Table::new();
-
-        section_hdr_table.load_preset(UTF8_BORDERS_ONLY)
-            .set_content_arrangement(ContentArrangement::Dynamic)
-            .set_header(vec![
-                /* Formatting options supplemented */
-                Cell::new("Symbol Name \u{00a7}").fg(Color::Green).add_attribute(Attribute::Bold), 
-                Cell::new("Flags").fg(Color::Green).add_attribute(Attribute::Bold),
-                Cell::new("Header Type").fg(Color::Green).add_attribute(Attribute::Bold),
-                Cell::new(format!("Offset {OMEGA}")).fg(Color::Green).add_attribute(Attribute::Bold),
-
-                /* No formatting options */
-                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold), 
-                Cell::new("Ent size").fg(Color::Green).add_attribute(Attribute::Bold), 
-                Cell::new("Has Table?").fg(Color::Green).add_attribute(Attribute::Bold)
-            ]);
-        
-        for elf_section_hdr in &elf_obj.section_headers
+        let expected: String = match self.get_flag_values("--hash-compare").first()
         {
-            let section_name: &str = elf_shdr_tab.get_at(elf_section_hdr.sh_name).unwrap_or("Not defined");
-            let section_offset: String = format!("{}", elf_section_hdr.sh_offset);
+            Some(expected) => expected.trim().to_lowercase(),
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --hash-compare <sha256-hex> [--hash-section <name>]");
+                self.exit_code.set(-1);
+                return;
+            }
+        };
 
-            /* ELF section header type */
-            let elf_sh_type: ElfSectionType = match elf_section_hdr.sh_type as u32 
+        let hashed: &[u8] = match (self.get_flag_values("--hash-section").first(), elf_obj)
+        {
+            (None, _) => data,
+            (Some(section_name), Some(elf_obj)) => match return_section_data(elf_obj, data, section_name)
             {
-                SHT_NULL => ElfSectionType::ShtNull,
-                SHT_PROGBITS => ElfSectionType::ShtProgBits,
-                SHT_SYMTAB => ElfSectionType::ShtSymTab,
-                SHT_STRTAB => ElfSectionType::ShtStrTab,
-                SHT_RELA => ElfSectionType::ShtRela,
-                SHT_HASH => ElfSectionType::ShtHash,
-                SHT_DYNAMIC => ElfSectionType::ShtDynamic,
-                SHT_NOTE => ElfSectionType::ShtNote,
-                SHT_NOBITS => ElfSectionType::ShtNoBits,
-                SHT_REL => ElfSectionType::ShtRel,
-                SHT_SHLIB => ElfSectionType::ShtShLib,
-                SHT_DYNSYM => ElfSectionType::ShtDynSym,
-                SHT_INIT_ARRAY => ElfSectionType::ShtInitArray,
-                SHT_FINI_ARRAY => ElfSectionType::ShtFiniArray,
-                SHT_PREINIT_ARRAY => ElfSectionType::ShtPreInitArray,
-                SHT_GROUP => ElfSectionType::ShtGroup,
-                SHT_SYMTAB_SHNDX => ElfSectionType::ShtSymTabShndx,
-
-                _  => ElfSectionType::ShtNull
-            };
+                Some(section_bytes) => section_bytes,
 
-            let section_hdr_sz: String = format!("{}", match (elf_section_hdr.sh_size >= 1024 as u64) as bool {
-                true => format!("{} Kb ({:.2} bytes)", ((&elf_section_hdr.sh_size / 1024) as f64), elf_section_hdr.sh_size),
-                false => format!("{} bytes", &elf_section_hdr.sh_size)
-            });
+                None => {
+                    eprintln!("Error - section \"{section_name}\" not found in binary (or has no file contents)!");
+                    self.exit_code.set(-1);
+                    return;
+                }
+            },
 
-            let section_ent_sz: String = format!("{} bytes", elf_section_hdr.sh_entsize); 
-
-            let _attributes: Vec<Attribute> = vec![
-                // Attribute::Bold,
-                // Attribute::Italic
-            ];
-
-            section_hdr_table.add_row(vec![
-                Cell::new(section_name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),  /* SECTION NAME */
-                Cell::new(format!("{}", 
-                    match elf_section_hdr.sh_flags as u32
-                    {
-                        SHF_UNDEFINED => SHF_UNDEFINED_STR,
-
-                        /* handle write/allocation primary flags */
-                        SHF_WRITE => "SHF_WRITE",
-                        SHF_ALLOC => "SHF_ALLOC",
-                        SHF_WRITE_ALLOC => "SHF_WRITE & SHF_ALLOC",
-
-                        /* assembly instructions (intel/at&t)? */
-                        SHF_ASM_INST_ALLOC => "SHF_ASM_OPCODE",
-
-                        SHF_EXECINSTR => "SHF_EXECINSTR",
-                        SHF_MERGE => "SHF_MERGE",
-                        SHF_STRINGS => "SHF_STRINGS",
-                        SHF_INFO_LINK => "SHF_INFO_LINK",
-                        SHF_LINK_ORDER => "SHF_LINK_ORDER",
-                        SHF_OS_NONCONFORMING => "SHF_OS_NON_CONFORMING",
-                        SHF_GROUP => "SHF_GROUP",
-                        SHF_TLS => "SHF_TLS",
-                        SHF_MASKOS => "SHF_MASKOS",
-                        SHF_MASKPROC => "SHF_MASKPROC",
-                        
-                        /* lazy method to handle bogus data */
-                        _ => SHF_UNDEFINED_STR
-                    }
-                )).fg(Color::Yellow),
-
-                Cell::new(&elf_sh_type.get_type()).fg(Color::DarkGreen).add_attribute(Attribute::Italic), 
-                Cell::new(&section_offset),                                                  /* OFFSET  */
-                Cell::new(&section_hdr_sz),                                                  /* HDR_SIZE */
-                
-                match (elf_section_hdr.sh_entsize > 0) as bool                               /* ENT_SIZE */
-                {
-                    true => Cell::new(&section_ent_sz),
-                    false => Cell::new("")
-                },                                  
+            (Some(_), None) => {
+                eprintln!("Error - --hash-section is only supported for ELF binaries!");
+                self.exit_code.set(-1);
+                return;
+            }
+        };
 
-                match (elf_section_hdr.sh_entsize > 0) as bool                               /* Has Table? */
-                {
-                    true => Cell::new(format!("{CHECK}")).fg(Color::Green).add_attribute(Attribute::Bold),
-                    false => Cell::new(format!("{CROSS}")).fg(Color::Red).add_attribute(Attribute::Dim)
-                } 
-            ]);
-        }
+        let computed: String = Sha256::digest(hashed).iter().map(|b| format!("{b:02x}")).collect();
+
+        let verified: bool = computed == expected;
 
-        println!("\n{section_hdr_table}");
-        println!("\n{} section headers detected.", (&elf_obj.header.e_shnum - 1 as u16));
+        let (color, reset): (&str, &str) = match (self.no_color, verified)
+        {
+            (true, _) => ("", ""),
+            (false, true) => (ANSI_GREEN, ANSI_RESET),
+            (false, false) => (ANSI_RED, ANSI_RESET)
+        };
 
-        match is_caller_singular
+        match verified
         {
-            SINGULAR_CALLER => std::process::exit(0),
-            MULTI_CALLER => ()
+            true => println!("{color}VERIFIED \u{2713}{reset}"),
+
+            false => {
+                println!("{color}MISMATCH \u{2717} (expected: {expected}, got: {computed}){reset}");
+
+                /* An earlier error in the batch keeps its exit code */
+                if self.exit_code.get() == 0
+                {
+                    self.exit_code.set(1);
+                }
+            }
         }
     }
 
 
-    fn parse_dynamic_syms(self: &Self, elf_obj: &Elf, is_caller_singular: bool) -> ()
+    fn detect_file_type(&self, path: &Path)
     {
-        /* Dynamically accessible symbols table */
-        let elf_dym_sym: &Strtab<'_> = &elf_obj.dynstrtab;
-        let elf_dymsym_vec: Vec<&str> = elf_dym_sym.to_vec().expect("Failed to convert dynamic symbol table to vector!");
-        
-        let mut c: i32 = 0;
-
-        println!("Dynamic Symbols Table =>\n");
+        let mut magic: [u8; 16] = [0; 16];
 
-        for (mut i, &v) in elf_dymsym_vec.iter().enumerate()
+        let length: usize = match fs::File::open(path).and_then(|mut file| file.read(&mut magic))
         {
-            i += 1;
+            Ok(length) => length,
 
-            println!("\t {i}. {v}");
-            c += 1;
-        }
+            Err(err) => {
+                eprintln!("Error - unable to read \"{}\": {err}", path.display());
+                std::process::exit(-1);
+            }
+        };
 
-        println!("\n[DYNSYMS] {c} dynamic symbols found.");
-        self.print_dynamic_libs(self.parse_dynamic_libs(&elf_obj));
+        let record: FileTypeRecord = FileTypeRecord {
+            file_type: return_file_type(&magic[..length]),
+            magic: magic[..length].iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join(" ")
+        };
 
-        match is_caller_singular
+        if self.is_structured_output()
         {
-            SINGULAR_CALLER => std::process::exit(0),
-            MULTI_CALLER => ()
+            self.insert_document("file_type", &record);
+            return;
         }
-    }
 
+        println!("{}", record.file_type);
+    }
 
-    /* Return the current listing of dynamic libraries associated with the binary */
-    fn parse_dynamic_libs(self: &Self, elf: &Elf) -> HashMap<String, ()>
+    /* Only the ELF header of each file is read, the whole file is never loaded */
+    fn scan_directory(&self, directory: &Path)
     {
-        let mut dyn_libs: HashMap<String, _> = HashMap::new();
+        let type_filter: Vec<String> = match self.get_flag_values("--elf-type-filter").first()
+        {
+            Some(filter) => filter.split(',').map(|elf_type| elf_type.trim().to_uppercase()).filter(|elf_type| !elf_type.is_empty()).collect(),
+            None => Vec::new()
+        };
 
-        for lib in &elf.libraries
+        if let Some(unknown) = type_filter.iter().find(|elf_type| !matches!(elf_type.as_str(), "ET_EXEC" | "ET_DYN" | "ET_REL" | "ET_CORE"))
         {
-            let x: String = lib.to_string();
+            eprintln!("Error - unknown ELF type \"{unknown}\" for --elf-type-filter, expected ET_EXEC, ET_DYN, ET_REL or ET_CORE");
+            std::process::exit(-1);
+        }
 
-            dyn_libs.insert(x, ());
+        if !directory.is_dir()
+        {
+            eprintln!("Error - \"{}\" is not a directory!", directory.display());
+            std::process::exit(-1);
         }
 
-        dyn_libs
-    }
+        let mut files: Vec<std::path::PathBuf> = Vec::new();
+        return_directory_files(directory, &mut files);
+        files.sort();
 
+        let mut scan: DirectoryScanRecord = DirectoryScanRecord {
+            directory: directory.display().to_string(),
+            files_scanned: files.len(),
+            elf_files: Vec::new(),
+            skipped_by_filter: Vec::new()
+        };
 
-    fn print_dynamic_libs(self: &Self, libs: HashMap<String, ()>) -> ()
-    {
+        for file in &files
         {
-            println!("\n* Dynamic Libraries found:");
+            let mut header_bytes: [u8; 64] = [0; 64];
+
+            let length: usize = match fs::File::open(file).and_then(|mut handle| handle.read(&mut header_bytes))
+            {
+                Ok(length) => length,
+                Err(_) => continue
+            };
+
+            /* Non-ELF (and unreadable or truncated) files are skipped silently */
+            let header: Header = match Elf::parse_header(&header_bytes[..length])
+            {
+                Ok(header) => header,
+                Err(_) => continue
+            };
+
+            let elf_type: String = format!("ET_{}", et_to_str(header.e_type));
 
-            for libs in libs
+            if !type_filter.is_empty() && !type_filter.contains(&elf_type)
             {
-                println!("\ttarget >> {}", libs.0);
+                match scan.skipped_by_filter.iter_mut().find(|record| record.type_name == elf_type)
+                {
+                    Some(record) => record.count += 1,
+                    None => scan.skipped_by_filter.push(TypeCountRecord { type_name: elf_type, count: 1 })
+                }
+
+                continue;
             }
+
+            scan.elf_files.push(ScannedElfRecord {
+                path: file.display().to_string(),
+                elf_type,
+                machine: machine_to_str(header.e_machine).to_string(),
+                class: match header.e_ident[EI_CLASS] == ELFCLASS64 { true => "ELF64", false => "ELF32" }.to_string()
+            });
         }
 
-        ()
+        if self.is_structured_output()
+        {
+            self.insert_document("scan_dir", &scan);
+            return;
+        }
+
+        println!("\nELF Files => {}", scan.directory);
+
+        let mut scan_table: Table = self.new_table();
+
+        scan_table.set_header(vec![
+                Cell::new("Path").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Machine").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Class").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &scan.elf_files
+        {
+            scan_table.add_row(vec![
+                Cell::new(&record.path).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.elf_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(&record.machine).fg(Color::Yellow),
+                Cell::new(&record.class).fg(Color::Yellow)
+            ]);
+        }
+
+        if !scan.elf_files.is_empty()
+        {
+            println!("\n{scan_table}");
+        }
+
+        println!("\n{} ELF files reported out of {} files scanned.", scan.elf_files.len(), scan.files_scanned);
+
+        for record in &scan.skipped_by_filter
+        {
+            println!("Skipped {} {} {} due to type filter.", record.count, record.type_name, match record.count { 1 => "file", _ => "files" });
+        }
     }
+
 }
 
 
@@ -374,248 +1172,385 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
 {
     let argv: Arguments = parse_args().unwrap();
 
-    // &str = stack allocation, String = heap allocation??
-    let params: Vec<&str> = vec![
-        "--sections",       /* ELF header section table */
-        "--dyn-syms",       /* Dynamic symbols */    
-        "--dyn-libs",       /* Dynamically linked libraries */
-        "NULL"
-    ];
+    /* Standalone modes operating on two binaries rather than the target */
+    if argv.flags.iter().any(|flag: &String| flag == "--compare-symbols")
+    {
+        let targets: &[String] = argv.get_flag_values("--compare-symbols");
+
+        if targets.len() < 2
+        {
+            eprintln!("Error - usage: binarymagic --compare-symbols <old-binary> <new-binary>");
+            std::process::exit(-1);
+        }
+
+        let has_changes: bool = argv.compare_symbols(&targets[0], &targets[1]);
+        argv.flush_document();
+
+        std::process::exit(has_changes as i32);
+    }
+
+    if argv.flags.iter().any(|flag: &String| flag == "--compare-sections")
+    {
+        let targets: &[String] = argv.get_flag_values("--compare-sections");
+
+        if targets.len() < 2
+        {
+            eprintln!("Error - usage: binarymagic --compare-sections <old-binary> <new-binary>");
+            std::process::exit(-1);
+        }
+
+        let has_changes: bool = argv.compare_sections(&targets[0], &targets[1]);
+        argv.flush_document();
+
+        std::process::exit(has_changes as i32);
+    }
+
+    if let Some(directory) = argv.get_flag_values("--scan-dir").first()
+    {
+        argv.scan_directory(Path::new(directory));
+        argv.flush_document();
+
+        std::process::exit(0);
+    }
 
     let path: &Path = Path::new(argv.file.as_str());
+
+    /* Identification only needs the magic, never read (or parse) the whole file for it */
+    if argv.flags.iter().any(|flag: &String| flag == "--file-type")
+    {
+        argv.detect_file_type(path);
+        argv.flush_document();
+
+        std::process::exit(0);
+    }
+
     let binary_fluff: Vec<u8> = fs::read(path).expect("Failed to read file data!");
 
-    if argv.optional_param.len() >= PARAM_DATA_LIMIT
+    if argv.flags.len() >= PARAM_DATA_LIMIT
     {
-        match params.iter().find(|x: &&&str| &argv.optional_param == **x)
-        {
-            Some(arg) => {
-                match arg {
-                    // &"--sections" because 'e = &&str', "--sections" = &str, so &"--sections" = &&str
-                    &"--sections" | 
-                    &"--dyn-syms" | &"--dyn-libs" => argv.initialize_primary_object(&binary_fluff, *(&arg), &argv),
-                   
-                    _ => ()
-                }
-            },
+        /* Run every requested method in order, then leave once the whole batch has been reported */
+        argv.initialize_primary_object(&binary_fluff);
+        argv.flush_document();
 
-            None => {
-                eprintln!("Error - unknown option: \"{}\"", argv.optional_param);
-                std::process::exit(-1);
-            }
-        };
+        std::process::exit(argv.exit_code.get());
     }
 
-    match Object::parse(&binary_fluff).expect("Failed to parse binary object file!")
+    /* No flags on a WASM module: same overview as ELF (sections, then imports/exports) */
+    if binary_fluff.starts_with(&WASM_MAGIC)
     {
-        Object::Elf(elf_obj) =>
-        {
-            argv.parse_header_sections(&elf_obj, MULTI_CALLER);
-            argv.parse_dynamic_syms(&elf_obj, MULTI_CALLER);
+        let wasm_module: WasmModule = return_wasm_module(&binary_fluff);
 
-            let elf_sz: u16 = elf_obj.header.e_ehsize;
+        argv.parse_wasm_sections(&wasm_module);
+        argv.parse_wasm_imports(&wasm_module);
+        argv.parse_wasm_exports(&wasm_module);
+        argv.flush_document();
 
-            let elf_end: Endian = elf_obj.header.endianness().expect("Failed to obtain endianness of binary!");
-            let elf_ident: [u8; SIZEOF_IDENT] = elf_obj.header.e_ident;
+        return Ok(());
+    }
 
-            let elf_emachine: u16 = elf_obj.header.e_machine;
-            let elf_eversion: u32 = elf_obj.header.e_version;
+    match Object::parse(&binary_fluff).expect("Failed to parse binary object file!")
+    {
+        Object::Elf(elf_obj) if elf_obj.header.e_type == ET_CORE => {
+            argv.parse_core_file(&elf_obj, &binary_fluff);
+            argv.flush_document();
+        },
 
-            let (elf_magic, elf_class, elf_data, elf_version): (String, u8, u8, u8) = return_hdr_magic(&elf_ident);
+        Object::Elf(elf_obj) =>
+        {
+            let is_ndjson: bool = matches!(argv.output_format, OutputFormat::Ndjson);
 
-            let msg: String = format!(r###"
-                FILE HEADER/MAGIC INFORMATION
-                =============================
+            if is_ndjson
+            {
+                argv.parse_file_header(&elf_obj, "header");
+            }
 
-                ARCH   : {elf_sz}-bit binary
-                MAGIC  : {}
-                         CLASS={} | DATA={} | VERSION={}
+            argv.print_elf_class_notice(&elf_obj);
+            argv.parse_header_sections(&elf_obj, &binary_fluff);
+            argv.parse_dynamic_syms(&elf_obj);
 
-                ENDIAN : {:#?}
-                E_TYPE : {}
-                E_MACH : {}
-                E_VERS : {}
-                E_ENTR : {}
-                ________________________
-            "###, 
-                elf_magic,                          /* MAGIC */
-                match elf_class as u8               /* CLASS TYPE */
-                {
-                    ELFCLASSNONE => format!("{ELFCLASSNONE} (NONE)"), 
-                    ELFCLASS32 => format!("{ELFCLASS32} (32 BIT)"),
-                    ELFCLASS64 => format!("{ELFCLASS64} (64 BIT)"), 
-                    
-                    _ => String::from("UNKNOWN")
-                },
-                
-                match elf_data as u8                /* DATA TYPE */
-                {
-                    ELFDATANONE => format!("{ELFDATANONE} (Invalid data encoding)"),
-                    ELFDATA2LSB => format!("{ELFDATA2LSB} (LE with 2\'s complement)"),
-                    ELFDATA2MSB => format!("{ELFDATA2MSB} (BE with 2\'s compliment)"),
-               
-                    _ => String::from("UNKNOWN")
-                },
+            if !is_ndjson
+            {
+                argv.parse_file_header(&elf_obj, "header");
+            }
 
-                elf_version,
-                elf_end,                            /* ENDIAN TYPE */
-                return_elf_etype(&elf_obj),         /* E_TYPE (Object file type) */
-                return_elf_emachine(elf_emachine),  /* E_MACH (CPU Architecture)*/
-                
-                match elf_eversion as u32           /* E_VERS */
-                { 
-                    0 => format!("{elf_eversion} (EV_NONE)"), 
-                    1 => format!("{elf_eversion} (EV_CURRENT)"),
-
-                    _ => String::from("UNKNOWN")
-                },
+            let (code_bytes, ro_data_bytes, rw_data_bytes): (u64, u64, u64) = compute_code_data_sizes(&elf_obj, &binary_fluff);
 
-                elf_obj.entry,                      /* ENTRY POINT */
-            );
+            match argv.is_structured_output()
+            {
+                true => argv.insert_document("code_data_sizes", &CodeDataSizeRecord {
+                    code: code_bytes,
+                    ro_data: ro_data_bytes,
+                    rw_data: rw_data_bytes
+                }),
+
+                false => println!("\nCode: {:.1} KiB | ROData: {:.1} KiB | RWData: {:.1} KiB",
+                    code_bytes as f64 / 1024.0,
+                    ro_data_bytes as f64 / 1024.0,
+                    rw_data_bytes as f64 / 1024.0
+                )
+            }
 
-            print!("{}", msg.unindent());
+            argv.parse_abi_tag(&elf_obj, &binary_fluff);
+            argv.flush_document();
         },
 
         Object::PE(pe) => println!("pe: {:#?}", &pe),
+        Object::Mach(Mach::Binary(macho)) => {
+            argv.parse_macho_dyld_info(&macho, &binary_fluff);
+            argv.extract_macho_uuid(&macho);
+            argv.flush_document();
+        },
+
         Object::Mach(mach) => println!("mach: {:#?}", &mach),
         Object::Unknown(magic) => println!("Invalid executable: could not parse file header: magic => {:#?}", magic),
 
         _ => ()
     };
 
-    return Ok(())
+    Ok(())
 }
 
 
-fn return_elf_emachine(emachine_id: u16) -> String
+/* Regular files below the directory, symlinks are not followed so link farms (/usr/lib) are not scanned twice */
+fn return_directory_files(directory: &Path, files: &mut Vec<std::path::PathBuf>)
 {
-    match emachine_id as u16
+    let entries: fs::ReadDir = match fs::read_dir(directory)
     {
-        EM_NONE => "No machine".to_string(),
-        EM_MIPS => "MIPS I Architecture".to_string(),
-        EM_PPC | EM_PPC64 => "PowerPC 32/64 bit".to_string(),
-        EM_X86_64 => "Intel/AMD 64-bit".to_string(),
+        Ok(entries) => entries,
+        Err(_) => return
+    };
 
-        _ => "Unknown".to_string()
+    for entry in entries.flatten()
+    {
+        match entry.file_type()
+        {
+            Ok(file_type) if file_type.is_dir() => return_directory_files(&entry.path(), files),
+            Ok(file_type) if file_type.is_file() => files.push(entry.path()),
+
+            _ => ()
+        }
     }
 }
 
 
-fn return_elf_etype(elf: &Elf) -> String 
+/* Raw file contents of a named section, None for SHT_NOBITS or out of bounds sections */
+fn return_section_data<'a>(elf: &Elf, data: &'a [u8], name: &str) -> Option<&'a [u8]>
+{
+    let shdr = elf.section_headers.iter().find(|shdr| elf.shdr_strtab.get_at(shdr.sh_name) == Some(name))?;
+
+    return_section_range(shdr, data)
+}
+
+
+/* File bytes of a section, None for SHT_NOBITS or when sh_offset + sh_size overflows or runs past the end of the file */
+fn return_section_range<'a>(shdr: &goblin::elf::SectionHeader, data: &'a [u8]) -> Option<&'a [u8]>
 {
-    let hdr_etype: u16 = elf.header.e_type;
-            
-    let etype_variant: ElfObjectType = match hdr_etype as u16
+    if shdr.sh_type == SHT_NOBITS
     {
-        0 => ElfObjectType::EtNone,
-        1 => ElfObjectType::EtRel,
-        2 => ElfObjectType::EtExec,
-        3 => ElfObjectType::EtDyn,
-        4 => ElfObjectType::EtCore,
+        return None;
+    }
 
-        _ => ElfObjectType::EtNone
-    };
+    let end: u64 = shdr.sh_offset.checked_add(shdr.sh_size)?;
 
-    etype_variant.get_type()
+    data.get(usize::try_from(shdr.sh_offset).ok()?..usize::try_from(end).ok()?)
 }
 
 
-fn return_hdr_magic(magic: &[u8; 16]) -> (String, u8, u8, u8)
+/* File backed (code, read-only data, writable data) bytes of the allocated sections, BSS is left out */
+fn compute_code_data_sizes(elf: &Elf, data: &[u8]) -> (u64, u64, u64)
 {
-    let (
-        elf_mag0, elf_mag1, elf_mag2, elf_mag3,
-        elf_class, elf_data, elf_version
-    ): 
-    (&u8, u8, u8, u8, u8, u8, u8) = (
-        &magic[0], // 0x7f
-        magic[1],  // 'E'
-        magic[2],  // 'L'
-        magic[3],  // 'F',
-        magic[4],  // CLASS
-        magic[5],  // DATA 
-        magic[6]   // VERSION
-    );
-
-    let mut i: usize = 0;
-    let mut magic_vector: Vec<String> = Vec::new();
-
-    while i < ELF_MAGIC_LEN
+    let (mut code_bytes, mut ro_data_bytes, mut rw_data_bytes): (u64, u64, u64) = (0, 0, 0);
+
+    for shdr in elf.section_headers.iter().filter(|shdr| shdr.sh_type != SHT_NOBITS)
     {
-        /* Start ELF MAGIC struct packing operations */
-        match i as i32 
-        {
-            0 => { println!("\n* Packing ELFMAG0 => {elf_mag0}"); magic_vector.push(format!("{:02x?}", elf_mag0)) },
-            1 => { println!("* Packing ELFMAG1 => {elf_mag1} ({})", elf_mag1 as char); magic_vector.push(format!("{:02x?}", elf_mag1)) },
-            2 => { println!("* Packing ELFMAG2 => {elf_mag2} ({})", elf_mag2 as char); magic_vector.push(format!("{:02x?}", elf_mag2)) },
-            3 => { println!("* Packing ELFMAG3 => {elf_mag3} ({})\n", elf_mag3 as char); magic_vector.push(format!("{:02x?}", elf_mag3)) },
+        /* A truncated file cannot hold more than what is actually there */
+        let size: u64 = shdr.sh_size.min((data.len() as u64).saturating_sub(shdr.sh_offset));
+        let flags: u32 = shdr.sh_flags as u32;
 
-            _ => ()
+        if flags & SHF_EXECINSTR != 0
+        {
+            code_bytes += size;
+        }
+        else if flags & SHF_ALLOC != 0 && flags & SHF_WRITE != 0
+        {
+            rw_data_bytes += size;
+        }
+        else if flags & SHF_ALLOC != 0
+        {
+            ro_data_bytes += size;
         }
-        
-        i += 1;
     }
 
-    for (mut i, v) in magic.into_iter().skip(ELF_MAGIC_LEN).enumerate()
+    (code_bytes, ro_data_bytes, rw_data_bytes)
+}
+
+
+/* Re-indent XML one element per line, good enough for manifests (no mixed content) */
+fn return_pretty_xml(xml: &str) -> String
+{
+    let mut pretty: String = String::new();
+    let mut depth: usize = 0;
+
+    for token in xml.split_inclusive('>').flat_map(|chunk| match chunk.find('<')
+    {
+        Some(0) | None => vec![chunk],
+        Some(tag) => vec![&chunk[..tag], &chunk[tag..]]
+    })
     {
-        let hex_pair: String = format!("{:02x?}", v);
-     
-        magic_vector.push(hex_pair);
-        i += 1;
+        let token: &str = token.trim();
 
-        if i == magic.len()
+        if token.is_empty()
         {
-            print!("\n");
+            continue;
+        }
 
-            break;
+        if token.starts_with("</")
+        {
+            depth = depth.saturating_sub(1);
         }
-    }
 
-    if magic_vector.len() < magic.len()
-    {
-        eprintln!("Error - vector (magic_vector) and vector (magic) don\'t match in length!");
+        pretty.push_str(&format!("{}{}\n", "  ".repeat(depth), token));
 
-        std::process::exit(-1);
+        if token.starts_with('<') && !token.starts_with("</") && !token.starts_with("<?") && !token.starts_with("<!") && !token.ends_with("/>")
+        {
+            depth += 1;
+        }
     }
 
-    (magic_vector.join(" "), elf_class, elf_data, elf_version)
+    pretty
 }
 
 
-fn parse_args() -> Option<Arguments>
+/* Format from the first 16 bytes only */
+fn return_file_type(magic: &[u8]) -> String
 {
-    let args: Vec<String> = env::args().skip(1).collect();
+    let word: u32 = magic.get(4..8).map_or(0, |bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
 
-    Some(Arguments { 
-        file: match (args.len() < 1) as bool 
-        { 
-            true => {
-                send_help();
-                "NULL".to_string()
-            },
+    match magic
+    {
+        [0x7f, b'E', b'L', b'F', class, data, ..] => format!("ELF {} {}",
+            match *class { 1 => "32-bit", 2 => "64-bit", _ => "(invalid class)" },
+            match *data { 1 => "LSB", 2 => "MSB", _ => "(invalid data encoding)" }
+        ),
+
+        [b'M', b'Z', ..] => String::from("PE/COFF (MZ executable)"),
+        [0xfe, 0xed, 0xfa, 0xce, ..] | [0xce, 0xfa, 0xed, 0xfe, ..] => String::from("Mach-O 32-bit"),
+        [0xfe, 0xed, 0xfa, 0xcf, ..] | [0xcf, 0xfa, 0xed, 0xfe, ..] => String::from("Mach-O 64-bit"),
+
+        /* Shared magic: a fat header holds a small arch count, a class file its (minor, major) version (major >= 45) */
+        [0xca, 0xfe, 0xba, 0xbe, ..] if word < 30 => format!("Mach-O universal (fat) binary, {word} architectures"),
+        [0xca, 0xfe, 0xba, 0xbe, ..] => format!("Java class file, version {}.{}", word & 0xffff, word >> 16),
+        [0xca, 0xfe, 0xba, 0xbf, ..] => format!("Mach-O universal (fat, 64-bit offsets) binary, {word} architectures"),
+
+        [0x00, b'a', b's', b'm', ..] => String::from("WebAssembly module"),
+        [b'!', b'<', b'a', b'r', b'c', b'h', b'>', b'\n', ..] => String::from("ar archive (static library)"),
+        [b'd', b'e', b'x', b'\n', ..] => String::from("Android DEX"),
+        [0x1f, 0x8b, ..] => String::from("gzip compressed data"),
+        [b'B', b'Z', b'h', ..] => String::from("bzip2 compressed data"),
+        [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => String::from("xz compressed data"),
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => String::from("zstd compressed data"),
+        [b'7', b'z', 0xbc, 0xaf, 0x27, 0x1c, ..] => String::from("7-zip archive"),
+        [b'P', b'K', 0x03, 0x04, ..] => String::from("ZIP archive (jar/apk/docx)"),
+        [b'%', b'P', b'D', b'F', ..] => String::from("PDF document"),
+        [0x89, b'P', b'N', b'G', ..] => String::from("PNG image"),
+        [b'#', b'!', ..] => String::from("Script (shebang)"),
+
+        /* zlib: deflate CMF byte, and (CMF << 8 | FLG) is a multiple of 31 */
+        [0x78, flg, ..] if (0x7800 | *flg as u16).is_multiple_of(31) => String::from("zlib compressed data"),
+
+        [] => String::from("Empty file"),
+        _ => String::from("Unknown format")
+    }
+}
 
-            false => args[0].clone()
-        },
 
-        optional_param: match (args.len() >= 2) as bool { true => args[1].clone(), false => "NULL".to_string() }
-    })
+fn read_le_u32(data: &[u8], offset: usize) -> Option<u32>
+{
+    data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
 }
 
 
-fn send_help() -> ()
+fn parse_args() -> Option<Arguments>
 {
-    let help: &str = r##"
-        BINARYMAGIC - v1.1 (ALPHA)
-        ==========================
-    
-        Arguments:
-        ----------
-
-            --sections      view the section header table of the ELF32/ELF64 binary      
-            --dyn-syms      view the dynamic symbol table of the ELF32/ELF64 binary
-            --dyn-libs      view the dynamic library table of the ELF32/ELF64 binary
-    "##;
-
-    println!("{}", help.unindent());
-
-    std::process::exit(0);
-}
\ No newline at end of file
+    let command: Command = CliArguments::command();
+    let matches: ArgMatches = command.clone().get_matches();
+    let cli: CliArguments = CliArguments::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    let output_format: OutputFormat = match (cli.fmt_yaml, cli.json, cli.machine_readable)
+    {
+        (true, _, _) => OutputFormat::Yaml,
+        (false, true, _) => OutputFormat::Json,
+        (false, false, true) => OutputFormat::Ndjson,
+        (false, false, false) => OutputFormat::Table
+    };
+
+    /* Box drawing characters are not safe on the terminals that need colors turned off */
+    let no_color: bool = cli.no_color;
+
+    let table_theme: String = match no_color
+    {
+        true => String::from("ascii-full"),
+        false => cli.fmt_table_theme.unwrap_or(String::from("utf8-borders"))
+    };
+
+    let number_format: NumberFormat = match cli.hex_offsets
+    {
+        true => NumberFormat::Hex,
+        false => NumberFormat::Decimal
+    };
+
+    /* clap already rejected anything outside the value_parser list */
+    let sort_symbols: Option<SymbolSortKey> = match cli.sort_symbols.as_deref()
+    {
+        Some("index") => Some(SymbolSortKey::Index),
+        Some("name") => Some(SymbolSortKey::Name),
+        Some("address") => Some(SymbolSortKey::Address { zero_last: cli.zero_last }),
+        Some("size") => Some(SymbolSortKey::Size),
+        Some("binding") => Some(SymbolSortKey::Binding),
+        Some("type") => Some(SymbolSortKey::Type),
+
+        _ => None
+    };
+
+    /* Analysis and check options given on the command line, the output options are global and stay out of the dispatch */
+    let given_options: Vec<&Arg> = command.get_arguments()
+        .filter(|arg| arg.get_long().is_some() && arg.get_help_heading() != Some(OUTPUT_HEADING))
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .collect();
+
+    /* Every option is an analysis flag, executed in the order given */
+    let mut ordered_flags: Vec<(usize, String)> = given_options.iter()
+        .filter_map(|arg| Some((matches.index_of(arg.get_id().as_str())?, format!("--{}", arg.get_long()?))))
+        .collect();
+
+    ordered_flags.sort();
+
+    let flags: Vec<String> = ordered_flags.into_iter().map(|(_, flag)| flag).collect();
+
+    /* Free form String values only, the typed numeric modifiers are read from `cli` */
+    let flag_values: HashMap<String, Vec<String>> = given_options.iter()
+        .filter(|arg| arg.get_action().takes_values())
+        .filter_map(|arg| Some((format!("--{}", arg.get_long()?), matches.try_get_many::<String>(arg.get_id().as_str()).ok()??.cloned().collect())))
+        .collect();
+
+    Some(Arguments { 
+        file: cli.file.unwrap_or_default(),
+        flags,
+        flag_values,
+        output_format,
+
+        document: RefCell::new(Mapping::new()),
+        streamed_records: StdCell::new(0),
+        exit_code: StdCell::new(0),
+
+        table_theme,
+        no_color,
+        quiet: cli.quiet,
+        number_format,
+        sort_symbols,
+
+        gadget_depth: cli.max_depth.map_or(DEFAULT_GADGET_DEPTH, |depth| depth as usize),
+        entry_depth: cli.depth.map_or(DEFAULT_ENTRY_DEPTH, |depth| depth as usize),
+        reloc_ns: cli.reloc_ns.map_or(DEFAULT_RELOC_NS, |ns| ns as u64)
+    })
+}
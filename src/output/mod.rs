@@ -0,0 +1,147 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* Table layout and the structured (YAML, JSON, NDJSON) output document */
+
+use super::*;
+
+impl Arguments
+{
+    pub(crate) fn new_table(&self) -> Table
+    {
+        let mut table: Table = make_table(&self.table_theme);
+
+        /* comfy_table only styles cells when it believes stdout is a terminal */
+        if self.no_color
+        {
+            table.force_no_tty();
+        }
+
+        table
+    }
+
+
+    pub(crate) fn is_structured_output(&self) -> bool
+    {
+        match self.output_format
+        {
+            OutputFormat::Table => false,
+            OutputFormat::Yaml | OutputFormat::Json | OutputFormat::Ndjson => true
+        }
+    }
+
+
+    /* Store the results of an analysis method under its own top level key */
+    pub(crate) fn insert_document<T: Serialize>(&self, key: &str, value: &T)
+    {
+        let value: Value = serde_yaml::to_value(value).expect("Failed to serialize analysis results!");
+
+        if let OutputFormat::Ndjson = self.output_format
+        {
+            self.stream_records(key, value);
+            return;
+        }
+
+        self.document.borrow_mut().insert(Value::String(key.to_string()), value);
+    }
+
+
+    /* NDJSON: lists are split into one object per element, every object is tagged with a "type" field */
+    fn stream_records(&self, key: &str, value: Value)
+    {
+        let record_type: &str = return_ndjson_type(key);
+
+        let records: Vec<Value> = match value
+        {
+            Value::Sequence(records) => records,
+            value => vec![value]
+        };
+
+        let mut stdout = std::io::stdout().lock();
+
+        for record in records
+        {
+            let mut object: Mapping = Mapping::new();
+
+            object.insert(Value::String(String::from("type")), Value::String(record_type.to_string()));
+
+            match record
+            {
+                Value::Mapping(fields) => object.extend(fields),
+                value => { object.insert(Value::String(String::from("value")), value); }
+            }
+
+            let line: String = serde_json::to_string(&object).expect("Failed to serialize NDJSON record!");
+
+            if writeln!(stdout, "{line}").is_err()
+            {
+                std::process::exit(-1);
+            }
+
+            self.streamed_records.set(self.streamed_records.get() + 1);
+        }
+    }
+
+
+    pub(crate) fn flush_document(&self)
+    {
+        let document = self.document.borrow();
+
+        if let OutputFormat::Ndjson = self.output_format
+        {
+            let mut summary: Mapping = Mapping::new();
+
+            summary.insert(Value::String(String::from("type")), Value::String(String::from("summary")));
+            summary.insert(Value::String(String::from("file")), Value::String(self.file.clone()));
+            summary.insert(Value::String(String::from("records")), Value::from(self.streamed_records.get() as u64));
+
+            println!("{}", serde_json::to_string(&summary).expect("Failed to serialize NDJSON summary!"));
+            return;
+        }
+
+        if !self.is_structured_output() || document.is_empty()
+        {
+            return;
+        }
+
+        match self.output_format
+        {
+            OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&*document).expect("Failed to serialize YAML document!")),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&*document).expect("Failed to serialize JSON document!")),
+            OutputFormat::Ndjson | OutputFormat::Table => ()
+        }
+    }
+}
+
+
+fn make_table(theme: &str) -> Table
+{
+    let preset: &str = TABLE_THEMES.iter()
+        .find(|(name, _)| *name == theme)
+        .map_or(UTF8_BORDERS_ONLY, |(_, preset)| preset);
+
+    let mut table: Table = Table::new();
+
+    table.load_preset(preset)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    table
+}
+
+
+/* NDJSON "type" of the objects produced from one insert_document key */
+fn return_ndjson_type(key: &str) -> &str
+{
+    match key
+    {
+        "sections" | "wasm_sections" => "section",
+        "symbols" | "annotated_symbols" | "macho_symbols" => "symbol",
+        "libraries" | "wasm_imports" | "reconstructed_imports" | "macho_imports" | "pe_imports" => "import",
+        "wasm_exports" | "macho_exports" => "export",
+
+        _ => key
+    }
+}
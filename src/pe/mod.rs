@@ -0,0 +1,1647 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* PE analysis */
+
+use super::*;
+
+impl ProgramArgumentMethod
+{
+    pub(crate) fn start_pe_method_selector(&self, args: &Arguments, pe_obj: &PE, data: &[u8], flag: &str)
+    {
+        match &self
+        {
+            ProgramArgumentMethod::PeDebug => args.parse_pe_debug_dir(pe_obj, data),
+            ProgramArgumentMethod::PeClr => args.parse_pe_clr_header(pe_obj, data),
+            ProgramArgumentMethod::PeCodeSign => args.parse_pe_authenticode(pe_obj, data),
+            ProgramArgumentMethod::HashCompare => args.hash_compare(None, data),
+            ProgramArgumentMethod::PeResources => args.parse_pe_resources(pe_obj, data),
+            ProgramArgumentMethod::Security => args.parse_pe_security(pe_obj, data),
+            ProgramArgumentMethod::PeImportsFilter => args.parse_pe_imports_filter(pe_obj),
+            ProgramArgumentMethod::PeCfg => args.parse_pe_cfg(pe_obj, data),
+            ProgramArgumentMethod::PeEntropy => args.parse_pe_entropy(pe_obj, data),
+            ProgramArgumentMethod::PeLoadConfig => args.parse_pe_load_config(pe_obj, data),
+            ProgramArgumentMethod::PeBaseRelocs => args.parse_pe_base_relocs(pe_obj, data),
+            ProgramArgumentMethod::PeLoadTime => args.parse_pe_load_time_estimate(pe_obj, data),
+
+            _ => {
+                eprintln!("Error - option \"{}\" is not supported for PE binaries!", flag);
+                std::process::exit(-1);
+            }
+        }
+    }
+}
+
+impl Arguments
+{
+    fn parse_pe_debug_dir(&self, pe_obj: &PE, data: &[u8])
+    {
+        let debug_dir = pe_obj.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_debug_table());
+
+        /* Each IMAGE_DEBUG_DIRECTORY entry is 28 bytes, goblin only exposes the first one */
+        let entries: Vec<PeDebugRecord> = match debug_dir.and_then(|dd| return_pe_rva_offset(pe_obj, dd.virtual_address).map(|offset| (offset, dd.size)))
+        {
+            Some((offset, size)) => (0..(size as usize / PE_DEBUG_DIRECTORY_SIZE)).filter_map(|i| {
+                let entry: usize = offset + i * PE_DEBUG_DIRECTORY_SIZE;
+
+                let debug_type: u32 = read_le_u32(data, entry + 12)?;
+                let size_of_data: u32 = read_le_u32(data, entry + 16)?;
+                let rva: u32 = read_le_u32(data, entry + 20)?;
+                let file_offset: u32 = read_le_u32(data, entry + 24)?;
+
+                let mut record: PeDebugRecord = PeDebugRecord {
+                    debug_type: return_pe_debug_type(debug_type),
+                    size: size_of_data,
+                    rva,
+                    file_offset,
+                    pdb_path: None,
+                    guid: None,
+                    age: None,
+                    pdb_exists: None
+                };
+
+                /* CodeView RSDS record: signature, GUID, age, NUL terminated PDB path */
+                if debug_type == IMAGE_DEBUG_TYPE_CODEVIEW && read_le_u32(data, file_offset as usize) == Some(CODEVIEW_PDB70_MAGIC)
+                {
+                    let cv: usize = file_offset as usize;
+                    let path_end: usize = (cv + size_of_data as usize).min(data.len());
+
+                    let pdb_path: String = data.get(cv + 24..path_end).map_or(String::new(), |path| {
+                        String::from_utf8_lossy(path.split(|&b| b == 0).next().unwrap_or(&[])).to_string()
+                    });
+
+                    record.guid = data.get(cv + 4..cv + 20).map(return_pe_guid);
+                    record.age = read_le_u32(data, cv + 20);
+                    record.pdb_exists = Some(Path::new(&pdb_path).exists());
+                    record.pdb_path = Some(pdb_path);
+                }
+
+                Some(record)
+            }).collect(),
+
+            None => Vec::new()
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_debug", &entries);
+            return;
+        }
+
+        println!("\nPE Debug Directory =>");
+
+        if entries.is_empty()
+        {
+            println!("\n* No debug directory present in this binary.");
+            return;
+        }
+
+        let mut debug_table: Table = self.new_table();
+
+        debug_table.set_header(vec![
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("RVA").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new(format!("Offset {OMEGA}")).fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for entry in &entries
+        {
+            debug_table.add_row(vec![
+                Cell::new(&entry.debug_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(format!("{} bytes", entry.size)),
+                Cell::new(format!("{:#x}", entry.rva)),
+                Cell::new(format!("{:#x}", entry.file_offset))
+            ]);
+        }
+
+        println!("\n{debug_table}");
+
+        for entry in entries.iter().filter(|entry| entry.pdb_path.is_some())
+        {
+            println!("\n* CodeView (RSDS) record:");
+            println!("\tPDB path >> {}", entry.pdb_path.as_deref().unwrap_or(""));
+            println!("\tGUID     >> {}", entry.guid.as_deref().unwrap_or("Not defined"));
+            println!("\tAge      >> {}", entry.age.unwrap_or(0));
+
+            match entry.pdb_exists
+            {
+                Some(true) => println!("\tOn disk  >> {CHECK} PDB found at the embedded path"),
+                _ => println!("\tOn disk  >> {CROSS} PDB not found on this system")
+            }
+        }
+    }
+
+
+    /* Data directory 14 points at the IMAGE_COR20_HEADER of managed (.NET) assemblies */
+    fn parse_pe_clr_header(&self, pe_obj: &PE, data: &[u8])
+    {
+        let clr_dir = pe_obj.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_clr_runtime_header());
+
+        let cor20: usize = match clr_dir.and_then(|dd| return_pe_rva_offset(pe_obj, dd.virtual_address))
+        {
+            Some(offset) => offset,
+
+            None => {
+                eprintln!("Error - binary has no CLR header, it is not a managed (.NET) assembly!");
+                std::process::exit(-1);
+            }
+        };
+
+        let (major, minor): (u16, u16) = (read_le_u16(data, cor20 + 4).unwrap_or(0), read_le_u16(data, cor20 + 6).unwrap_or(0));
+        let metadata_rva: u32 = read_le_u32(data, cor20 + 8).unwrap_or(0);
+        let metadata_size: u32 = read_le_u32(data, cor20 + 12).unwrap_or(0);
+        let flags: u32 = read_le_u32(data, cor20 + 16).unwrap_or(0);
+        let entry_point: u32 = read_le_u32(data, cor20 + 20).unwrap_or(0);
+        let strong_name_rva: u32 = read_le_u32(data, cor20 + 32).unwrap_or(0);
+        let strong_name_size: u32 = read_le_u32(data, cor20 + 36).unwrap_or(0);
+
+        /* Metadata root: "BSJB", major, minor, reserved, length, then the runtime version string ("v4.0.30319") */
+        let metadata_version: Option<String> = return_pe_rva_offset(pe_obj, metadata_rva)
+            .filter(|&root| read_le_u32(data, root) == Some(CLR_METADATA_MAGIC))
+            .and_then(|root| {
+                let length: usize = read_le_u32(data, root + 12)? as usize;
+
+                data.get(root + 16..root + 16 + length).map(|version| {
+                    String::from_utf8_lossy(version.split(|&b| b == 0).next().unwrap_or(&[])).to_string()
+                })
+            });
+
+        let dll_characteristics: u16 = pe_obj.header.optional_header.map_or(0, |opt_hdr| opt_hdr.windows_fields.dll_characteristics);
+
+        let clr: ClrHeaderRecord = ClrHeaderRecord {
+            runtime_version: format!("{major}.{minor}"),
+            metadata_version,
+            flags: return_clr_flags(flags),
+            metadata_rva,
+            metadata_size,
+
+            /* NATIVE_ENTRYPOINT switches the field from a metadata token to an RVA */
+            entry_point: match (entry_point, flags & COMIMAGE_FLAGS_NATIVE_ENTRYPOINT != 0)
+            {
+                (0, _) => None,
+                (rva, true) => Some(format!("RVA {rva:#x}")),
+                (token, false) => Some(format!("Token {token:#010x}"))
+            },
+
+            strong_name_rva,
+            strong_name_size,
+            strong_name_signed: flags & COMIMAGE_FLAGS_STRONGNAMESIGNED != 0 && strong_name_size != 0,
+
+            platform: match (flags & COMIMAGE_FLAGS_32BITREQUIRED != 0, flags & COMIMAGE_FLAGS_32BITPREFERRED != 0)
+            {
+                (true, true) => String::from("AnyCPU (32-bit preferred)"),
+                (true, false) => String::from("x86 only (32BIT_REQUIRED)"),
+
+                _ => String::from("AnyCPU")
+            },
+
+            aslr: dll_characteristics & IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE != 0
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_clr", &clr);
+            return;
+        }
+
+        println!("\nCLR Header =>");
+
+        let mut clr_table: Table = self.new_table();
+
+        clr_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (property, value) in [
+            ("CLR Runtime Version", clr.runtime_version.clone()),
+            ("Metadata Version", clr.metadata_version.clone().unwrap_or_else(|| String::from("Unknown"))),
+            ("Flags", match clr.flags.is_empty() { true => String::from("None"), false => clr.flags.join(" | ") }),
+            ("Metadata", format!("RVA {:#x} ({} bytes)", clr.metadata_rva, clr.metadata_size)),
+            ("Entry Point", clr.entry_point.clone().unwrap_or_else(|| String::from("None (library)"))),
+            ("Strong Name", match clr.strong_name_signed
+            {
+                true => format!("Signed (RVA {:#x}, {} bytes)", clr.strong_name_rva, clr.strong_name_size),
+                false => String::from("Not signed")
+            }),
+            ("Platform", clr.platform.clone()),
+            ("ASLR (DYNAMIC_BASE)", match clr.aslr { true => String::from("Enabled"), false => String::from("Disabled") })
+        ]
+        {
+            clr_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{clr_table}");
+    }
+
+
+    /* Informational only, the signature is decoded but never validated against a trusted root */
+    fn parse_pe_authenticode(&self, pe_obj: &PE, data: &[u8])
+    {
+        let cert_dir = pe_obj.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_certificate_table());
+
+        let mut record: AuthenticodeRecord = AuthenticodeRecord {
+            signed: false,
+            table_offset: 0,
+            table_size: 0,
+            revision: None,
+            certificate_type: None,
+            decoded: false,
+            signer_cn: None,
+            issuer_cn: None,
+            not_before: None,
+            not_after: None,
+            expired: None
+        };
+
+        /* Unlike every other data directory, the certificate table address is a file offset rather than an RVA */
+        if let Some(dd) = cert_dir.filter(|dd| dd.virtual_address != 0 && dd.size != 0)
+        {
+            let table: usize = dd.virtual_address as usize;
+
+            record.signed = true;
+            record.table_offset = dd.virtual_address;
+            record.table_size = dd.size;
+
+            /* WIN_CERTIFICATE: dwLength, wRevision, wCertificateType, bCertificate[] */
+            let length: usize = read_le_u32(data, table).unwrap_or(0) as usize;
+            let cert_type: u16 = read_le_u16(data, table + 6).unwrap_or(0);
+
+            record.revision = read_le_u16(data, table + 4).map(|revision| match revision
+            {
+                0x0100 => String::from("1.0 (WIN_CERT_REVISION_1_0)"),
+                0x0200 => String::from("2.0 (WIN_CERT_REVISION_2_0)"),
+
+                _ => format!("Unknown ({revision:#06x})")
+            });
+
+            record.certificate_type = Some(String::from(match cert_type
+            {
+                WIN_CERT_TYPE_X509 => "WIN_CERT_TYPE_X509",
+                WIN_CERT_TYPE_PKCS_SIGNED_DATA => "WIN_CERT_TYPE_PKCS_SIGNED_DATA",
+                WIN_CERT_TYPE_RESERVED_1 => "WIN_CERT_TYPE_RESERVED_1",
+                WIN_CERT_TYPE_TS_STACK_SIGNED => "WIN_CERT_TYPE_TS_STACK_SIGNED",
+
+                _ => "Unknown"
+            }));
+
+            let signer: Option<Certificate> = match cert_type
+            {
+                WIN_CERT_TYPE_PKCS_SIGNED_DATA => data.get(table + 8..table + length.max(8)).and_then(return_authenticode_signer),
+                _ => None
+            };
+
+            if let Some(cert) = signer
+            {
+                let validity = &cert.tbs_certificate.validity;
+                let now: std::time::Duration = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+
+                record.decoded = true;
+                record.signer_cn = return_x509_common_name(&cert.tbs_certificate.subject);
+                record.issuer_cn = return_x509_common_name(&cert.tbs_certificate.issuer);
+                record.not_before = Some(validity.not_before.to_string());
+                record.not_after = Some(validity.not_after.to_string());
+                record.expired = Some(validity.not_after.to_unix_duration() < now);
+            }
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_code_sign", &record);
+            return;
+        }
+
+        println!("\nAuthenticode Signature =>");
+
+        if !record.signed
+        {
+            println!("\n* No certificate table present, the binary is not signed.");
+            return;
+        }
+
+        let mut sign_table: Table = self.new_table();
+
+        sign_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let unknown = || String::from("Unknown");
+
+        for (property, value) in [
+            ("Certificate Table", format!("Offset {:#x} ({} bytes)", record.table_offset, record.table_size)),
+            ("Revision", record.revision.clone().unwrap_or_else(unknown)),
+            ("Certificate Type", record.certificate_type.clone().unwrap_or_else(unknown)),
+            ("Signature Decoded", match record.decoded { true => String::from("Yes"), false => String::from("No") }),
+            ("Signer CN", record.signer_cn.clone().unwrap_or_else(unknown)),
+            ("Issuer CN", record.issuer_cn.clone().unwrap_or_else(unknown)),
+            ("Valid From", record.not_before.clone().unwrap_or_else(unknown)),
+            ("Valid Until", record.not_after.clone().unwrap_or_else(unknown))
+        ]
+        {
+            sign_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{sign_table}");
+
+        if record.expired == Some(true)
+        {
+            println!("\n* WARNING: the signing certificate has expired (no chain validation is performed).");
+        }
+    }
+
+    /* Resource tree: type directory -> name/ID directory -> language directory -> IMAGE_RESOURCE_DATA_ENTRY */
+    fn parse_pe_resources(&self, pe_obj: &PE, data: &[u8])
+    {
+        let resource_dir = pe_obj.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_resource_table());
+
+        let base: usize = match resource_dir.and_then(|dd| return_pe_rva_offset(pe_obj, dd.virtual_address))
+        {
+            Some(offset) => offset,
+
+            None => {
+                eprintln!("Error - binary has no resource directory!");
+                std::process::exit(-1);
+            }
+        };
+
+        let mut resources: Vec<PeResourceRecord> = Vec::new();
+
+        for (type_id, type_offset) in return_resource_directory_entries(data, base, base)
+        {
+            let resource_type: String = match type_id
+            {
+                Ok(id) => return_pe_resource_type(id),
+                Err(name) => name
+            };
+
+            for (name_id, name_offset) in return_resource_subdirectory(data, base, type_offset)
+            {
+                let name: String = match name_id
+                {
+                    Ok(id) => format!("#{id}"),
+                    Err(name) => name
+                };
+
+                for (language_id, data_entry) in return_resource_subdirectory(data, base, name_offset)
+                {
+                    /* A data entry never lives behind the subdirectory bit, anything else is a malformed tree */
+                    if data_entry & IMAGE_RESOURCE_DATA_IS_DIRECTORY != 0
+                    {
+                        continue;
+                    }
+
+                    let entry: usize = base + data_entry as usize;
+
+                    resources.push(PeResourceRecord {
+                        resource_type: resource_type.clone(),
+                        name: name.clone(),
+                        language: language_id.unwrap_or(0),
+                        data_rva: read_le_u32(data, entry).unwrap_or(0),
+                        size: read_le_u32(data, entry + 4).unwrap_or(0)
+                    });
+                }
+            }
+        }
+
+        /* Application manifest, embedded as UTF-8 XML under RT_MANIFEST */
+        let manifest: Option<String> = resources.iter()
+            .find(|resource| resource.resource_type == return_pe_resource_type(RT_MANIFEST))
+            .and_then(|resource| {
+                let offset: usize = return_pe_rva_offset(pe_obj, resource.data_rva)?;
+                let bytes: &[u8] = data.get(offset..offset + resource.size as usize)?;
+
+                Some(String::from_utf8_lossy(bytes).trim_start_matches('\u{feff}').to_string())
+            });
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_resources", &resources);
+
+            if let Some(manifest) = &manifest
+            {
+                self.insert_document("pe_manifest", manifest);
+            }
+
+            return;
+        }
+
+        println!("\nPE Resources =>");
+
+        let mut resource_table: Table = self.new_table();
+
+        resource_table.set_header(vec![
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Language").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Data RVA").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let mut previous_type: Option<&str> = None;
+
+        for resource in &resources
+        {
+            /* Tree view, the type is only printed on the first entry of its subtree */
+            let type_cell: Cell = match previous_type == Some(resource.resource_type.as_str())
+            {
+                true => Cell::new(""),
+                false => Cell::new(&resource.resource_type).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            };
+
+            resource_table.add_row(vec![
+                type_cell,
+                Cell::new(format!("\u{2514}\u{2500} {}", resource.name)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{:#06x}", resource.language)).fg(Color::Yellow),
+                Cell::new(format!("{:#x}", resource.data_rva)),
+                Cell::new(format!("{} bytes", resource.size))
+            ]);
+
+            previous_type = Some(resource.resource_type.as_str());
+        }
+
+        println!("\n{resource_table}");
+        println!("\n{} resources.", resources.len());
+
+        if let Some(manifest) = &manifest
+        {
+            println!("\nManifest =>\n\n{}", return_pretty_xml(manifest));
+        }
+    }
+
+    fn parse_pe_imports_filter(&self, pe_obj: &PE)
+    {
+        let pattern: &str = match self.get_flag_values("--pe-imports-filter").first()
+        {
+            Some(pattern) => pattern,
+
+            None => {
+                eprintln!("Error - usage: binarymagic <TARGET> --pe-imports-filter <dll-name|glob>");
+                std::process::exit(-1);
+            }
+        };
+
+        /* DLL names are case insensitive on Windows (KERNEL32.dll == kernel32.DLL) */
+        let matcher: WildMatch = WildMatch::new_case_insensitive(pattern);
+
+        let records: Vec<PeImportRecord> = pe_obj.imports.iter()
+            .filter(|import| matcher.matches(import.dll))
+            .map(|import| PeImportRecord {
+                dll: import.dll.to_string(),
+                name: import.name.to_string(),
+                ordinal: import.ordinal,
+                iat_rva: import.offset
+            })
+            .collect();
+
+        if records.is_empty()
+        {
+            eprintln!("DLL {pattern} not imported by this binary");
+            std::process::exit(1);
+        }
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_imports", &records);
+            return;
+        }
+
+        println!("\nPE Imports ({pattern}) =>");
+
+        /* Keep the import directory order of the DLLs */
+        let mut libraries: Vec<&str> = Vec::new();
+
+        for record in &records
+        {
+            if !libraries.contains(&record.dll.as_str())
+            {
+                libraries.push(&record.dll);
+            }
+        }
+
+        for library in &libraries
+        {
+            let mut import_table: Table = self.new_table();
+
+            import_table.set_header(vec![
+                    Cell::new(library).fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("Ordinal/Hint").fg(Color::Green).add_attribute(Attribute::Bold),
+                    Cell::new("IAT RVA").fg(Color::Green).add_attribute(Attribute::Bold)
+                ]);
+
+            for record in records.iter().filter(|record| record.dll == *library)
+            {
+                import_table.add_row(vec![
+                    Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                    Cell::new(record.ordinal).fg(Color::Yellow),
+                    Cell::new(format!("{:#x}", record.iat_rva)).fg(Color::Yellow)
+                ]);
+            }
+
+            println!("\n{import_table}");
+        }
+
+        println!("\n{} imports from {} libraries: {}", records.len(), libraries.len(), libraries.join(", "));
+    }
+
+    fn parse_pe_cfg(&self, pe_obj: &PE, data: &[u8])
+    {
+        let cfg: PeCfgRecord = return_pe_cfg(pe_obj, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_cfg", &cfg);
+            return;
+        }
+
+        println!("\nControl Flow Guard =>");
+
+        let mut cfg_table: Table = self.new_table();
+
+        cfg_table.set_header(vec![
+                Cell::new("Property").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let enabled = |flag: bool| -> String {
+            match flag
+            {
+                true => String::from("Enabled"),
+                false => String::from("Disabled")
+            }
+        };
+
+        let address = |va: Option<u64>| -> String {
+            match va
+            {
+                Some(0) => String::from("None"),
+                Some(va) => format!("{va:#x}"),
+                None => String::from("N/A (load config too small)")
+            }
+        };
+
+        for (property, value) in [
+            ("CFG (GUARD_CF)", enabled(cfg.enabled)),
+            ("CF Instrumented", enabled(cfg.instrumented)),
+            ("Export Suppression", enabled(cfg.export_suppression)),
+            ("XFG", enabled(cfg.xfg)),
+            ("Guard Flags", match cfg.guard_flags.is_empty() { true => String::from("None"), false => cfg.guard_flags.join(" | ") }),
+            ("Check Function Pointer", address(cfg.check_function_pointer)),
+            ("Dispatch Function Pointer", address(cfg.dispatch_function_pointer)),
+            ("CF Function Table", address(cfg.function_table)),
+            ("CF Function Count", cfg.function_count.map_or(String::from("N/A"), |count| count.to_string())),
+            ("EH Continuation Table", address(cfg.eh_continuation_table)),
+            ("EH Continuation Count", cfg.eh_continuation_count.map_or(String::from("N/A"), |count| count.to_string()))
+        ]
+        {
+            cfg_table.add_row(vec![
+                Cell::new(property).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{cfg_table}");
+
+        /* The function table lists valid indirect call targets, not the call sites themselves */
+        if let Some(count) = cfg.function_count.filter(|_| cfg.enabled)
+        {
+            println!("\n{count} functions are valid CFG indirect call targets.");
+        }
+    }
+
+
+    fn parse_pe_load_config(&self, pe_obj: &PE, data: &[u8])
+    {
+        let load_config: PeLoadConfigRecord = return_pe_load_config(pe_obj, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_load_config", &load_config);
+            return;
+        }
+
+        if !load_config.present
+        {
+            println!("\nNo load configuration directory in this binary (no /GS cookie, SafeSEH or CFG metadata).");
+            return;
+        }
+
+        println!("\nLoad Configuration Directory =>");
+
+        let mut load_config_table: Table = self.new_table();
+
+        load_config_table.set_header(vec![
+                Cell::new("Field").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Value").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Meaning").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        let address = |va: Option<u64>| -> String {
+            match va
+            {
+                Some(0) => String::from("None"),
+                Some(va) => format!("{va:#x}"),
+                None => String::from("N/A")
+            }
+        };
+
+        let count = |value: Option<u64>| -> String { value.map_or(String::from("N/A"), |value| value.to_string()) };
+
+        for (field, value, meaning) in [
+            ("Size", load_config.size.to_string(), String::from("directory size, older toolchains emit fewer fields")),
+            ("TimeDateStamp", format!("{:#x}", load_config.time_date_stamp), String::new()),
+            ("Version", load_config.version.clone(), String::new()),
+            ("SecurityCookie", address(load_config.security_cookie), match load_config.stack_cookie
+            {
+                true => String::from("stack cookie (/GS) protection"),
+                false => String::from("no stack cookie")
+            }),
+            ("SEHandlerTable", address(load_config.se_handler_table), load_config.se_handler_table_rva.map_or(String::new(), |rva| format!("RVA {rva:#x} (SafeSEH)"))),
+            ("SEHandlerCount", count(load_config.se_handler_count), String::from("registered exception handlers")),
+            ("GuardCFCheckFunctionPointer", address(load_config.guard_cf_check_function_pointer), String::from("CFG check routine")),
+            ("GuardCFDispatchFunctionPointer", address(load_config.guard_cf_dispatch_function_pointer), String::from("CFG dispatch routine")),
+            ("GuardCFFunctionTable", address(load_config.guard_cf_function_table), String::from("valid indirect call targets")),
+            ("GuardCFFunctionCount", count(load_config.guard_cf_function_count), String::new()),
+            ("GuardFlags", load_config.guard_flags.map_or(String::from("N/A"), |flags| format!("{flags:#010x}")), match load_config.guard_flag_names.is_empty()
+            {
+                true => String::from("None"),
+                false => load_config.guard_flag_names.join(" | ")
+            }),
+            ("DynamicValueRelocTable", address(load_config.dynamic_value_reloc_table), String::new()),
+            ("CHPEMetadataPointer", address(load_config.hybrid_metadata_pointer), String::from("hybrid metadata (ARM64X/ARM64EC)")),
+            ("GuardEHContinuationTable", address(load_config.guard_eh_continuation_table), String::new()),
+            ("GuardEHContinuationCount", count(load_config.guard_eh_continuation_count), String::new())
+        ]
+        {
+            load_config_table.add_row(vec![
+                Cell::new(field).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(value).fg(Color::Yellow),
+                Cell::new(meaning).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{load_config_table}");
+    }
+
+
+    fn parse_pe_security(&self, pe_obj: &PE, data: &[u8])
+    {
+        let dll_characteristics: u16 = pe_obj.header.optional_header.map_or(0, |opt_hdr| opt_hdr.windows_fields.dll_characteristics);
+
+        let base_relocs: PeBaseRelocRecord = return_pe_base_relocs(pe_obj, data);
+
+        let security: PeSecurityRecord = PeSecurityRecord {
+            nx: dll_characteristics & IMAGE_DLLCHARACTERISTICS_NX_COMPAT != 0,
+            aslr: dll_characteristics & IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE != 0,
+            high_entropy_va: dll_characteristics & IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA != 0,
+            cfg: return_pe_cfg(pe_obj, data).enabled,
+            stack_cookie: return_pe_load_config(pe_obj, data).stack_cookie,
+            base_relocations: base_relocs.relocation_count,
+            base_relocation_blocks: base_relocs.block_count
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("security", &security);
+            return;
+        }
+
+        println!("\nSecurity Checks =>");
+
+        let mut security_table: Table = self.new_table();
+
+        security_table.set_header(vec![
+                Cell::new("Check").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Status").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (check, status) in [
+            ("NX (NX_COMPAT)", security.nx),
+            ("ASLR (DYNAMIC_BASE)", security.aslr),
+            ("High Entropy VA", security.high_entropy_va),
+            ("CFG (GUARD_CF)", security.cfg),
+            ("Stack Cookie (/GS)", security.stack_cookie)
+        ]
+        {
+            security_table.add_row(vec![
+                Cell::new(check).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                match status
+                {
+                    true => Cell::new("Enabled").fg(Color::Green),
+                    false => Cell::new("Disabled").fg(Color::Red)
+                }
+            ]);
+        }
+
+        println!("\n{security_table}");
+
+        println!("\nBase relocations: {} in {} block(s), {} bytes of .reloc data", security.base_relocations, security.base_relocation_blocks, base_relocs.directory_size);
+
+        if security.aslr && security.base_relocations == 0
+        {
+            println!("Warning: DYNAMIC_BASE is set but there are no base relocations, the loader cannot rebase this image.");
+        }
+    }
+
+    fn parse_pe_base_relocs(&self, pe_obj: &PE, data: &[u8])
+    {
+        let base_relocs: PeBaseRelocRecord = return_pe_base_relocs(pe_obj, data);
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_base_relocs", &base_relocs);
+            return;
+        }
+
+        if !base_relocs.present
+        {
+            println!("\nNo base relocation directory (relocations stripped, the image can only load at its preferred base).");
+            return;
+        }
+
+        println!("\nBase Relocation Blocks =>");
+
+        let mut block_table: Table = self.new_table();
+
+        block_table.set_header(vec![
+                Cell::new("Page RVA").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Block Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Relocations").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Types").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for block in base_relocs.blocks.iter()
+        {
+            let types: Vec<String> = block.types.iter().map(|t| format!("{} x{}", t.type_name, t.count)).collect();
+
+            block_table.add_row(vec![
+                Cell::new(format!("{:#x}", block.page_rva)).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(block.block_size).fg(Color::Yellow),
+                Cell::new(block.relocations).fg(Color::Yellow),
+                Cell::new(types.join(", ")).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{block_table}");
+
+        let mut type_table: Table = self.new_table();
+
+        type_table.set_header(vec![
+                Cell::new("Type").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for type_count in base_relocs.types.iter()
+        {
+            type_table.add_row(vec![
+                Cell::new(&type_count.type_name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(type_count.count).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{type_table}");
+
+        println!("\nTotal relocations: {}", base_relocs.relocation_count);
+        println!("Blocks: {}", base_relocs.block_count);
+        println!("Directory size: {} bytes", base_relocs.directory_size);
+    }
+
+    fn parse_pe_load_time_estimate(&self, pe_obj: &PE, data: &[u8])
+    {
+        let base_relocs: PeBaseRelocRecord = return_pe_base_relocs(pe_obj, data);
+        let tls_callbacks: usize = return_pe_tls_callbacks(pe_obj, data).len();
+
+        let warm_ns: u64 = pe_obj.imports.len() as u64 * PE_IMPORT_NS
+            + base_relocs.relocation_count as u64 * PE_BASE_RELOC_NS
+            + tls_callbacks as u64 * PE_TLS_CALLBACK_NS;
+
+        let estimate: PeLoadTimeRecord = PeLoadTimeRecord {
+            imports: pe_obj.imports.len(),
+            imported_dlls: pe_obj.libraries.len(),
+            base_relocations: base_relocs.relocation_count,
+            relocated_pages: base_relocs.block_count,
+            tls_callbacks,
+            warm_load_ms: warm_ns as f64 / 1_000_000.0,
+            cold_load_ms: (warm_ns + base_relocs.block_count as u64 * PE_COLD_PAGE_NS) as f64 / 1_000_000.0,
+            potentially_slow: pe_obj.imports.len() > PE_SLOW_IMPORT_COUNT
+        };
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_load_time", &estimate);
+            return;
+        }
+
+        println!("\nLoad Time Estimate =>");
+
+        let mut estimate_table: Table = self.new_table();
+
+        estimate_table.set_header(vec![
+                Cell::new("Item").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Count").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Cost").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for (item, count, cost) in [
+            ("Imported functions", estimate.imports, format!("~{}µs each ({} DLLs)", PE_IMPORT_NS as f64 / 1000.0, estimate.imported_dlls)),
+            ("Base relocations", estimate.base_relocations, format!("~{}µs each", PE_BASE_RELOC_NS as f64 / 1000.0)),
+            ("Relocated pages", estimate.relocated_pages, format!("~{}µs each, cold only", PE_COLD_PAGE_NS as f64 / 1000.0)),
+            ("TLS callbacks", estimate.tls_callbacks, format!("~{}µs each", PE_TLS_CALLBACK_NS as f64 / 1000.0))
+        ]
+        {
+            estimate_table.add_row(vec![
+                Cell::new(item).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(count).fg(Color::Yellow),
+                Cell::new(cost).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{estimate_table}");
+        println!("\nEstimated cold load: ~{:.3}ms / warm load: ~{:.3}ms", estimate.cold_load_ms, estimate.warm_load_ms);
+        println!("(rough heuristic, compare builds of the same binary rather than reading it as an absolute time)");
+
+        if estimate.potentially_slow
+        {
+            println!("\nWARNING: {} imported functions (> {}), potentially slow to load.", estimate.imports, PE_SLOW_IMPORT_COUNT);
+        }
+    }
+
+    fn parse_pe_entropy(&self, pe_obj: &PE, data: &[u8])
+    {
+        let records: Vec<PeEntropyRecord> = pe_obj.sections.iter().map(|section| {
+            let name: String = section.name().unwrap_or("").to_string();
+
+            let start: usize = section.pointer_to_raw_data as usize;
+            let bytes: &[u8] = data.get(start..start.saturating_add(section.size_of_raw_data as usize)).unwrap_or(&[]);
+
+            let entropy: f64 = return_shannon_entropy(bytes);
+
+            PeEntropyRecord {
+                virtual_size: section.virtual_size,
+                raw_size: section.size_of_raw_data,
+                entropy,
+
+                indicator: match entropy > ENTROPY_HIGH_LIMIT
+                {
+                    true => String::from("HIGH"),
+                    false => String::from("NORMAL")
+                },
+
+                known_packer_name: PE_PACKER_SECTION_NAMES.iter().any(|packer| packer.eq_ignore_ascii_case(&name)),
+                name
+            }
+        }).collect();
+
+        if self.is_structured_output()
+        {
+            self.insert_document("pe_entropy", &records);
+            return;
+        }
+
+        println!("\nPE Section Entropy =>");
+
+        let mut entropy_table: Table = self.new_table();
+
+        entropy_table.set_header(vec![
+                Cell::new("Section Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Virtual Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Raw Size").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Entropy").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Packer Indicator").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &records
+        {
+            let indicator: String = match record.known_packer_name
+            {
+                true => format!("{} (known packer section name)", record.indicator),
+                false => record.indicator.clone()
+            };
+
+            entropy_table.add_row(vec![
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(format!("{} bytes", record.virtual_size)).fg(Color::Yellow),
+                Cell::new(format!("{} bytes", record.raw_size)).fg(Color::Yellow),
+                Cell::new(format!("{:.4}", record.entropy)).fg(Color::Yellow),
+
+                match record.indicator == "HIGH" || record.known_packer_name
+                {
+                    true => Cell::new(indicator).fg(Color::Red).add_attribute(Attribute::Bold),
+                    false => Cell::new(indicator).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+                }
+            ]);
+        }
+
+        println!("\n{entropy_table}");
+
+        if records.iter().any(|record| record.indicator == "HIGH" || record.known_packer_name)
+        {
+            println!("\n* The binary is likely packed or carries compressed/encrypted data.");
+        }
+    }
+}
+
+
+/* Empirical --pe-load-time costs: import lookup (name hash + export search), base relocation fixup,
+   TLS callback dispatch, and the page fault + copy-on-write of every relocated page when nothing is cached */
+const PE_IMPORT_NS: u64 = 1000;
+const PE_BASE_RELOC_NS: u64 = 100;
+const PE_TLS_CALLBACK_NS: u64 = 5000;
+const PE_COLD_PAGE_NS: u64 = 50_000;
+const PE_SLOW_IMPORT_COUNT: usize = 1000;
+
+/* Shannon entropy (bits per byte) above which section contents look compressed or encrypted */
+const ENTROPY_HIGH_LIMIT: f64 = 7.0;
+
+/* Section names left behind by common PE packers (UPX, NSIS, ASPack, MPRESS, Petite, ...) */
+const PE_PACKER_SECTION_NAMES: [&str; 12] = [
+    "UPX0", "UPX1", "UPX2", ".ndata", ".pack", ".packed", ".aspack", ".adata", ".MPRESS1", ".MPRESS2", ".petite", ".themida"
+];
+
+const PE_DEBUG_DIRECTORY_SIZE: usize = 28;
+const CODEVIEW_PDB70_MAGIC: u32 = 0x5344_5352;      /* "RSDS" */
+
+/* IMAGE_COR20_HEADER flags (not exported by goblin) */
+const COMIMAGE_FLAGS_ILONLY: u32 = 0x0000_0001;
+const COMIMAGE_FLAGS_32BITREQUIRED: u32 = 0x0000_0002;
+const COMIMAGE_FLAGS_IL_LIBRARY: u32 = 0x0000_0004;
+const COMIMAGE_FLAGS_STRONGNAMESIGNED: u32 = 0x0000_0008;
+const COMIMAGE_FLAGS_NATIVE_ENTRYPOINT: u32 = 0x0000_0010;
+const COMIMAGE_FLAGS_TRACKDEBUGDATA: u32 = 0x0001_0000;
+const COMIMAGE_FLAGS_32BITPREFERRED: u32 = 0x0002_0000;
+
+/* WIN_CERTIFICATE wCertificateType values */
+const WIN_CERT_TYPE_X509: u16 = 0x0001;
+const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+const WIN_CERT_TYPE_RESERVED_1: u16 = 0x0003;
+const WIN_CERT_TYPE_TS_STACK_SIGNED: u16 = 0x0004;
+
+const X509_COMMON_NAME_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.3");
+
+const CLR_METADATA_MAGIC: u32 = 0x424a_5342;        /* "BSJB" */
+const IMAGE_DLLCHARACTERISTICS_DYNAMIC_BASE: u16 = 0x0040;
+const IMAGE_DLLCHARACTERISTICS_HIGH_ENTROPY_VA: u16 = 0x0020;
+const IMAGE_DLLCHARACTERISTICS_NX_COMPAT: u16 = 0x0100;
+const IMAGE_DLLCHARACTERISTICS_GUARD_CF: u16 = 0x4000;
+
+/* IMAGE_LOAD_CONFIG_DIRECTORY GuardFlags */
+const IMAGE_GUARD_CF_INSTRUMENTED: u32 = 0x0000_0100;
+const IMAGE_GUARD_CFW_INSTRUMENTED: u32 = 0x0000_0200;
+const IMAGE_GUARD_CF_FUNCTION_TABLE_PRESENT: u32 = 0x0000_0400;
+const IMAGE_GUARD_PROTECT_DELAYLOAD_IAT: u32 = 0x0000_1000;
+const IMAGE_GUARD_CF_EXPORT_SUPPRESSION_INFO_PRESENT: u32 = 0x0000_4000;
+const IMAGE_GUARD_CF_ENABLE_EXPORT_SUPPRESSION: u32 = 0x0000_8000;
+const IMAGE_GUARD_CF_LONGJUMP_TABLE_PRESENT: u32 = 0x0001_0000;
+const IMAGE_GUARD_EH_CONTINUATION_TABLE_PRESENT: u32 = 0x0040_0000;
+const IMAGE_GUARD_XFG_ENABLED: u32 = 0x0080_0000;
+
+/* IMAGE_RESOURCE_DIRECTORY_ENTRY high bits: name is a string / offset points at a subdirectory */
+const IMAGE_RESOURCE_NAME_IS_STRING: u32 = 0x8000_0000;
+const IMAGE_RESOURCE_DATA_IS_DIRECTORY: u32 = 0x8000_0000;
+const RT_MANIFEST: u32 = 24;
+
+#[derive(Serialize)]
+struct PeDebugRecord
+{
+    debug_type: String,
+    size: u32,
+    rva: u32,
+    file_offset: u32,
+    pdb_path: Option<String>,
+    guid: Option<String>,
+    age: Option<u32>,
+    pdb_exists: Option<bool>
+}
+
+#[derive(Serialize)]
+struct AuthenticodeRecord
+{
+    signed: bool,
+    table_offset: u32,
+    table_size: u32,
+    revision: Option<String>,
+    certificate_type: Option<String>,
+    decoded: bool,
+    signer_cn: Option<String>,
+    issuer_cn: Option<String>,
+    not_before: Option<String>,
+    not_after: Option<String>,
+    expired: Option<bool>
+}
+
+#[derive(Serialize)]
+struct ClrHeaderRecord
+{
+    runtime_version: String,
+    metadata_version: Option<String>,
+    flags: Vec<String>,
+    metadata_rva: u32,
+    metadata_size: u32,
+    entry_point: Option<String>,
+    strong_name_rva: u32,
+    strong_name_size: u32,
+    strong_name_signed: bool,
+    platform: String,
+    aslr: bool
+}
+
+#[derive(Serialize)]
+struct PeResourceRecord
+{
+    resource_type: String,
+    name: String,
+    language: u32,
+    data_rva: u32,
+    size: u32
+}
+
+#[derive(Serialize)]
+struct PeImportRecord
+{
+    dll: String,
+    name: String,
+    ordinal: u16,
+    /* Address of the IAT slot the loader patches (goblin's `offset`, an RVA despite the name) */
+    iat_rva: usize
+}
+
+#[derive(Serialize)]
+struct PeSecurityRecord
+{
+    nx: bool,
+    aslr: bool,
+    high_entropy_va: bool,
+    cfg: bool,
+    stack_cookie: bool,
+    base_relocations: usize,
+    base_relocation_blocks: usize
+}
+
+#[derive(Serialize)]
+struct PeLoadConfigRecord
+{
+    present: bool,
+    size: u32,
+    time_date_stamp: u32,
+    version: String,
+    /* Pointer fields are VAs, None when the directory is too old/small to carry them */
+    security_cookie: Option<u64>,
+    stack_cookie: bool,
+    se_handler_table: Option<u64>,
+    se_handler_table_rva: Option<u64>,
+    se_handler_count: Option<u64>,
+    guard_cf_check_function_pointer: Option<u64>,
+    guard_cf_dispatch_function_pointer: Option<u64>,
+    guard_cf_function_table: Option<u64>,
+    guard_cf_function_count: Option<u64>,
+    guard_flags: Option<u32>,
+    guard_flag_names: Vec<String>,
+    dynamic_value_reloc_table: Option<u64>,
+    /* CHPEMetadataPointer, ARM64X/ARM64EC hybrid binaries */
+    hybrid_metadata_pointer: Option<u64>,
+    guard_eh_continuation_table: Option<u64>,
+    guard_eh_continuation_count: Option<u64>
+}
+
+#[derive(Serialize)]
+struct PeEntropyRecord
+{
+    name: String,
+    virtual_size: u32,
+    raw_size: u32,
+    entropy: f64,
+    indicator: String,
+    known_packer_name: bool
+}
+
+#[derive(Serialize)]
+struct PeBaseRelocBlockRecord
+{
+    page_rva: u32,
+    block_size: u32,
+    relocations: usize,
+    types: Vec<TypeCountRecord>
+}
+
+#[derive(Serialize)]
+struct PeBaseRelocRecord
+{
+    present: bool,
+    directory_size: u32,
+    block_count: usize,
+    /* IMAGE_REL_BASED_ABSOLUTE padding entries are not counted */
+    relocation_count: usize,
+    types: Vec<TypeCountRecord>,
+    blocks: Vec<PeBaseRelocBlockRecord>
+}
+
+#[derive(Serialize)]
+struct PeLoadTimeRecord
+{
+    imports: usize,
+    imported_dlls: usize,
+    base_relocations: usize,
+    relocated_pages: usize,
+    tls_callbacks: usize,
+    warm_load_ms: f64,
+    cold_load_ms: f64,
+    /* More than PE_SLOW_IMPORT_COUNT imported functions */
+    potentially_slow: bool
+}
+
+#[derive(Serialize)]
+struct PeCfgRecord
+{
+    /* IMAGE_DLLCHARACTERISTICS_GUARD_CF, what the loader actually honours */
+    enabled: bool,
+    instrumented: bool,
+    export_suppression: bool,
+    xfg: bool,
+    guard_flags: Vec<String>,
+    /* Load config fields (VAs), None when the directory is too old/small to carry them */
+    check_function_pointer: Option<u64>,
+    dispatch_function_pointer: Option<u64>,
+    function_table: Option<u64>,
+    function_count: Option<u64>,
+    eh_continuation_table: Option<u64>,
+    eh_continuation_count: Option<u64>
+}
+
+fn return_pe_resource_type(type_id: u32) -> String
+{
+    match type_id
+    {
+        1 => String::from("RT_CURSOR"),
+        2 => String::from("RT_BITMAP"),
+        3 => String::from("RT_ICON"),
+        4 => String::from("RT_MENU"),
+        5 => String::from("RT_DIALOG"),
+        6 => String::from("RT_STRING"),
+        7 => String::from("RT_FONTDIR"),
+        8 => String::from("RT_FONT"),
+        9 => String::from("RT_ACCELERATOR"),
+        10 => String::from("RT_RCDATA"),
+        11 => String::from("RT_MESSAGETABLE"),
+        12 => String::from("RT_GROUP_CURSOR"),
+        14 => String::from("RT_GROUP_ICON"),
+        16 => String::from("RT_VERSION"),
+        17 => String::from("RT_DLGINCLUDE"),
+        19 => String::from("RT_PLUGPLAY"),
+        20 => String::from("RT_VXD"),
+        21 => String::from("RT_ANICURSOR"),
+        22 => String::from("RT_ANIICON"),
+        23 => String::from("RT_HTML"),
+        RT_MANIFEST => String::from("RT_MANIFEST"),
+
+        _ => format!("#{type_id}")
+    }
+}
+
+
+/* Entries of an IMAGE_RESOURCE_DIRECTORY as (Ok(id) | Err(name), OffsetToData), offsets are relative to the resource root */
+fn return_resource_directory_entries(data: &[u8], base: usize, directory: usize) -> Vec<(Result<u32, String>, u32)>
+{
+    let named: usize = read_le_u16(data, directory + 12).unwrap_or(0) as usize;
+    let ids: usize = read_le_u16(data, directory + 14).unwrap_or(0) as usize;
+
+    (0..named + ids).filter_map(|index| {
+        let entry: usize = directory + 16 + index * 8;
+        let name: u32 = read_le_u32(data, entry)?;
+        let offset: u32 = read_le_u32(data, entry + 4)?;
+
+        /* IMAGE_RESOURCE_DIR_STRING_U: u16 length followed by that many UTF-16LE code units */
+        let identifier: Result<u32, String> = match name & IMAGE_RESOURCE_NAME_IS_STRING != 0
+        {
+            true => {
+                let string: usize = base + (name & !IMAGE_RESOURCE_NAME_IS_STRING) as usize;
+                let length: usize = read_le_u16(data, string).unwrap_or(0) as usize;
+
+                let units: Vec<u16> = (0..length).filter_map(|unit| read_le_u16(data, string + 2 + unit * 2)).collect();
+
+                Err(String::from_utf16_lossy(&units))
+            },
+
+            false => Ok(name)
+        };
+
+        Some((identifier, offset))
+    }).collect()
+}
+
+
+fn return_resource_subdirectory(data: &[u8], base: usize, offset: u32) -> Vec<(Result<u32, String>, u32)>
+{
+    match offset & IMAGE_RESOURCE_DATA_IS_DIRECTORY != 0
+    {
+        true => return_resource_directory_entries(data, base, base + (offset & !IMAGE_RESOURCE_DATA_IS_DIRECTORY) as usize),
+        false => Vec::new()
+    }
+}
+
+
+fn read_le_u16(data: &[u8], offset: usize) -> Option<u16>
+{
+    data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+
+fn read_le_u64(data: &[u8], offset: usize) -> Option<u64>
+{
+    data.get(offset..offset + 8).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+}
+
+
+/* Shannon entropy in bits per byte, 0.0 for constant data up to 8.0 for uniformly random bytes */
+fn return_shannon_entropy(bytes: &[u8]) -> f64
+{
+    if bytes.is_empty()
+    {
+        return 0.0;
+    }
+
+    let mut counts: [usize; 256] = [0; 256];
+
+    for &byte in bytes
+    {
+        counts[byte as usize] += 1;
+    }
+
+    counts.iter().filter(|&&count| count != 0).map(|&count| {
+        let probability: f64 = count as f64 / bytes.len() as f64;
+
+        -probability * probability.log2()
+    }).sum()
+}
+
+/* Translate a PE relative virtual address into a file offset */
+fn return_pe_rva_offset(pe: &PE, rva: u32) -> Option<usize>
+{
+    let file_alignment: u32 = pe.header.optional_header.map_or(0x200, |opt_hdr| opt_hdr.windows_fields.file_alignment);
+
+    find_offset(rva as usize, &pe.sections, file_alignment, &ParseOptions::default())
+}
+
+
+/* IMAGE_LOAD_CONFIG_DIRECTORY bytes, empty when the binary has none */
+fn return_pe_load_config_directory<'a>(pe: &PE, data: &'a [u8]) -> &'a [u8]
+{
+    let load_config = pe.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_load_config_table());
+
+    load_config
+        .and_then(|dd| return_pe_rva_offset(pe, dd.virtual_address))
+        .and_then(|offset| {
+            /* The directory's own Size field decides which trailing fields exist */
+            let size: usize = read_le_u32(data, offset)? as usize;
+            data.get(offset..offset + size)
+        })
+        .unwrap_or(&[])
+}
+
+
+fn return_pe_guard_flags(guard_flags: u32) -> Vec<String>
+{
+    [
+        (IMAGE_GUARD_CF_INSTRUMENTED, "CF_INSTRUMENTED"),
+        (IMAGE_GUARD_CFW_INSTRUMENTED, "CFW_INSTRUMENTED"),
+        (IMAGE_GUARD_CF_FUNCTION_TABLE_PRESENT, "CF_FUNCTION_TABLE_PRESENT"),
+        (IMAGE_GUARD_PROTECT_DELAYLOAD_IAT, "PROTECT_DELAYLOAD_IAT"),
+        (IMAGE_GUARD_CF_EXPORT_SUPPRESSION_INFO_PRESENT, "CF_EXPORT_SUPPRESSION_INFO_PRESENT"),
+        (IMAGE_GUARD_CF_ENABLE_EXPORT_SUPPRESSION, "CF_ENABLE_EXPORT_SUPPRESSION"),
+        (IMAGE_GUARD_CF_LONGJUMP_TABLE_PRESENT, "CF_LONGJUMP_TABLE_PRESENT"),
+        (IMAGE_GUARD_EH_CONTINUATION_TABLE_PRESENT, "EH_CONTINUATION_TABLE_PRESENT"),
+        (IMAGE_GUARD_XFG_ENABLED, "XFG_ENABLED")
+    ]
+    .iter()
+    .filter(|(flag, _)| guard_flags & flag != 0)
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
+
+
+/* Pointer sized load config field at its 32-bit/64-bit layout offset, None past the directory's Size */
+fn return_pe_load_config_field(pe: &PE, directory: &[u8], offset32: usize, offset64: usize) -> Option<u64>
+{
+    match pe.is_64
+    {
+        true => read_le_u64(directory, offset64),
+        false => read_le_u32(directory, offset32).map(|value| value as u64)
+    }
+}
+
+
+fn return_pe_load_config(pe: &PE, data: &[u8]) -> PeLoadConfigRecord
+{
+    let directory: &[u8] = return_pe_load_config_directory(pe, data);
+    let image_base: u64 = pe.header.optional_header.map_or(0, |opt_hdr| opt_hdr.windows_fields.image_base);
+
+    let field = |offset32: usize, offset64: usize| -> Option<u64> { return_pe_load_config_field(pe, directory, offset32, offset64) };
+
+    let guard_flags: Option<u32> = match pe.is_64
+    {
+        true => read_le_u32(directory, 144),
+        false => read_le_u32(directory, 88)
+    };
+
+    let security_cookie: Option<u64> = field(60, 88);
+    let se_handler_table: Option<u64> = field(64, 96);
+
+    PeLoadConfigRecord {
+        present: !directory.is_empty(),
+        size: read_le_u32(directory, 0).unwrap_or(0),
+        time_date_stamp: read_le_u32(directory, 4).unwrap_or(0),
+        version: format!("{}.{}", read_le_u16(directory, 8).unwrap_or(0), read_le_u16(directory, 10).unwrap_or(0)),
+        security_cookie,
+        stack_cookie: security_cookie.is_some_and(|va| va != 0),
+        se_handler_table,
+        /* The directory stores a VA, the RVA is what section lookups want */
+        se_handler_table_rva: se_handler_table.filter(|va| *va != 0).map(|va| va.wrapping_sub(image_base)),
+        se_handler_count: field(68, 104),
+        guard_cf_check_function_pointer: field(72, 112),
+        guard_cf_dispatch_function_pointer: field(76, 120),
+        guard_cf_function_table: field(80, 128),
+        guard_cf_function_count: field(84, 136),
+        guard_flags,
+        guard_flag_names: return_pe_guard_flags(guard_flags.unwrap_or(0)),
+        dynamic_value_reloc_table: field(120, 192),
+        hybrid_metadata_pointer: field(124, 200),
+        guard_eh_continuation_table: field(164, 264),
+        guard_eh_continuation_count: field(168, 272)
+    }
+}
+
+
+fn return_pe_base_reloc_type(machine: u16, reloc_type: u8) -> &'static str
+{
+    match (reloc_type, machine)
+    {
+        (0, _) => "ABSOLUTE",
+        (1, _) => "HIGH",
+        (2, _) => "LOW",
+        (3, _) => "HIGHLOW",
+        (4, _) => "HIGHADJ",
+        (5, COFF_MACHINE_ARM | COFF_MACHINE_ARMNT) => "ARM_MOV32",
+        (5, _) => "MIPS_JMPADDR",
+        (7, _) => "THUMB_MOV32",
+        (9, _) => "MIPS_JMPADDR16",
+        (10, _) => "DIR64",
+
+        _ => "UNKNOWN"
+    }
+}
+
+
+/* Walks the IMAGE_BASE_RELOCATION blocks of data directory 5, each a page RVA + block size followed by u16 (type << 12 | offset) entries */
+/* AddressOfCallBacks of IMAGE_TLS_DIRECTORY, a VA of a null terminated array of callback VAs (goblin 0.7 does not parse the TLS directory) */
+fn return_pe_tls_callbacks(pe: &PE, data: &[u8]) -> Vec<u64>
+{
+    let image_base: u64 = pe.header.optional_header.map_or(0, |opt_hdr| opt_hdr.windows_fields.image_base);
+    let directory = pe.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_tls_table());
+
+    let read_pointer = |offset: usize| -> Option<u64> {
+        match pe.is_64
+        {
+            true => read_le_u64(data, offset),
+            false => read_le_u32(data, offset).map(|value| value as u64)
+        }
+    };
+
+    let pointer_size: usize = match pe.is_64 { true => 8, false => 4 };
+    let mut callbacks: Vec<u64> = Vec::new();
+
+    let callback_array: Option<usize> = directory
+        .filter(|dd| dd.size != 0)
+        .and_then(|dd| return_pe_rva_offset(pe, dd.virtual_address))
+        .and_then(|offset| read_pointer(offset + 3 * pointer_size))
+        .filter(|va| *va != 0)
+        .and_then(|va| return_pe_rva_offset(pe, va.wrapping_sub(image_base) as u32));
+
+    if let Some(mut offset) = callback_array
+    {
+        while let Some(callback) = read_pointer(offset).filter(|va| *va != 0)
+        {
+            callbacks.push(callback);
+            offset += pointer_size;
+        }
+    }
+
+    callbacks
+}
+
+
+fn return_pe_base_relocs(pe: &PE, data: &[u8]) -> PeBaseRelocRecord
+{
+    let machine: u16 = pe.header.coff_header.machine;
+    let directory = pe.header.optional_header.and_then(|opt_hdr| *opt_hdr.data_directories.get_base_relocation_table());
+
+    let (offset, directory_size): (Option<usize>, u32) = match directory
+    {
+        Some(dd) if dd.size != 0 => (return_pe_rva_offset(pe, dd.virtual_address), dd.size),
+        _ => (None, 0)
+    };
+
+    let mut blocks: Vec<PeBaseRelocBlockRecord> = Vec::new();
+    let mut totals: Vec<TypeCountRecord> = Vec::new();
+
+    if let Some(start) = offset
+    {
+        let end: usize = (start + directory_size as usize).min(data.len());
+        let mut block: usize = start;
+
+        while block + 8 <= end
+        {
+            let page_rva: u32 = read_le_u32(data, block).unwrap_or(0);
+            let block_size: u32 = read_le_u32(data, block + 4).unwrap_or(0);
+
+            /* A block never holds less than its own header, anything smaller is corrupt or trailing padding */
+            if block_size < 8
+            {
+                break;
+            }
+
+            let mut types: Vec<TypeCountRecord> = Vec::new();
+            let mut relocations: usize = 0;
+            let mut entry: usize = block + 8;
+            let block_end: usize = (block + block_size as usize).min(end);
+
+            while entry + 2 <= block_end
+            {
+                let reloc_type: u8 = (read_le_u16(data, entry).unwrap_or(0) >> 12) as u8;
+                let type_name: &str = return_pe_base_reloc_type(machine, reloc_type);
+
+                /* HIGHADJ carries the low 16 bits of the adjustment in the following slot */
+                entry += match reloc_type
+                {
+                    4 => 4,
+                    _ => 2
+                };
+
+                if reloc_type != 0
+                {
+                    relocations += 1;
+                }
+
+                for counts in [&mut types, &mut totals]
+                {
+                    match counts.iter_mut().find(|t| t.type_name == type_name)
+                    {
+                        Some(type_count) => type_count.count += 1,
+                        None => counts.push(TypeCountRecord { type_name: type_name.to_string(), count: 1 })
+                    }
+                }
+            }
+
+            blocks.push(PeBaseRelocBlockRecord { page_rva, block_size, relocations, types });
+            block += block_size as usize;
+        }
+    }
+
+    totals.sort_by_key(|record| std::cmp::Reverse(record.count));
+
+    PeBaseRelocRecord {
+        present: offset.is_some(),
+        directory_size,
+        block_count: blocks.len(),
+        relocation_count: blocks.iter().map(|block| block.relocations).sum(),
+        types: totals,
+        blocks
+    }
+}
+
+
+/* Control Flow Guard state from DllCharacteristics and the IMAGE_LOAD_CONFIG_DIRECTORY32/64 */
+fn return_pe_cfg(pe: &PE, data: &[u8]) -> PeCfgRecord
+{
+    let dll_characteristics: u16 = pe.header.optional_header.map_or(0, |opt_hdr| opt_hdr.windows_fields.dll_characteristics);
+    let directory: &[u8] = return_pe_load_config_directory(pe, data);
+
+    let field = |offset32: usize, offset64: usize| -> Option<u64> { return_pe_load_config_field(pe, directory, offset32, offset64) };
+
+    let guard_flags: u32 = match pe.is_64
+    {
+        true => read_le_u32(directory, 144),
+        false => read_le_u32(directory, 88)
+    }.unwrap_or(0);
+
+    PeCfgRecord {
+        enabled: dll_characteristics & IMAGE_DLLCHARACTERISTICS_GUARD_CF != 0,
+        instrumented: guard_flags & IMAGE_GUARD_CF_INSTRUMENTED != 0,
+        export_suppression: guard_flags & IMAGE_GUARD_CF_ENABLE_EXPORT_SUPPRESSION != 0,
+        xfg: guard_flags & IMAGE_GUARD_XFG_ENABLED != 0,
+
+        guard_flags: return_pe_guard_flags(guard_flags),
+
+        check_function_pointer: field(72, 112),
+        dispatch_function_pointer: field(76, 120),
+        function_table: field(80, 128),
+        function_count: field(84, 136),
+        eh_continuation_table: field(164, 264),
+        eh_continuation_count: field(168, 272)
+    }
+}
+
+fn return_clr_flags(flags: u32) -> Vec<String>
+{
+    [
+        (COMIMAGE_FLAGS_ILONLY, "IL_ONLY"),
+        (COMIMAGE_FLAGS_32BITREQUIRED, "32BIT_REQUIRED"),
+        (COMIMAGE_FLAGS_IL_LIBRARY, "IL_LIBRARY"),
+        (COMIMAGE_FLAGS_STRONGNAMESIGNED, "STRONGNAMESIGNED"),
+        (COMIMAGE_FLAGS_NATIVE_ENTRYPOINT, "NATIVE_ENTRYPOINT"),
+        (COMIMAGE_FLAGS_TRACKDEBUGDATA, "TRACKDEBUGDATA"),
+        (COMIMAGE_FLAGS_32BITPREFERRED, "32BIT_PREFERRED")
+    ]
+    .iter()
+    .filter(|(mask, _)| flags & mask != 0)
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
+
+
+/* PKCS#7 SignedData -> the certificate matching the first SignerInfo (falls back to the first embedded one) */
+fn return_authenticode_signer(pkcs7: &[u8]) -> Option<Certificate>
+{
+    /* bCertificate is padded to 8 bytes, so only the leading DER object is decoded */
+    let content_info: ContentInfo = ContentInfo::decode(&mut SliceReader::new(pkcs7).ok()?).ok()?;
+    let signed_data: SignedData = content_info.content.decode_as::<SignedData>().ok()?;
+
+    let certs: Vec<Certificate> = signed_data.certificates?.0.into_vec().into_iter()
+        .filter_map(|choice| match choice
+        {
+            CertificateChoices::Certificate(cert) => Some(cert),
+            _ => None
+        })
+        .collect();
+
+    let signer_idx: usize = signed_data.signer_infos.0.iter().next()
+        .and_then(|signer_info| match &signer_info.sid
+        {
+            SignerIdentifier::IssuerAndSerialNumber(id) => certs.iter().position(|cert| {
+                cert.tbs_certificate.issuer == id.issuer && cert.tbs_certificate.serial_number == id.serial_number
+            }),
+
+            _ => None
+        })
+        .unwrap_or(0);
+
+    certs.into_iter().nth(signer_idx)
+}
+
+
+fn return_x509_common_name(name: &x509_cert::name::Name) -> Option<String>
+{
+    name.0.iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .find(|atv| atv.oid == X509_COMMON_NAME_OID)
+        .map(|atv| String::from_utf8_lossy(atv.value.value()).to_string())
+}
+
+
+/* Mixed endian GUID layout: u32-u16-u16 little endian followed by 8 raw bytes */
+fn return_pe_guid(guid: &[u8]) -> String
+{
+    format!("{:08X}-{:04X}-{:04X}-{}-{}",
+        read_le_u32(guid, 0).unwrap_or(0),
+        read_le_u16(guid, 4).unwrap_or(0),
+        read_le_u16(guid, 6).unwrap_or(0),
+        guid[8..10].iter().map(|b| format!("{:02X}", b)).collect::<String>(),
+        guid[10..16].iter().map(|b| format!("{:02X}", b)).collect::<String>()
+    )
+}
+
+
+fn return_pe_debug_type(debug_type: u32) -> String
+{
+    match debug_type
+    {
+        0 => "IMAGE_DEBUG_TYPE_UNKNOWN".to_string(),
+        1 => "IMAGE_DEBUG_TYPE_COFF".to_string(),
+        2 => "IMAGE_DEBUG_TYPE_CODEVIEW".to_string(),
+        3 => "IMAGE_DEBUG_TYPE_FPO".to_string(),
+        4 => "IMAGE_DEBUG_TYPE_MISC".to_string(),
+        5 => "IMAGE_DEBUG_TYPE_EXCEPTION".to_string(),
+        6 => "IMAGE_DEBUG_TYPE_FIXUP".to_string(),
+        7 => "IMAGE_DEBUG_TYPE_OMAP_TO_SRC".to_string(),
+        8 => "IMAGE_DEBUG_TYPE_OMAP_FROM_SRC".to_string(),
+        9 => "IMAGE_DEBUG_TYPE_BORLAND".to_string(),
+        10 => "IMAGE_DEBUG_TYPE_RESERVED10".to_string(),
+        11 => "IMAGE_DEBUG_TYPE_CLSID".to_string(),
+        12 => "IMAGE_DEBUG_TYPE_VC_FEATURE".to_string(),
+        13 => "IMAGE_DEBUG_TYPE_POGO".to_string(),
+        14 => "IMAGE_DEBUG_TYPE_ILTCG".to_string(),
+        15 => "IMAGE_DEBUG_TYPE_MPX".to_string(),
+        16 => "IMAGE_DEBUG_TYPE_REPRO".to_string(),
+        20 => "IMAGE_DEBUG_TYPE_EX_DLLCHARACTERISTICS".to_string(),
+
+        _ => format!("UNKNOWN ({debug_type})")
+    }
+}
@@ -0,0 +1,300 @@
+/*
+    2023 The BinaryMagic Authors.
+
+    GNU PL 3.0 (GPL-3.0) - All rights reserved.
+*/
+
+/* WebAssembly analysis */
+
+use super::*;
+
+impl ProgramArgumentMethod
+{
+    pub(crate) fn start_wasm_method_selector(&self, args: &Arguments, wasm_module: &WasmModule, flag: &str)
+    {
+        match &self
+        {
+            ProgramArgumentMethod::Sections => args.parse_wasm_sections(wasm_module),
+            ProgramArgumentMethod::DynamicSymbols => args.parse_wasm_exports(wasm_module),
+            ProgramArgumentMethod::DynamicLibraries => args.parse_wasm_imports(wasm_module),
+
+            _ => {
+                eprintln!("Error - option \"{}\" is not supported for WASM binaries!", flag);
+                std::process::exit(-1);
+            }
+        }
+    }
+}
+
+impl Arguments
+{
+    pub(crate) fn parse_wasm_sections(&self, wasm_module: &WasmModule)
+    {
+        if self.is_structured_output()
+        {
+            self.insert_document("wasm_sections", &wasm_module.sections);
+            return;
+        }
+
+        println!("\nWASM Sections =>");
+
+        let mut section_table: Table = self.new_table();
+
+        section_table.set_header(vec![
+                Cell::new("Section ID").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Section Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Offset").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Size").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &wasm_module.sections
+        {
+            section_table.add_row(vec![
+                Cell::new(record.id).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.name).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(format!("{:#x}", record.offset)),
+                Cell::new(format!("{} bytes", record.size)).fg(Color::Yellow)
+            ]);
+        }
+
+        println!("\n{section_table}");
+        println!("\nThere are {} sections in this WASM module.", wasm_module.sections.len());
+    }
+
+
+    pub(crate) fn parse_wasm_imports(&self, wasm_module: &WasmModule)
+    {
+        if self.is_structured_output()
+        {
+            self.insert_document("wasm_imports", &wasm_module.imports);
+            return;
+        }
+
+        println!("\nWASM Imports =>");
+
+        let mut import_table: Table = self.new_table();
+
+        import_table.set_header(vec![
+                Cell::new("Module").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Kind").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &wasm_module.imports
+        {
+            import_table.add_row(vec![
+                Cell::new(&record.module).fg(Color::Yellow),
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.kind).fg(Color::DarkGreen).add_attribute(Attribute::Italic)
+            ]);
+        }
+
+        println!("\n{import_table}");
+    }
+
+
+    pub(crate) fn parse_wasm_exports(&self, wasm_module: &WasmModule)
+    {
+        if self.is_structured_output()
+        {
+            self.insert_document("wasm_exports", &wasm_module.exports);
+            return;
+        }
+
+        println!("\nWASM Exports =>");
+
+        let mut export_table: Table = self.new_table();
+
+        export_table.set_header(vec![
+                Cell::new("Symbol Name").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Kind").fg(Color::Green).add_attribute(Attribute::Bold),
+                Cell::new("Index").fg(Color::Green).add_attribute(Attribute::Bold)
+            ]);
+
+        for record in &wasm_module.exports
+        {
+            export_table.add_row(vec![
+                Cell::new(&record.name).fg(Color::DarkGrey).add_attribute(Attribute::Bold),
+                Cell::new(&record.kind).fg(Color::DarkGreen).add_attribute(Attribute::Italic),
+                Cell::new(record.index)
+            ]);
+        }
+
+        println!("\n{export_table}");
+    }
+}
+
+
+enum WasmSectionType
+{
+    Custom(String),     /* id 0, carries its own name (name, producers, .debug_*) */
+    Type,
+    Import,
+    Function,
+    Table,
+    Memory,
+    Global,
+    Export,
+    Start,
+    Element,
+    Code,
+    Data,
+    DataCount,
+    Tag,
+    Unknown(u8)
+}
+
+impl WasmSectionType
+{
+    fn get_type(&self) -> String
+    {
+        match self
+        {
+            WasmSectionType::Custom(name) => format!("Custom ({name})"),
+            WasmSectionType::Type => String::from("Type"),
+            WasmSectionType::Import => String::from("Import"),
+            WasmSectionType::Function => String::from("Function"),
+            WasmSectionType::Table => String::from("Table"),
+            WasmSectionType::Memory => String::from("Memory"),
+            WasmSectionType::Global => String::from("Global"),
+            WasmSectionType::Export => String::from("Export"),
+            WasmSectionType::Start => String::from("Start"),
+            WasmSectionType::Element => String::from("Element"),
+            WasmSectionType::Code => String::from("Code"),
+            WasmSectionType::Data => String::from("Data"),
+            WasmSectionType::DataCount => String::from("DataCount"),
+            WasmSectionType::Tag => String::from("Tag"),
+            WasmSectionType::Unknown(id) => format!("Unknown ({id})")
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WasmSectionRecord
+{
+    id: u8,
+    name: String,
+    offset: u64,
+    size: u64
+}
+
+#[derive(Serialize)]
+struct WasmImportRecord
+{
+    module: String,
+    name: String,
+    kind: String
+}
+
+#[derive(Serialize)]
+struct WasmExportRecord
+{
+    name: String,
+    kind: String,
+    index: u32
+}
+
+/* Everything BinaryMagic reports about a WASM module, collected in a single pass over its sections */
+pub(crate) struct WasmModule
+{
+    sections: Vec<WasmSectionRecord>,
+    imports: Vec<WasmImportRecord>,
+    exports: Vec<WasmExportRecord>
+}
+
+fn return_wasm_section_type(payload: &Payload) -> WasmSectionType
+{
+    match payload
+    {
+        Payload::CustomSection(reader) => WasmSectionType::Custom(reader.name().to_string()),
+        Payload::TypeSection(_) => WasmSectionType::Type,
+        Payload::ImportSection(_) => WasmSectionType::Import,
+        Payload::FunctionSection(_) => WasmSectionType::Function,
+        Payload::TableSection(_) => WasmSectionType::Table,
+        Payload::MemorySection(_) => WasmSectionType::Memory,
+        Payload::GlobalSection(_) => WasmSectionType::Global,
+        Payload::ExportSection(_) => WasmSectionType::Export,
+        Payload::StartSection { .. } => WasmSectionType::Start,
+        Payload::ElementSection(_) => WasmSectionType::Element,
+        Payload::CodeSectionStart { .. } => WasmSectionType::Code,
+        Payload::DataSection(_) => WasmSectionType::Data,
+        Payload::DataCountSection { .. } => WasmSectionType::DataCount,
+        Payload::TagSection(_) => WasmSectionType::Tag,
+        Payload::UnknownSection { id, .. } => WasmSectionType::Unknown(*id),
+
+        _ => WasmSectionType::Unknown(0xff)
+    }
+}
+
+
+pub(crate) fn return_wasm_module(data: &[u8]) -> WasmModule
+{
+    let mut wasm_module: WasmModule = WasmModule { sections: Vec::new(), imports: Vec::new(), exports: Vec::new() };
+
+    for payload in Parser::new(0).parse_all(data)
+    {
+        let payload: Payload = match payload
+        {
+            Ok(payload) => payload,
+
+            Err(err) => {
+                eprintln!("Error - failed to parse WASM module: {err}");
+                std::process::exit(-1);
+            }
+        };
+
+        /* Ranges cover the section contents only, the id byte and LEB128 size prefix are excluded */
+        if let Some((id, range)) = payload.as_section()
+        {
+            wasm_module.sections.push(WasmSectionRecord {
+                id,
+                name: return_wasm_section_type(&payload).get_type(),
+                offset: range.start,
+                size: range.end - range.start
+            });
+        }
+
+        match payload
+        {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports().flatten()
+                {
+                    wasm_module.imports.push(WasmImportRecord {
+                        module: import.module.to_string(),
+                        name: import.name.to_string(),
+                        kind: match import.ty
+                        {
+                            TypeRef::Func(_) | TypeRef::FuncExact(_) => String::from("func"),
+                            TypeRef::Table(_) => String::from("table"),
+                            TypeRef::Memory(_) => String::from("memory"),
+                            TypeRef::Global(_) => String::from("global"),
+                            TypeRef::Tag(_) => String::from("tag")
+                        }
+                    });
+                }
+            },
+
+            Payload::ExportSection(reader) => {
+                for export in reader.into_iter().flatten()
+                {
+                    wasm_module.exports.push(WasmExportRecord {
+                        name: export.name.to_string(),
+                        kind: match export.kind
+                        {
+                            ExternalKind::Func | ExternalKind::FuncExact => String::from("func"),
+                            ExternalKind::Table => String::from("table"),
+                            ExternalKind::Memory => String::from("memory"),
+                            ExternalKind::Global => String::from("global"),
+                            ExternalKind::Tag => String::from("tag")
+                        },
+                        index: export.index
+                    });
+                }
+            },
+
+            _ => ()
+        }
+    }
+
+    wasm_module
+}